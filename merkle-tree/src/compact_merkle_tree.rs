@@ -0,0 +1,393 @@
+// Compact binary layout of ConfigMerkleTree, all integers are little endian.
+//
+// | magic [u8; 8] | format_version u64 | merkle_root [u8; 32] | version u64 | node_count u64 | bucket_count u64 |
+// | bucket_count * (escrow_owner [u8; 32], record_offset u64) |
+// | node_count * record |
+//
+// Buckets form an open addressing hash table keyed by escrow owner, so a node is found in O(1) without reading the records.
+// Record layout:
+// | escrow_owner [u8; 32] | registry_index u8 | deposit_cap u64 | flags u8 | price_discount_bps u16 |
+// | immediate_release_bps u16 | vest_duration u64 | proof_len u8 | proof_len * [u8; 32] |
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use memmap2::Mmap;
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    config_merkle_tree::{ConfigMerkleTree, Result},
+    error::MerkleTreeError::{self, MerkleValidationError},
+    tree_node::TreeNode,
+    utils::derive_merkle_root_config,
+};
+
+const MAGIC: &[u8; 8] = b"PSMKTREE";
+const FORMAT_VERSION: u64 = 1;
+
+const HEADER_SIZE: usize = 72;
+const BUCKET_SIZE: usize = 40;
+const RECORD_FIXED_SIZE: usize = 55;
+const PROOF_ELEMENT_SIZE: usize = 32;
+
+const EMPTY_BUCKET_OFFSET: u64 = u64::MAX;
+
+const PRICE_DISCOUNT_FLAG: u8 = 1;
+const VESTING_OVERRIDE_FLAG: u8 = 1 << 1;
+
+fn get_bucket_count(node_count: usize) -> usize {
+    // Keep the load factor at most 0.5 to bound the probing length
+    node_count.saturating_mul(2).next_power_of_two()
+}
+
+fn get_start_bucket(escrow_owner: &Pubkey, bucket_count: usize) -> usize {
+    // Pubkey bytes are uniformly distributed, therefore used as the hash directly
+    let bytes = escrow_owner.to_bytes();
+    let hash = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    (hash as usize) & (bucket_count - 1)
+}
+
+fn get_record_size(tree_node: &TreeNode) -> Result<usize> {
+    let proof_len = tree_node.proof.as_ref().map(Vec::len).ok_or_else(|| {
+        MerkleValidationError(format!("Missing proof of {}", tree_node.escrow_owner))
+    })?;
+    Ok(RECORD_FIXED_SIZE + proof_len * PROOF_ELEMENT_SIZE)
+}
+
+fn write_record<W: Write>(writer: &mut W, tree_node: &TreeNode) -> Result<()> {
+    let proof = tree_node.proof.as_deref().unwrap_or_default();
+    let proof_len = u8::try_from(proof.len())
+        .map_err(|_| MerkleValidationError("Proof is too long".to_string()))?;
+
+    let mut flags = 0;
+    if tree_node.price_discount_bps.is_some() {
+        flags |= PRICE_DISCOUNT_FLAG;
+    }
+    if tree_node.immediate_release_bps.is_some() && tree_node.vest_duration.is_some() {
+        flags |= VESTING_OVERRIDE_FLAG;
+    }
+
+    writer.write_all(tree_node.escrow_owner.as_ref())?;
+    writer.write_all(&[tree_node.registry_index])?;
+    writer.write_all(&tree_node.deposit_cap.to_le_bytes())?;
+    writer.write_all(&[flags])?;
+    writer.write_all(
+        &tree_node
+            .price_discount_bps
+            .unwrap_or_default()
+            .to_le_bytes(),
+    )?;
+    writer.write_all(
+        &tree_node
+            .immediate_release_bps
+            .unwrap_or_default()
+            .to_le_bytes(),
+    )?;
+    writer.write_all(&tree_node.vest_duration.unwrap_or_default().to_le_bytes())?;
+    writer.write_all(&[proof_len])?;
+    for proof_element in proof {
+        writer.write_all(proof_element)?;
+    }
+
+    Ok(())
+}
+
+impl ConfigMerkleTree {
+    /// Load a serialized merkle tree from file path. Files with the bin extension are read as compact tree
+    pub fn new_from_tree_file(path: &PathBuf) -> Result<Self> {
+        if path.extension().is_some_and(|extension| extension == "bin") {
+            CompactMerkleTree::open(path)?.to_config_merkle_tree()
+        } else {
+            ConfigMerkleTree::new_from_file(path)
+        }
+    }
+
+    /// Write the merkle tree in the compact binary layout to a filepath
+    pub fn write_to_compact_file(&self, path: &PathBuf) -> Result<()> {
+        let node_count = self.tree_nodes.len();
+        let bucket_count = get_bucket_count(node_count);
+
+        // Records are laid out in the tree node order, right after the buckets
+        let mut buckets = vec![(Pubkey::default(), EMPTY_BUCKET_OFFSET); bucket_count];
+        let mut record_offset = HEADER_SIZE + bucket_count * BUCKET_SIZE;
+
+        for tree_node in self.tree_nodes.iter() {
+            let mut bucket = get_start_bucket(&tree_node.escrow_owner, bucket_count);
+            while buckets[bucket].1 != EMPTY_BUCKET_OFFSET {
+                if buckets[bucket].0 == tree_node.escrow_owner {
+                    return Err(MerkleValidationError(
+                        "Duplicate escrow owners found".to_string(),
+                    ));
+                }
+                bucket = (bucket + 1) & (bucket_count - 1);
+            }
+            buckets[bucket] = (tree_node.escrow_owner, record_offset as u64);
+            record_offset += get_record_size(tree_node)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&self.merkle_root)?;
+        writer.write_all(&self.version.to_le_bytes())?;
+        writer.write_all(&(node_count as u64).to_le_bytes())?;
+        writer.write_all(&(bucket_count as u64).to_le_bytes())?;
+
+        for (escrow_owner, record_offset) in buckets.iter() {
+            writer.write_all(escrow_owner.as_ref())?;
+            writer.write_all(&record_offset.to_le_bytes())?;
+        }
+
+        for tree_node in self.tree_nodes.iter() {
+            write_record(&mut writer, tree_node)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Read only view over a merkle tree in the compact binary layout
+pub struct CompactMerkleTree<T: AsRef<[u8]>> {
+    data: T,
+    pub merkle_root: [u8; 32],
+    pub version: u64,
+    pub node_count: u64,
+    bucket_count: usize,
+}
+
+impl CompactMerkleTree<Mmap> {
+    /// Memory map a compact merkle tree file. Nodes are only read upon lookup.
+    pub fn open(path: &PathBuf) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: The file is expected not to be modified while it's mapped
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::new(mmap)
+    }
+}
+
+impl<T: AsRef<[u8]>> CompactMerkleTree<T> {
+    pub fn new(data: T) -> Result<Self> {
+        let bytes = data.as_ref();
+
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+            return Err(MerkleValidationError(
+                "Invalid compact merkle tree".to_string(),
+            ));
+        }
+
+        let format_version = read_u64(bytes, 8)?;
+        if format_version != FORMAT_VERSION {
+            return Err(MerkleValidationError(format!(
+                "Unsupported compact merkle tree format version {}",
+                format_version
+            )));
+        }
+
+        let merkle_root = read_array::<32>(bytes, 16)?;
+        let version = read_u64(bytes, 48)?;
+        let node_count = read_u64(bytes, 56)?;
+        let bucket_count = usize::try_from(read_u64(bytes, 64)?)
+            .map_err(|_| MerkleValidationError("Invalid bucket count".to_string()))?;
+
+        if !bucket_count.is_power_of_two()
+            || bytes.len() < HEADER_SIZE.saturating_add(bucket_count.saturating_mul(BUCKET_SIZE))
+        {
+            return Err(MerkleValidationError("Invalid bucket count".to_string()));
+        }
+
+        Ok(Self {
+            data,
+            merkle_root,
+            version,
+            node_count,
+            bucket_count,
+        })
+    }
+
+    pub fn get_merkle_root_config_pubkey(&self, presale: Pubkey, program_id: &Pubkey) -> Pubkey {
+        derive_merkle_root_config(&presale, self.version, program_id)
+    }
+
+    /// Find the node of the escrow owner through the bucket index
+    pub fn get_node(&self, escrow_owner: &Pubkey) -> Result<TreeNode> {
+        let bytes = self.data.as_ref();
+        let mut bucket = get_start_bucket(escrow_owner, self.bucket_count);
+
+        for _ in 0..self.bucket_count {
+            let bucket_offset = HEADER_SIZE + bucket * BUCKET_SIZE;
+            let record_offset = read_u64(bytes, bucket_offset + 32)?;
+
+            if record_offset == EMPTY_BUCKET_OFFSET {
+                break;
+            }
+
+            if read_array::<32>(bytes, bucket_offset)? == escrow_owner.to_bytes() {
+                let record_offset = usize::try_from(record_offset)
+                    .map_err(|_| MerkleValidationError("Invalid record offset".to_string()))?;
+                return read_record(bytes, record_offset).map(|(tree_node, _)| tree_node);
+            }
+
+            bucket = (bucket + 1) & (self.bucket_count - 1);
+        }
+
+        Err(MerkleTreeError::NodeNotFound(*escrow_owner))
+    }
+
+    /// Read all nodes in the tree node order
+    pub fn get_nodes(&self) -> Result<Vec<TreeNode>> {
+        let bytes = self.data.as_ref();
+        let mut record_offset = HEADER_SIZE + self.bucket_count * BUCKET_SIZE;
+        let mut tree_nodes = vec![];
+
+        for _ in 0..self.node_count {
+            let (tree_node, record_size) = read_record(bytes, record_offset)?;
+            tree_nodes.push(tree_node);
+            record_offset = add_offset(record_offset, record_size)?;
+        }
+
+        Ok(tree_nodes)
+    }
+
+    pub fn to_config_merkle_tree(&self) -> Result<ConfigMerkleTree> {
+        Ok(ConfigMerkleTree {
+            merkle_root: self.merkle_root,
+            version: self.version,
+            max_num_nodes: self.node_count,
+            tree_nodes: self.get_nodes()?,
+        })
+    }
+}
+
+fn get_truncated_error() -> MerkleTreeError {
+    MerkleValidationError("Compact merkle tree is truncated".to_string())
+}
+
+/// Offsets come from the file, so they're added with overflow check
+fn add_offset(offset: usize, size: usize) -> Result<usize> {
+    offset.checked_add(size).ok_or_else(get_truncated_error)
+}
+
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N]> {
+    bytes
+        .get(offset..add_offset(offset, N)?)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or_else(get_truncated_error)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64> {
+    read_array::<8>(bytes, offset).map(u64::from_le_bytes)
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    read_array::<2>(bytes, offset).map(u16::from_le_bytes)
+}
+
+/// Returns the node and the size of its record
+fn read_record(bytes: &[u8], offset: usize) -> Result<(TreeNode, usize)> {
+    let escrow_owner = Pubkey::new_from_array(read_array::<32>(bytes, offset)?);
+    let [registry_index] = read_array::<1>(bytes, add_offset(offset, 32)?)?;
+    let deposit_cap = read_u64(bytes, add_offset(offset, 33)?)?;
+    let [flags] = read_array::<1>(bytes, add_offset(offset, 41)?)?;
+    let price_discount_bps = read_u16(bytes, add_offset(offset, 42)?)?;
+    let immediate_release_bps = read_u16(bytes, add_offset(offset, 44)?)?;
+    let vest_duration = read_u64(bytes, add_offset(offset, 46)?)?;
+    let [proof_len] = read_array::<1>(bytes, add_offset(offset, 54)?)?;
+
+    let mut proof = Vec::with_capacity(proof_len.into());
+    for i in 0..usize::from(proof_len) {
+        proof.push(read_array::<32>(
+            bytes,
+            add_offset(offset, RECORD_FIXED_SIZE + i * PROOF_ELEMENT_SIZE)?,
+        )?);
+    }
+
+    let is_vesting_overridden = flags & VESTING_OVERRIDE_FLAG != 0;
+    let tree_node = TreeNode {
+        escrow_owner,
+        registry_index,
+        deposit_cap,
+        price_discount_bps: (flags & PRICE_DISCOUNT_FLAG != 0).then_some(price_discount_bps),
+        immediate_release_bps: is_vesting_overridden.then_some(immediate_release_bps),
+        vest_duration: is_vesting_overridden.then_some(vest_duration),
+        proof: Some(proof),
+    };
+
+    Ok((
+        tree_node,
+        RECORD_FIXED_SIZE + usize::from(proof_len) * PROOF_ELEMENT_SIZE,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_merkle_tree_lookup() {
+        let tree_nodes = (0..100u64)
+            .map(|i| TreeNode {
+                escrow_owner: Pubkey::new_unique(),
+                registry_index: (i % 3) as u8,
+                deposit_cap: i + 1,
+                price_discount_bps: (i % 2 == 0).then_some(500),
+                immediate_release_bps: (i % 5 == 0).then_some(10_000),
+                vest_duration: (i % 5 == 0).then_some(0),
+                proof: None,
+            })
+            .collect::<Vec<_>>();
+        let tree = ConfigMerkleTree::new(tree_nodes, 1).unwrap();
+
+        let path = std::env::temp_dir().join(format!("{}.bin", Pubkey::new_unique()));
+        tree.write_to_compact_file(&path).unwrap();
+
+        let compact_tree = CompactMerkleTree::open(&path).unwrap();
+        assert_eq!(compact_tree.merkle_root, tree.merkle_root);
+        assert_eq!(compact_tree.version, tree.version);
+
+        for tree_node in tree.tree_nodes.iter() {
+            assert_eq!(
+                &compact_tree.get_node(&tree_node.escrow_owner).unwrap(),
+                tree_node
+            );
+        }
+
+        assert!(matches!(
+            compact_tree.get_node(&Pubkey::new_unique()),
+            Err(MerkleTreeError::NodeNotFound(_))
+        ));
+
+        let config_merkle_tree = compact_tree.to_config_merkle_tree().unwrap();
+        assert_eq!(config_merkle_tree.tree_nodes, tree.tree_nodes);
+        config_merkle_tree.validate().unwrap();
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_compact_merkle_tree_corrupted_record_offset() {
+        let tree_node = TreeNode::new(Pubkey::new_unique(), 0, 1);
+        let tree = ConfigMerkleTree::new(vec![tree_node.clone()], 1).unwrap();
+
+        let path = std::env::temp_dir().join(format!("{}.bin", Pubkey::new_unique()));
+        tree.write_to_compact_file(&path).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        // Point the bucket of the node close to usize::MAX, so the record field offsets overflow
+        let bucket_offset = HEADER_SIZE
+            + get_start_bucket(&tree_node.escrow_owner, get_bucket_count(1)) * BUCKET_SIZE;
+        bytes[bucket_offset + 32..bucket_offset + 40]
+            .copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+
+        let compact_tree = CompactMerkleTree::new(bytes).unwrap();
+        assert!(matches!(
+            compact_tree.get_node(&tree_node.escrow_owner),
+            Err(MerkleValidationError(_))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt,
     fs::File,
     io::{BufReader, Write},
     path::PathBuf,
@@ -9,13 +10,16 @@ use std::{
 use indexmap::IndexMap;
 use presale::verify;
 use serde::{Deserialize, Serialize};
-use solana_program::{hash::hashv, pubkey::Pubkey};
+use solana_program::{
+    hash::{hashv, Hash},
+    pubkey::Pubkey,
+};
 
 use crate::{
     error::MerkleTreeError::{self, MerkleValidationError},
     merkle_tree::MerkleTree,
     tree_node::TreeNode,
-    utils::get_proof,
+    utils::{derive_merkle_root_config, get_proof},
 };
 
 // proof struct
@@ -23,8 +27,19 @@ use crate::{
 pub struct UserProof {
     /// merkle root config of alpha vault that user belongs
     pub merkle_root_config: String,
+    /// Presale registry index
+    pub registry_index: u8,
     /// Max deposit amount
     pub max_cap: u64,
+    /// Discount on the registry token price in bps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_discount_bps: Option<u16>,
+    /// Personal immediate release bps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub immediate_release_bps: Option<u16>,
+    /// Personal vest duration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vest_duration: Option<u64>,
     /// proof
     pub proof: Vec<[u8; 32]>,
 }
@@ -47,17 +62,97 @@ pub struct ConfigMerkleTree {
 
 pub type Result<T> = result::Result<T, MerkleTreeError>;
 
-impl ConfigMerkleTree {
-    pub fn new(tree_nodes: Vec<TreeNode>, version: u64) -> Result<Self> {
-        // Combine tree nodes with the same escrow owner, while retaining original order
-        let mut tree_nodes_map: IndexMap<Pubkey, TreeNode> = IndexMap::new();
-        for tree_node in tree_nodes {
-            let escrow_owner = tree_node.escrow_owner;
-            tree_nodes_map.insert(escrow_owner, tree_node);
+/// Leaf of an escrow owner present in both versions with different content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeNodeChange {
+    pub escrow_owner: Pubkey,
+    pub previous_registry_index: u8,
+    pub registry_index: u8,
+    pub previous_deposit_cap: u64,
+    pub deposit_cap: u64,
+    /// Whether the price discount or vesting override changed
+    pub is_terms_override_changed: bool,
+}
+
+/// Audit trail between two versions of the merkle tree of a presale
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigMerkleTreeDiff {
+    pub base_version: u64,
+    pub version: u64,
+    pub base_merkle_root: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub added: Vec<TreeNode>,
+    pub removed: Vec<Pubkey>,
+    pub changed: Vec<TreeNodeChange>,
+}
+
+impl ConfigMerkleTreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigMerkleTreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "version {} ({}) -> version {} ({})",
+            self.base_version,
+            Hash::new_from_array(self.base_merkle_root),
+            self.version,
+            Hash::new_from_array(self.merkle_root)
+        )?;
+        for tree_node in self.added.iter() {
+            writeln!(
+                f,
+                "+ {} registry_index {} deposit_cap {}",
+                tree_node.escrow_owner, tree_node.registry_index, tree_node.deposit_cap
+            )?;
+        }
+        for escrow_owner in self.removed.iter() {
+            writeln!(f, "- {}", escrow_owner)?;
+        }
+        for change in self.changed.iter() {
+            write!(
+                f,
+                "~ {} registry_index {} -> {} deposit_cap {} -> {}",
+                change.escrow_owner,
+                change.previous_registry_index,
+                change.registry_index,
+                change.previous_deposit_cap,
+                change.deposit_cap
+            )?;
+            if change.is_terms_override_changed {
+                write!(f, " terms override changed")?;
+            }
+            writeln!(f)?;
         }
 
-        // Convert IndexMap back to Vec while preserving the order
-        let mut tree_nodes: Vec<TreeNode> = tree_nodes_map.values().cloned().collect();
+        Ok(())
+    }
+}
+
+/// Changes applied on a base tree to build the next version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TreeChangeSet {
+    /// Nodes added to the tree, or replacing the node of the same escrow owner
+    pub upserts: Vec<TreeNode>,
+    /// Escrow owners removed from the tree
+    pub removals: Vec<Pubkey>,
+}
+
+impl ConfigMerkleTree {
+    pub fn new(mut tree_nodes: Vec<TreeNode>, version: u64) -> Result<Self> {
+        // Reject duplicated escrow owner instead of dropping one of its nodes
+        let mut escrow_owners = HashSet::new();
+        for tree_node in tree_nodes.iter() {
+            if !escrow_owners.insert(tree_node.escrow_owner) {
+                return Err(MerkleValidationError(format!(
+                    "Escrow owner {} is duplicated",
+                    tree_node.escrow_owner
+                )));
+            }
+        }
 
         let hashed_nodes = tree_nodes
             .iter()
@@ -104,17 +199,15 @@ impl ConfigMerkleTree {
         file.write_all(serialized.as_bytes()).unwrap();
     }
 
-    pub fn get_node(&self, escrow_owner: &Pubkey) -> TreeNode {
-        for i in self.tree_nodes.iter() {
-            if i.escrow_owner == *escrow_owner {
-                return i.clone();
-            }
-        }
-
-        panic!("Escrow owner not found in tree");
+    pub fn get_node(&self, escrow_owner: &Pubkey) -> Result<TreeNode> {
+        self.tree_nodes
+            .iter()
+            .find(|n| n.escrow_owner == *escrow_owner)
+            .cloned()
+            .ok_or(MerkleTreeError::NodeNotFound(*escrow_owner))
     }
 
-    fn validate(&self) -> Result<()> {
+    pub fn validate(&self) -> Result<()> {
         // The Merkle tree can be at most height 32, implying a max node count of 2^32 - 1
         if self.max_num_nodes > 2u64.pow(32) - 1 {
             return Err(MerkleValidationError(format!(
@@ -141,6 +234,18 @@ impl ConfigMerkleTree {
             ));
         }
 
+        // validate that the vesting override is complete
+        if self
+            .tree_nodes
+            .iter()
+            .any(|n| n.immediate_release_bps.is_some() != n.vest_duration.is_some())
+        {
+            return Err(MerkleValidationError(
+                "Vesting override requires both immediate_release_bps and vest_duration"
+                    .to_string(),
+            ));
+        }
+
         if self.verify_proof().is_err() {
             return Err(MerkleValidationError(
                 "Merkle root is invalid given nodes".to_string(),
@@ -162,12 +267,15 @@ impl ConfigMerkleTree {
             .collect();
         let mk = MerkleTree::new(&hashed_nodes[..], true);
 
-        assert_eq!(
-            mk.get_root()
-                .ok_or(MerkleValidationError("invalid merkle proof".to_string()))?
-                .to_bytes(),
-            root
-        );
+        let computed_root = mk
+            .get_root()
+            .ok_or(MerkleValidationError("invalid merkle proof".to_string()))?
+            .to_bytes();
+        if computed_root != root {
+            return Err(MerkleValidationError(
+                "Merkle root does not match nodes".to_string(),
+            ));
+        }
 
         // Verify each node against the root
         for (i, _node) in hashed_nodes.iter().enumerate() {
@@ -182,6 +290,25 @@ impl ConfigMerkleTree {
         Ok(())
     }
 
+    /// verify that the proof stored in each node leads to the merkle root
+    pub fn verify_node_proofs(&self) -> Result<()> {
+        for node in self.tree_nodes.iter() {
+            let proof = node.proof.clone().ok_or_else(|| {
+                MerkleValidationError(format!("Missing proof of {}", node.escrow_owner))
+            })?;
+            let leaf = hashv(&[LEAF_PREFIX, &node.hash().to_bytes()]);
+
+            if !verify(proof, self.merkle_root, leaf.to_bytes()) {
+                return Err(MerkleValidationError(format!(
+                    "Invalid proof of {}",
+                    node.escrow_owner
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     // Converts Merkle Tree to a map for faster key access
     pub fn convert_to_hashmap(&self) -> HashMap<Pubkey, TreeNode> {
         self.tree_nodes
@@ -191,14 +318,209 @@ impl ConfigMerkleTree {
     }
 
     pub fn get_merkle_root_config_pubkey(&self, presale: Pubkey, program_id: &Pubkey) -> Pubkey {
-        Pubkey::find_program_address(
-            &[
-                presale::seeds::MERKLE_ROOT_CONFIG_PREFIX.as_ref(),
-                presale.as_ref(),
-                self.version.to_le_bytes().as_ref(),
+        derive_merkle_root_config(&presale, self.version, program_id)
+    }
+
+    /// Nodes by escrow owner in the tree node order. Fails on duplicated escrow owner, which would otherwise be missing from the diff.
+    fn get_nodes_by_escrow_owner(&self) -> Result<IndexMap<Pubkey, &TreeNode>> {
+        let mut nodes = IndexMap::new();
+        for tree_node in self.tree_nodes.iter() {
+            if nodes.insert(tree_node.escrow_owner, tree_node).is_some() {
+                return Err(MerkleValidationError(format!(
+                    "Escrow owner {} is duplicated in tree version {}",
+                    tree_node.escrow_owner, self.version
+                )));
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// Diff of `self` against the base version. Nodes are compared by leaf content, proofs are ignored.
+    pub fn diff(&self, base: &ConfigMerkleTree) -> Result<ConfigMerkleTreeDiff> {
+        let base_nodes = base.get_nodes_by_escrow_owner()?;
+        let nodes = self.get_nodes_by_escrow_owner()?;
+
+        let mut added = vec![];
+        let mut changed = vec![];
+        for (escrow_owner, tree_node) in nodes.iter() {
+            match base_nodes.get(escrow_owner) {
+                None => added.push(TreeNode {
+                    proof: None,
+                    ..(*tree_node).clone()
+                }),
+                Some(base_node) if base_node.hash() != tree_node.hash() => {
+                    changed.push(TreeNodeChange {
+                        escrow_owner: *escrow_owner,
+                        previous_registry_index: base_node.registry_index,
+                        registry_index: tree_node.registry_index,
+                        previous_deposit_cap: base_node.deposit_cap,
+                        deposit_cap: tree_node.deposit_cap,
+                        is_terms_override_changed: base_node.get_terms_override()
+                            != tree_node.get_terms_override(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        let removed = base_nodes
+            .keys()
+            .filter(|escrow_owner| !nodes.contains_key(*escrow_owner))
+            .copied()
+            .collect();
+
+        Ok(ConfigMerkleTreeDiff {
+            base_version: base.version,
+            version: self.version,
+            base_merkle_root: base.merkle_root,
+            merkle_root: self.merkle_root,
+            added,
+            removed,
+            changed,
+        })
+    }
+
+    /// Build the next version from the nodes of `self` and the change set. Nodes keep their order, and added nodes are appended.
+    pub fn rebuild(&self, change_set: &TreeChangeSet, version: u64) -> Result<Self> {
+        if version <= self.version {
+            return Err(MerkleValidationError(format!(
+                "Version {} must be greater than base version {}",
+                version, self.version
+            )));
+        }
+
+        let mut touched_owners = HashSet::new();
+        for escrow_owner in change_set
+            .upserts
+            .iter()
+            .map(|n| &n.escrow_owner)
+            .chain(change_set.removals.iter())
+        {
+            if !touched_owners.insert(*escrow_owner) {
+                return Err(MerkleValidationError(format!(
+                    "Escrow owner {} is changed more than once",
+                    escrow_owner
+                )));
+            }
+        }
+
+        let mut tree_nodes_map: IndexMap<Pubkey, TreeNode> = IndexMap::new();
+        for tree_node in self.tree_nodes.iter() {
+            tree_nodes_map.insert(tree_node.escrow_owner, tree_node.clone());
+        }
+
+        for escrow_owner in change_set.removals.iter() {
+            if tree_nodes_map.shift_remove(escrow_owner).is_none() {
+                return Err(MerkleTreeError::NodeNotFound(*escrow_owner));
+            }
+        }
+
+        for tree_node in change_set.upserts.iter() {
+            tree_nodes_map.insert(tree_node.escrow_owner, tree_node.clone());
+        }
+
+        ConfigMerkleTree::new(tree_nodes_map.into_values().collect(), version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebuild_and_diff() {
+        let wallet_0 = Pubkey::new_unique();
+        let wallet_1 = Pubkey::new_unique();
+        let wallet_2 = Pubkey::new_unique();
+        let wallet_3 = Pubkey::new_unique();
+
+        let base = ConfigMerkleTree::new(
+            vec![
+                TreeNode::new(wallet_0, 0, 100),
+                TreeNode::new(wallet_1, 0, 200),
+                TreeNode::new(wallet_2, 0, 300),
+            ],
+            0,
+        )
+        .unwrap();
+
+        let change_set = TreeChangeSet {
+            upserts: vec![
+                TreeNode::new(wallet_1, 0, 250),
+                TreeNode::new(wallet_3, 0, 400),
+            ],
+            removals: vec![wallet_2],
+        };
+        let tree = base.rebuild(&change_set, 1).unwrap();
+
+        let escrow_owners: Vec<Pubkey> = tree.tree_nodes.iter().map(|n| n.escrow_owner).collect();
+        assert_eq!(escrow_owners, vec![wallet_0, wallet_1, wallet_3]);
+
+        let diff = tree.diff(&base).unwrap();
+        assert_eq!(diff.base_version, 0);
+        assert_eq!(diff.version, 1);
+        assert_eq!(diff.added, vec![TreeNode::new(wallet_3, 0, 400)]);
+        assert_eq!(diff.removed, vec![wallet_2]);
+        assert_eq!(
+            diff.changed,
+            vec![TreeNodeChange {
+                escrow_owner: wallet_1,
+                previous_registry_index: 0,
+                registry_index: 0,
+                previous_deposit_cap: 200,
+                deposit_cap: 250,
+                is_terms_override_changed: false,
+            }]
+        );
+
+        assert!(base.diff(&base).unwrap().is_empty());
+
+        // Duplicated escrow owner is reported instead of being dropped from the diff
+        let mut duplicated = base.rebuild(&change_set, 2).unwrap();
+        duplicated.tree_nodes.push(TreeNode::new(wallet_0, 0, 500));
+        assert!(duplicated.diff(&base).is_err());
+        assert!(base.diff(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_rebuild_with_invalid_change_set() {
+        let wallet_0 = Pubkey::new_unique();
+        let wallet_1 = Pubkey::new_unique();
+        let base = ConfigMerkleTree::new(vec![TreeNode::new(wallet_0, 0, 100)], 1).unwrap();
+
+        assert!(base.rebuild(&TreeChangeSet::default(), 1).is_err());
+
+        let duplicated_upsert = TreeChangeSet {
+            upserts: vec![
+                TreeNode::new(wallet_1, 0, 100),
+                TreeNode::new(wallet_1, 0, 200),
+            ],
+            removals: vec![],
+        };
+        assert!(base.rebuild(&duplicated_upsert, 2).is_err());
+
+        let unknown_removal = TreeChangeSet {
+            upserts: vec![],
+            removals: vec![wallet_1],
+        };
+        assert!(matches!(
+            base.rebuild(&unknown_removal, 2),
+            Err(MerkleTreeError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_with_duplicated_escrow_owner() {
+        let wallet_0 = Pubkey::new_unique();
+
+        assert!(ConfigMerkleTree::new(
+            vec![
+                TreeNode::new(wallet_0, 0, 100),
+                TreeNode::new(wallet_0, 0, 200)
             ],
-            program_id,
+            0,
         )
-        .0
+        .is_err());
     }
 }
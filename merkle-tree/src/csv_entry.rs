@@ -0,0 +1,236 @@
+use std::{collections::HashMap, fs::File, io::Read, path::Path, str::FromStr};
+
+use csv::StringRecord;
+use presale::MAX_PRESALE_REGISTRY_COUNT;
+use solana_program::pubkey::Pubkey;
+
+use crate::{config_merkle_tree::Result, error::MerkleTreeError, tree_node::TreeNode};
+
+const WALLET_COLUMN: &str = "wallet";
+const REGISTRY_INDEX_COLUMN: &str = "registry_index";
+const DEPOSIT_CAP_COLUMN: &str = "deposit_cap";
+const PRICE_DISCOUNT_BPS_COLUMN: &str = "price_discount_bps";
+const IMMEDIATE_RELEASE_BPS_COLUMN: &str = "immediate_release_bps";
+const VEST_DURATION_COLUMN: &str = "vest_duration";
+
+const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Read tree nodes from a CSV file with the header `wallet,registry_index,deposit_cap`.
+/// `price_discount_bps`, `immediate_release_bps` and `vest_duration` columns are optional, and empty cells mean no override.
+pub fn read_tree_nodes_from_csv(path: &Path) -> Result<Vec<TreeNode>> {
+    parse_tree_nodes_from_csv(File::open(path)?)
+}
+
+pub fn parse_tree_nodes_from_csv<R: Read>(reader: R) -> Result<Vec<TreeNode>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let columns = CsvColumns::new(reader.headers()?)?;
+
+    let mut tree_nodes = vec![];
+    // Row of the first occurrence of each wallet
+    let mut wallet_rows: HashMap<Pubkey, u64> = HashMap::new();
+
+    for record in reader.records() {
+        let record = record?;
+        let row = record.position().map(|p| p.line()).unwrap_or_default();
+        let tree_node = columns.parse_record(&record, row)?;
+
+        if let Some(first_row) = wallet_rows.insert(tree_node.escrow_owner, row) {
+            return Err(MerkleTreeError::InvalidRow {
+                row,
+                message: format!(
+                    "wallet {} is duplicated with row {}",
+                    tree_node.escrow_owner, first_row
+                ),
+            });
+        }
+
+        tree_nodes.push(tree_node);
+    }
+
+    if tree_nodes.is_empty() {
+        return Err(MerkleTreeError::MerkleValidationError(
+            "CSV has no wallet".to_string(),
+        ));
+    }
+
+    Ok(tree_nodes)
+}
+
+struct CsvColumns {
+    wallet: usize,
+    registry_index: usize,
+    deposit_cap: usize,
+    price_discount_bps: Option<usize>,
+    immediate_release_bps: Option<usize>,
+    vest_duration: Option<usize>,
+}
+
+impl CsvColumns {
+    fn new(headers: &StringRecord) -> Result<Self> {
+        let find = |name: &str| headers.iter().position(|header| header == name);
+        let find_required = |name: &str| {
+            find(name).ok_or_else(|| {
+                MerkleTreeError::MerkleValidationError(format!("CSV is missing column {}", name))
+            })
+        };
+
+        Ok(Self {
+            wallet: find_required(WALLET_COLUMN)?,
+            registry_index: find_required(REGISTRY_INDEX_COLUMN)?,
+            deposit_cap: find_required(DEPOSIT_CAP_COLUMN)?,
+            price_discount_bps: find(PRICE_DISCOUNT_BPS_COLUMN),
+            immediate_release_bps: find(IMMEDIATE_RELEASE_BPS_COLUMN),
+            vest_duration: find(VEST_DURATION_COLUMN),
+        })
+    }
+
+    fn parse_record(&self, record: &StringRecord, row: u64) -> Result<TreeNode> {
+        let invalid_row = |message: String| MerkleTreeError::InvalidRow { row, message };
+
+        let escrow_owner: Pubkey = parse_cell(record, Some(self.wallet), WALLET_COLUMN, row)?
+            .ok_or_else(|| invalid_row(format!("{} is empty", WALLET_COLUMN)))?;
+
+        let registry_index: u8 = parse_cell(
+            record,
+            Some(self.registry_index),
+            REGISTRY_INDEX_COLUMN,
+            row,
+        )?
+        .ok_or_else(|| invalid_row(format!("{} is empty", REGISTRY_INDEX_COLUMN)))?;
+        if usize::from(registry_index) >= MAX_PRESALE_REGISTRY_COUNT {
+            return Err(invalid_row(format!(
+                "{} must be less than {}",
+                REGISTRY_INDEX_COLUMN, MAX_PRESALE_REGISTRY_COUNT
+            )));
+        }
+
+        let deposit_cap: u64 = parse_cell(record, Some(self.deposit_cap), DEPOSIT_CAP_COLUMN, row)?
+            .ok_or_else(|| invalid_row(format!("{} is empty", DEPOSIT_CAP_COLUMN)))?;
+        if deposit_cap == 0 {
+            return Err(invalid_row(format!(
+                "{} must be positive",
+                DEPOSIT_CAP_COLUMN
+            )));
+        }
+
+        let price_discount_bps: Option<u16> = parse_cell(
+            record,
+            self.price_discount_bps,
+            PRICE_DISCOUNT_BPS_COLUMN,
+            row,
+        )?;
+        if price_discount_bps.is_some_and(|bps| bps >= MAX_BASIS_POINTS) {
+            return Err(invalid_row(format!(
+                "{} must be less than {}",
+                PRICE_DISCOUNT_BPS_COLUMN, MAX_BASIS_POINTS
+            )));
+        }
+
+        let immediate_release_bps: Option<u16> = parse_cell(
+            record,
+            self.immediate_release_bps,
+            IMMEDIATE_RELEASE_BPS_COLUMN,
+            row,
+        )?;
+        if immediate_release_bps.is_some_and(|bps| bps > MAX_BASIS_POINTS) {
+            return Err(invalid_row(format!(
+                "{} must not exceed {}",
+                IMMEDIATE_RELEASE_BPS_COLUMN, MAX_BASIS_POINTS
+            )));
+        }
+
+        let vest_duration: Option<u64> =
+            parse_cell(record, self.vest_duration, VEST_DURATION_COLUMN, row)?;
+        if immediate_release_bps.is_some() != vest_duration.is_some() {
+            return Err(invalid_row(format!(
+                "{} and {} must be set together",
+                IMMEDIATE_RELEASE_BPS_COLUMN, VEST_DURATION_COLUMN
+            )));
+        }
+
+        Ok(TreeNode {
+            escrow_owner,
+            registry_index,
+            deposit_cap,
+            price_discount_bps,
+            immediate_release_bps,
+            vest_duration,
+            proof: None,
+        })
+    }
+}
+
+/// Parse a cell of the record. Missing column and empty cell are None.
+fn parse_cell<T: FromStr>(
+    record: &StringRecord,
+    column: Option<usize>,
+    column_name: &str,
+    row: u64,
+) -> Result<Option<T>> {
+    let Some(value) = column.and_then(|column| record.get(column)) else {
+        return Ok(None);
+    };
+
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| MerkleTreeError::InvalidRow {
+            row,
+            message: format!("invalid {} {}", column_name, value),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tree_nodes_from_csv() {
+        let wallet_0 = Pubkey::new_unique();
+        let wallet_1 = Pubkey::new_unique();
+        let csv = format!(
+            "wallet,registry_index,deposit_cap,price_discount_bps,immediate_release_bps,vest_duration\n\
+             {},0,100,,,\n\
+             {},1,200,500,10000,0\n",
+            wallet_0, wallet_1
+        );
+
+        let tree_nodes = parse_tree_nodes_from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(tree_nodes.len(), 2);
+        assert_eq!(tree_nodes[0].escrow_owner, wallet_0);
+        assert!(tree_nodes[0].get_terms_override().is_none());
+        assert_eq!(tree_nodes[1].registry_index, 1);
+        assert_eq!(tree_nodes[1].deposit_cap, 200);
+        assert_eq!(tree_nodes[1].price_discount_bps, Some(500));
+        assert_eq!(tree_nodes[1].vest_duration, Some(0));
+    }
+
+    #[test]
+    fn test_parse_tree_nodes_from_csv_with_invalid_row() {
+        let wallet = Pubkey::new_unique();
+
+        let csv = format!(
+            "wallet,registry_index,deposit_cap\n{},0,100\nabc,0,100\n",
+            wallet
+        );
+        let err = parse_tree_nodes_from_csv(csv.as_bytes()).unwrap_err();
+        assert!(matches!(err, MerkleTreeError::InvalidRow { row: 3, .. }));
+
+        let csv = format!("wallet,registry_index,deposit_cap\n{},0,0\n", wallet);
+        let err = parse_tree_nodes_from_csv(csv.as_bytes()).unwrap_err();
+        assert!(matches!(err, MerkleTreeError::InvalidRow { row: 2, .. }));
+
+        let csv = format!(
+            "wallet,registry_index,deposit_cap\n{},0,100\n{},1,100\n",
+            wallet, wallet
+        );
+        let err = parse_tree_nodes_from_csv(csv.as_bytes()).unwrap_err();
+        assert!(matches!(err, MerkleTreeError::InvalidRow { row: 3, .. }));
+    }
+}
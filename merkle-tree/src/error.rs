@@ -1,3 +1,4 @@
+use solana_program::pubkey::Pubkey;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,4 +11,12 @@ pub enum MerkleTreeError {
     IoError(#[from] std::io::Error),
     #[error("Serde Error: {0}")]
     SerdeError(#[from] serde_json::Error),
+    #[error("CSV Error: {0}")]
+    CsvError(#[from] csv::Error),
+    #[error("Row {row}: {message}")]
+    InvalidRow { row: u64, message: String },
+    #[error("Escrow owner {0} not found in tree")]
+    NodeNotFound(Pubkey),
+    #[error("Invalid presale account: {0}")]
+    InvalidPresaleAccount(String),
 }
@@ -1,5 +1,8 @@
+pub mod compact_merkle_tree;
 pub mod config_merkle_tree;
+pub mod csv_entry;
 pub mod error;
 pub mod merkle_tree;
+pub mod presale_validation;
 pub mod tree_node;
 pub mod utils;
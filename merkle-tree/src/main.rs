@@ -0,0 +1,189 @@
+use std::{fs, path::PathBuf, process::exit, str::FromStr};
+
+use clap::{Parser, Subcommand};
+use merkle_tree::{
+    compact_merkle_tree::CompactMerkleTree,
+    config_merkle_tree::{ConfigMerkleTree, Result},
+    csv_entry::read_tree_nodes_from_csv,
+    error::MerkleTreeError,
+    presale_validation::read_presale_account,
+};
+use solana_program::{hash::Hash, pubkey::Pubkey};
+
+#[derive(Parser)]
+#[command(about = "Build and verify the merkle tree of permissioned with merkle proof presale")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build the merkle tree from a CSV of wallet,registry_index,deposit_cap and write the tree and per user proof files
+    Build {
+        /// CSV file. price_discount_bps, immediate_release_bps and vest_duration columns are optional
+        #[arg(long)]
+        csv: PathBuf,
+        /// Version of the merkle root config
+        #[arg(long)]
+        version: u64,
+        /// Presale of the merkle root config
+        #[arg(long)]
+        presale: Pubkey,
+        #[arg(long, default_value_t = presale::ID)]
+        program_id: Pubkey,
+        /// Directory of tree.json, tree.bin, root.txt and proofs/<wallet>.json
+        #[arg(long)]
+        output_dir: PathBuf,
+    },
+    /// Verify a tree file against a merkle root
+    Verify {
+        /// Tree file. Files with the bin extension are read as compact tree
+        #[arg(long)]
+        tree: PathBuf,
+        /// Base58 encoded merkle root
+        #[arg(long)]
+        root: String,
+        /// Presale account data dumped by `solana account <PRESALE> --output-file <FILE>`. Leaves are checked against the presale registries when set
+        #[arg(long)]
+        presale_account: Option<PathBuf>,
+    },
+    /// Print the added, removed and changed escrow owners between two tree files
+    Diff {
+        /// Tree file of the previous version
+        #[arg(long)]
+        base: PathBuf,
+        /// Tree file of the new version
+        #[arg(long)]
+        tree: PathBuf,
+    },
+    /// Print the proof of a wallet from a compact tree file
+    Proof {
+        #[arg(long)]
+        tree: PathBuf,
+        #[arg(long)]
+        wallet: Pubkey,
+        /// Presale of the merkle root config
+        #[arg(long)]
+        presale: Pubkey,
+        #[arg(long, default_value_t = presale::ID)]
+        program_id: Pubkey,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Build {
+            csv,
+            version,
+            presale,
+            program_id,
+            output_dir,
+        } => build(csv, version, presale, program_id, output_dir),
+        Command::Verify {
+            tree,
+            root,
+            presale_account,
+        } => verify(tree, root, presale_account),
+        Command::Diff { base, tree } => diff(base, tree),
+        Command::Proof {
+            tree,
+            wallet,
+            presale,
+            program_id,
+        } => proof(tree, wallet, presale, program_id),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        exit(1);
+    }
+}
+
+fn build(
+    csv: PathBuf,
+    version: u64,
+    presale: Pubkey,
+    program_id: Pubkey,
+    output_dir: PathBuf,
+) -> Result<()> {
+    let tree_nodes = read_tree_nodes_from_csv(&csv)?;
+    let tree = ConfigMerkleTree::new(tree_nodes, version)?;
+    let merkle_root_config = tree.get_merkle_root_config_pubkey(presale, &program_id);
+
+    let proofs_dir = output_dir.join("proofs");
+    fs::create_dir_all(&proofs_dir)?;
+
+    tree.write_to_file(&output_dir.join("tree.json"));
+    tree.write_to_compact_file(&output_dir.join("tree.bin"))?;
+
+    let root = Hash::new_from_array(tree.merkle_root);
+    fs::write(output_dir.join("root.txt"), root.to_string())?;
+
+    for tree_node in tree.tree_nodes.iter() {
+        let user_proof = tree_node.to_user_proof(&merkle_root_config)?;
+        let path = proofs_dir.join(format!("{}.json", tree_node.escrow_owner));
+        fs::write(path, serde_json::to_string_pretty(&user_proof)?)?;
+    }
+
+    println!("merkle root: {}", root);
+    println!("merkle root config: {}", merkle_root_config);
+
+    Ok(())
+}
+
+fn verify(tree: PathBuf, root: String, presale_account: Option<PathBuf>) -> Result<()> {
+    let root = Hash::from_str(&root)
+        .map_err(|_| MerkleTreeError::MerkleValidationError(format!("Invalid root {}", root)))?;
+
+    let tree = ConfigMerkleTree::new_from_tree_file(&tree)?;
+    if tree.merkle_root != root.to_bytes() {
+        return Err(MerkleTreeError::MerkleValidationError(format!(
+            "Tree root {} does not match {}",
+            Hash::new_from_array(tree.merkle_root),
+            root
+        )));
+    }
+
+    if let Some(presale_account) = presale_account {
+        let presale = read_presale_account(&presale_account)?;
+        let invalid_leaves = tree.validate_against_presale(&presale);
+        for invalid_leaf in invalid_leaves.iter() {
+            eprintln!("{}", invalid_leaf);
+        }
+
+        if !invalid_leaves.is_empty() {
+            return Err(MerkleTreeError::MerkleValidationError(format!(
+                "{} leaves are invalid for the presale",
+                invalid_leaves.len()
+            )));
+        }
+    }
+
+    tree.validate()?;
+    tree.verify_node_proofs()?;
+
+    println!("verified {} nodes against {}", tree.max_num_nodes, root);
+
+    Ok(())
+}
+
+fn diff(base: PathBuf, tree: PathBuf) -> Result<()> {
+    let base = ConfigMerkleTree::new_from_tree_file(&base)?;
+    let tree = ConfigMerkleTree::new_from_tree_file(&tree)?;
+    print!("{}", tree.diff(&base)?);
+
+    Ok(())
+}
+
+fn proof(tree: PathBuf, wallet: Pubkey, presale: Pubkey, program_id: Pubkey) -> Result<()> {
+    let tree = CompactMerkleTree::open(&tree)?;
+    let tree_node = tree.get_node(&wallet)?;
+    let merkle_root_config = tree.get_merkle_root_config_pubkey(presale, &program_id);
+    let user_proof = tree_node.to_user_proof(&merkle_root_config)?;
+    println!("{}", serde_json::to_string_pretty(&user_proof)?);
+
+    Ok(())
+}
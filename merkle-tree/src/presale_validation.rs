@@ -0,0 +1,273 @@
+use std::{collections::HashSet, fmt, fs, mem::size_of, path::PathBuf};
+
+use anchor_lang::Discriminator;
+use presale::{Presale, PresaleRegistry, WhitelistMode, MAX_PRESALE_REGISTRY_COUNT};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    config_merkle_tree::{ConfigMerkleTree, Result},
+    error::MerkleTreeError::InvalidPresaleAccount,
+};
+
+/// Reason a leaf would fail `create_permissioned_escrow_with_merkle_proof` of the presale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidLeafReason {
+    RegistryIndexOutOfRange {
+        registry_index: u8,
+        total_presale_registry_count: u8,
+    },
+    RegistryNotPermissionedWithMerkleProof {
+        registry_index: u8,
+    },
+    DepositCapOutOfRange {
+        deposit_cap: u64,
+        buyer_minimum_deposit_cap: u64,
+        buyer_maximum_deposit_cap: u64,
+    },
+    DuplicateEscrowOwner,
+    InvalidTermsOverride,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLeaf {
+    /// Index of the leaf in the tree nodes
+    pub index: usize,
+    pub escrow_owner: Pubkey,
+    pub reason: InvalidLeafReason,
+}
+
+impl fmt::Display for InvalidLeaf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Leaf {} ({}): ", self.index, self.escrow_owner)?;
+        match &self.reason {
+            InvalidLeafReason::RegistryIndexOutOfRange {
+                registry_index,
+                total_presale_registry_count,
+            } => write!(
+                f,
+                "registry_index {} is not below total_presale_registry_count {}",
+                registry_index, total_presale_registry_count
+            ),
+            InvalidLeafReason::RegistryNotPermissionedWithMerkleProof { registry_index } => {
+                write!(
+                    f,
+                    "registry {} is not permissioned with merkle proof",
+                    registry_index
+                )
+            }
+            InvalidLeafReason::DepositCapOutOfRange {
+                deposit_cap,
+                buyer_minimum_deposit_cap,
+                buyer_maximum_deposit_cap,
+            } => write!(
+                f,
+                "deposit_cap {} is outside of {}..={}",
+                deposit_cap, buyer_minimum_deposit_cap, buyer_maximum_deposit_cap
+            ),
+            InvalidLeafReason::DuplicateEscrowOwner => write!(f, "duplicated escrow owner"),
+            InvalidLeafReason::InvalidTermsOverride => {
+                write!(f, "terms override is not allowed by the presale")
+            }
+        }
+    }
+}
+
+/// Decode the raw data of a presale account, including the account discriminator
+pub fn decode_presale_account(data: &[u8]) -> Result<Presale> {
+    let discriminator = Presale::DISCRIMINATOR;
+    if !data.starts_with(discriminator) {
+        return Err(InvalidPresaleAccount(
+            "Account discriminator mismatch".to_string(),
+        ));
+    }
+
+    let presale: Presale = bytemuck::try_pod_read_unaligned(
+        data.get(discriminator.len()..discriminator.len() + size_of::<Presale>())
+            .ok_or_else(|| InvalidPresaleAccount("Account data too small".to_string()))?,
+    )
+    .map_err(|err| InvalidPresaleAccount(err.to_string()))?;
+
+    if usize::from(presale.total_presale_registry_count) > MAX_PRESALE_REGISTRY_COUNT {
+        return Err(InvalidPresaleAccount(format!(
+            "total_presale_registry_count {} is above {}",
+            presale.total_presale_registry_count, MAX_PRESALE_REGISTRY_COUNT
+        )));
+    }
+
+    Ok(presale)
+}
+
+/// Read a presale account dumped by `solana account <PRESALE> --output-file <FILE>`
+pub fn read_presale_account(path: &PathBuf) -> Result<Presale> {
+    decode_presale_account(&fs::read(path)?)
+}
+
+impl ConfigMerkleTree {
+    /// Report every leaf that can't be used to create an escrow of the presale. The program only checks these when the user creates the escrow.
+    pub fn validate_against_presale(&self, presale: &Presale) -> Vec<InvalidLeaf> {
+        let mut invalid_leaves = vec![];
+        let mut escrow_owners = HashSet::new();
+
+        for (index, tree_node) in self.tree_nodes.iter().enumerate() {
+            let mut report = |reason| {
+                invalid_leaves.push(InvalidLeaf {
+                    index,
+                    escrow_owner: tree_node.escrow_owner,
+                    reason,
+                })
+            };
+
+            if !escrow_owners.insert(tree_node.escrow_owner) {
+                report(InvalidLeafReason::DuplicateEscrowOwner);
+            }
+
+            let registry_index = usize::from(tree_node.registry_index);
+            let presale_registry = presale
+                .presale_registries
+                .get(registry_index)
+                .filter(|_| tree_node.registry_index < presale.total_presale_registry_count);
+
+            if let Some(PresaleRegistry {
+                buyer_minimum_deposit_cap,
+                buyer_maximum_deposit_cap,
+                ..
+            }) = presale_registry.copied()
+            {
+                if presale.get_registry_whitelist_mode(registry_index).ok()
+                    != Some(WhitelistMode::PermissionWithMerkleProof)
+                {
+                    report(InvalidLeafReason::RegistryNotPermissionedWithMerkleProof {
+                        registry_index: tree_node.registry_index,
+                    });
+                }
+
+                if tree_node.deposit_cap == 0
+                    || tree_node.deposit_cap < buyer_minimum_deposit_cap
+                    || tree_node.deposit_cap > buyer_maximum_deposit_cap
+                {
+                    report(InvalidLeafReason::DepositCapOutOfRange {
+                        deposit_cap: tree_node.deposit_cap,
+                        buyer_minimum_deposit_cap,
+                        buyer_maximum_deposit_cap,
+                    });
+                }
+            } else {
+                report(InvalidLeafReason::RegistryIndexOutOfRange {
+                    registry_index: tree_node.registry_index,
+                    total_presale_registry_count: presale.total_presale_registry_count,
+                });
+            }
+
+            if let Some(terms_override) = tree_node.get_terms_override() {
+                if terms_override.validate(presale).is_err() {
+                    report(InvalidLeafReason::InvalidTermsOverride);
+                }
+            }
+        }
+
+        invalid_leaves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::*;
+    use crate::tree_node::TreeNode;
+
+    #[test]
+    fn test_validate_against_presale() {
+        let mut presale = Presale::zeroed();
+        presale.whitelist_mode = WhitelistMode::PermissionWithMerkleProof.into();
+        presale.total_presale_registry_count = 2;
+        presale.presale_registries[0].buyer_minimum_deposit_cap = 100;
+        presale.presale_registries[0].buyer_maximum_deposit_cap = 1000;
+        presale.presale_registries[1].buyer_minimum_deposit_cap = 1;
+        presale.presale_registries[1].buyer_maximum_deposit_cap = 50;
+        presale.presale_registries[1].override_whitelist_mode = 1;
+        presale.presale_registries[1].whitelist_mode = WhitelistMode::Permissionless.into();
+
+        let wallet_0 = Pubkey::new_unique();
+        let wallet_1 = Pubkey::new_unique();
+        let wallet_2 = Pubkey::new_unique();
+
+        let mut tree = ConfigMerkleTree::new(
+            vec![
+                TreeNode::new(wallet_0, 0, 100),
+                TreeNode::new(wallet_1, 1, 51),
+                TreeNode::new(wallet_2, 2, 10),
+            ],
+            0,
+        )
+        .unwrap();
+        tree.tree_nodes.push(TreeNode::new(wallet_0, 0, 1000));
+
+        let invalid_leaves = tree.validate_against_presale(&presale);
+        assert_eq!(
+            invalid_leaves,
+            vec![
+                InvalidLeaf {
+                    index: 1,
+                    escrow_owner: wallet_1,
+                    reason: InvalidLeafReason::RegistryNotPermissionedWithMerkleProof {
+                        registry_index: 1
+                    },
+                },
+                InvalidLeaf {
+                    index: 1,
+                    escrow_owner: wallet_1,
+                    reason: InvalidLeafReason::DepositCapOutOfRange {
+                        deposit_cap: 51,
+                        buyer_minimum_deposit_cap: 1,
+                        buyer_maximum_deposit_cap: 50,
+                    },
+                },
+                InvalidLeaf {
+                    index: 2,
+                    escrow_owner: wallet_2,
+                    reason: InvalidLeafReason::RegistryIndexOutOfRange {
+                        registry_index: 2,
+                        total_presale_registry_count: 2,
+                    },
+                },
+                InvalidLeaf {
+                    index: 3,
+                    escrow_owner: wallet_0,
+                    reason: InvalidLeafReason::DuplicateEscrowOwner,
+                },
+            ]
+        );
+    }
+    #[test]
+    fn test_validate_against_corrupted_presale() {
+        let mut presale = Presale::zeroed();
+        presale.whitelist_mode = WhitelistMode::PermissionWithMerkleProof.into();
+        presale.total_presale_registry_count = u8::MAX;
+
+        let wallet = Pubkey::new_unique();
+        let tree = ConfigMerkleTree::new(
+            vec![TreeNode::new(wallet, MAX_PRESALE_REGISTRY_COUNT as u8, 1)],
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tree.validate_against_presale(&presale),
+            vec![InvalidLeaf {
+                index: 0,
+                escrow_owner: wallet,
+                reason: InvalidLeafReason::RegistryIndexOutOfRange {
+                    registry_index: MAX_PRESALE_REGISTRY_COUNT as u8,
+                    total_presale_registry_count: u8::MAX,
+                },
+            }]
+        );
+
+        let data = [Presale::DISCRIMINATOR, bytemuck::bytes_of(&presale)].concat();
+        assert!(matches!(
+            decode_presale_account(&data),
+            Err(InvalidPresaleAccount(_))
+        ));
+    }
+}
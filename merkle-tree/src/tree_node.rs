@@ -1,7 +1,13 @@
+use presale::{EscrowTermsOverride, VestingOverride};
 use serde::{Deserialize, Serialize};
 use solana_program::{hash::hashv, pubkey::Pubkey};
 use solana_sdk::hash::Hash;
 
+use crate::{
+    config_merkle_tree::{Result, UserProof},
+    error::MerkleTreeError::MerkleValidationError,
+};
+
 /// Represents the escrow information for an account.
 #[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct TreeNode {
@@ -11,16 +17,80 @@ pub struct TreeNode {
     pub registry_index: u8,
     /// Personal deposit cap
     pub deposit_cap: u64,
+    /// Discount on the registry token price in bps. Fixed price presale only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_discount_bps: Option<u16>,
+    /// Personal immediate release bps. Must be set together with vest_duration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub immediate_release_bps: Option<u16>,
+    /// Personal vest duration. Must be set together with immediate_release_bps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vest_duration: Option<u64>,
     /// Escrow owner proof of inclusion in the Merkle Tree
     pub proof: Option<Vec<[u8; 32]>>,
 }
 
 impl TreeNode {
+    /// Node without terms override. The proof is set by `ConfigMerkleTree::new`
+    pub fn new(escrow_owner: Pubkey, registry_index: u8, deposit_cap: u64) -> Self {
+        Self {
+            escrow_owner,
+            registry_index,
+            deposit_cap,
+            price_discount_bps: None,
+            immediate_release_bps: None,
+            vest_duration: None,
+            proof: None,
+        }
+    }
+
     pub fn hash(&self) -> Hash {
+        let terms_override_bytes = self
+            .get_terms_override()
+            .map(|terms_override| terms_override.to_leaf_bytes())
+            .unwrap_or_default();
+
         hashv(&[
             &self.escrow_owner.to_bytes(),
             &self.registry_index.to_le_bytes(),
             &self.deposit_cap.to_le_bytes(),
+            &terms_override_bytes,
         ])
     }
+
+    /// Proof to be used by the escrow owner to create the escrow
+    pub fn to_user_proof(&self, merkle_root_config: &Pubkey) -> Result<UserProof> {
+        let proof = self.proof.clone().ok_or_else(|| {
+            MerkleValidationError(format!("Missing proof of {}", self.escrow_owner))
+        })?;
+
+        Ok(UserProof {
+            merkle_root_config: merkle_root_config.to_string(),
+            registry_index: self.registry_index,
+            max_cap: self.deposit_cap,
+            price_discount_bps: self.price_discount_bps,
+            immediate_release_bps: self.immediate_release_bps,
+            vest_duration: self.vest_duration,
+            proof,
+        })
+    }
+
+    /// Personal terms passed to `create_permissioned_escrow_with_merkle_proof`. None when the node has no override.
+    pub fn get_terms_override(&self) -> Option<EscrowTermsOverride> {
+        let vesting = self.immediate_release_bps.zip(self.vest_duration).map(
+            |(immediate_release_bps, vest_duration)| VestingOverride {
+                immediate_release_bps,
+                vest_duration,
+            },
+        );
+
+        if self.price_discount_bps.is_none() && vesting.is_none() {
+            return None;
+        }
+
+        Some(EscrowTermsOverride {
+            price_discount_bps: self.price_discount_bps.unwrap_or_default(),
+            vesting,
+        })
+    }
 }
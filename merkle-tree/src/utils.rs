@@ -1,3 +1,5 @@
+use solana_program::pubkey::Pubkey;
+
 use crate::merkle_tree::MerkleTree;
 
 pub fn get_proof(merkle_tree: &MerkleTree, index: usize) -> Vec<[u8; 32]> {
@@ -14,3 +16,15 @@ pub fn get_proof(merkle_tree: &MerkleTree, index: usize) -> Vec<[u8; 32]> {
     }
     proof
 }
+
+pub fn derive_merkle_root_config(presale: &Pubkey, version: u64, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::MERKLE_ROOT_CONFIG_PREFIX.as_ref(),
+            presale.as_ref(),
+            version.to_le_bytes().as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
@@ -0,0 +1,105 @@
+#![allow(unexpected_cfgs)]
+//! Minimal program which only exists to exercise the presale finalization callback CPI in tests.
+use anchor_lang::prelude::*;
+
+declare_id!("5P6TqCmN6wzgP9iJqcKCfxxFVGHeBnUbH7KgBv9zbZ3F");
+
+pub const RECORD_PREFIX: &[u8] = b"record";
+pub const FINALIZATION_CALLBACK_AUTHORITY_PREFIX: &[u8] = b"finalization_callback_authority";
+
+pub const PRESALE_PROGRAM_ID: Pubkey = pubkey!("presSVxnf9UU8jMxhgSMqaRwNiT36qeBdNeTRKjTdbj");
+
+/// Mirror of the presale program callback args
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PresaleFinalizedCallbackArgs {
+    pub presale_progress: u8,
+    pub presale_supply: u64,
+    pub total_deposit: u64,
+    pub total_escrow: u64,
+    pub total_deposit_fee: u64,
+}
+
+#[program]
+pub mod mock_finalization_callback {
+    use super::*;
+
+    pub fn initialize_record(ctx: Context<InitializeRecordCtx>) -> Result<()> {
+        ctx.accounts.record.presale = ctx.accounts.presale.key();
+        Ok(())
+    }
+
+    /// Record how many times the callback was invoked, and the latest presale totals
+    pub fn on_presale_finalized(
+        ctx: Context<OnPresaleFinalizedCtx>,
+        args: PresaleFinalizedCallbackArgs,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.record;
+
+        record.finalize_count += 1;
+
+        record.presale_progress = args.presale_progress;
+        record.total_deposit = args.total_deposit;
+        record.base_token_vault = ctx.accounts.base_token_vault.key();
+        record.quote_token_vault = ctx.accounts.quote_token_vault.key();
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Record {
+    pub presale: Pubkey,
+    pub base_token_vault: Pubkey,
+    pub quote_token_vault: Pubkey,
+    pub total_deposit: u64,
+    pub finalize_count: u64,
+    pub presale_progress: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRecordCtx<'info> {
+    #[account(
+        init,
+        seeds = [RECORD_PREFIX, presale.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + Record::INIT_SPACE
+    )]
+    pub record: Account<'info, Record>,
+
+    /// CHECK: Presale of the record
+    pub presale: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OnPresaleFinalizedCtx<'info> {
+    /// CHECK: Presale being finalized
+    pub presale: UncheckedAccount<'info>,
+
+    /// Per presale PDA of the presale program, proves the callback is invoked by the presale program
+    #[account(
+        seeds = [FINALIZATION_CALLBACK_AUTHORITY_PREFIX, presale.key().as_ref()],
+        bump,
+        seeds::program = PRESALE_PROGRAM_ID,
+    )]
+    pub finalization_callback_authority: Signer<'info>,
+
+    /// CHECK: Presale base token vault
+    pub base_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Presale quote token vault
+    pub quote_token_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [RECORD_PREFIX, presale.key().as_ref()],
+        bump
+    )]
+    pub record: Account<'info, Record>,
+}
@@ -14,11 +14,19 @@ pub const SCALE_MULTIPLIER: u128 = 1u128 << SCALE_OFFSET; // 2^64
 
 pub const MAX_PRESALE_REGISTRY_COUNT: usize = 5;
 
+// Fixed price presale registry 0 is priced at the presale token price, so the price of the rest fits in the extra args padding.
+pub const MAX_REGISTRY_Q_PRICE_COUNT: usize = MAX_PRESALE_REGISTRY_COUNT - 1;
+
 pub const MAX_DEPOSIT_FEE_BPS: u16 = 5000; // 50%
 
-// Only permissioned whitelist mode allowed to have multiple presale registries. The constant defined below is the default index for permissionless registries.
+pub const MAX_WITHDRAW_PENALTY_BPS: u16 = 5000; // 50%
+
+// Registry of the escrow when the presale has a single presale registry.
 pub const DEFAULT_PERMISSIONLESS_REGISTRY_INDEX: u8 = 0;
 
+// Tag of the signature voucher message, so that a signature for another purpose can't be used as a voucher
+pub const SIGNATURE_VOUCHER_TAG: &[u8] = b"presale_escrow_voucher";
+
 pub const DISABLE_WITHDRAW_MASK: u8 = 0b1;
 pub const DISABLE_END_PRESALE_ONCE_CAP_REACHED_MASK: u8 = 0b1;
 
@@ -33,4 +41,12 @@ pub mod seeds {
     pub const MERKLE_ROOT_CONFIG_PREFIX: &[u8] = b"merkle_root";
     pub const OPERATOR_PREFIX: &[u8] = b"operator";
     pub const PERMISSIONED_SERVER_METADATA_PREFIX: &[u8] = b"server_metadata";
+    pub const NATIVE_UNWRAP_PREFIX: &[u8] = b"native_unwrap";
+    pub const QUOTE_MINT_CONFIG_PREFIX: &[u8] = b"quote_mint_config";
+    pub const REFERRAL_PREFIX: &[u8] = b"referral";
+    pub const TOKEN_GATE_CONFIG_PREFIX: &[u8] = b"token_gate_config";
+    pub const GATING_TOKEN_VAULT_PREFIX: &[u8] = b"gating_token_vault";
+    pub const DENYLIST_PREFIX: &[u8] = b"denylist";
+    pub const SIGNATURE_VOUCHER_RECEIPT_PREFIX: &[u8] = b"signature_voucher_receipt";
+    pub const FINALIZATION_CALLBACK_AUTHORITY_PREFIX: &[u8] = b"finalization_callback_authority";
 }
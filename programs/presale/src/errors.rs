@@ -117,9 +117,6 @@ pub enum PresaleError {
     #[msg("Invalid presale registry index")]
     InvalidPresaleRegistryIndex,
 
-    #[msg("Multiple presale registries are not allowed")]
-    MultiplePresaleRegistriesNotAllowed,
-
     #[msg("Invalid deposit cap")]
     InvalidDepositCap,
 
@@ -137,4 +134,88 @@ pub enum PresaleError {
 
     #[msg("Presale min/max cap gap too small")]
     PresaleMinMaxCapGapTooSmall,
+
+    #[msg("Presale registry is not open")]
+    PresaleRegistryNotOpen,
+
+    #[msg("Presale is not ended")]
+    PresaleNotEnded,
+
+    #[msg("Presale is already finalized")]
+    PresaleAlreadyFinalized,
+
+    #[msg("Invalid finalization callback program")]
+    InvalidFinalizationCallbackProgram,
+
+    #[msg("Presale must be finalized before the creator withdraws")]
+    PresaleNotFinalized,
+
+    #[msg("Quote mint is not the native mint")]
+    QuoteMintNotNative,
+
+    #[msg("Invalid quote mint config")]
+    InvalidQuoteMintConfig,
+
+    #[msg("Escrow deposited with another quote mint")]
+    EscrowQuoteMintMismatch,
+
+    #[msg("Presale has already started")]
+    PresaleAlreadyStarted,
+
+    #[msg("Invalid referral")]
+    InvalidReferral,
+
+    #[msg("Referral fee is already claimed")]
+    ReferralFeeAlreadyClaimed,
+
+    #[msg("Referral is not supported for deposits with alternative quote mint")]
+    ReferralNotSupportedWithQuoteMint,
+
+    #[msg("Withdraw is closed within the cutoff window before presale end")]
+    WithdrawCutoffReached,
+
+    #[msg("Invalid signature voucher")]
+    InvalidSignatureVoucher,
+
+    #[msg("Signature voucher is expired")]
+    SignatureVoucherExpired,
+
+    #[msg("Invalid token gate config")]
+    InvalidTokenGateConfig,
+
+    #[msg("Insufficient gating token holding")]
+    InsufficientGatingTokenHolding,
+
+    #[msg("Merkle root config is revoked")]
+    MerkleRootConfigRevoked,
+
+    #[msg("Invalid denylist entry")]
+    InvalidDenylistEntry,
+
+    #[msg("Wallet is denylisted")]
+    WalletDenylisted,
+
+    #[msg("Invalid escrow terms override")]
+    InvalidEscrowTermsOverride,
+
+    #[msg("Invalid operator params")]
+    InvalidOperatorParams,
+
+    #[msg("Operator is expired")]
+    OperatorExpired,
+
+    #[msg("Operator quota exceeded")]
+    OperatorQuotaExceeded,
+
+    #[msg("Deposit cap is below the escrow total deposit")]
+    DepositCapBelowTotalDeposit,
+
+    #[msg("Escrow doesn't belong to the denylisted wallet")]
+    InvalidDenylistEscrow,
+
+    #[msg("Gating token is locked until the presale ends")]
+    GatingTokenLocked,
+
+    #[msg("Merkle root config is older than the one which set the escrow deposit cap")]
+    MerkleRootConfigOutdated,
 }
@@ -4,6 +4,7 @@ use crate::*;
 pub struct EvtFixedPricePresaleArgsCreate {
     pub presale: Pubkey,
     pub q_price: u128,
+    pub registry_q_prices: [u128; MAX_REGISTRY_Q_PRICE_COUNT],
 }
 
 #[event]
@@ -26,6 +27,7 @@ pub struct EvtPresaleVaultCreate {
     pub presale_end_time: u64,
     pub presale_maximum_cap: u64,
     pub presale_minimum_cap: u64,
+    pub finalization_callback_program: Pubkey,
 }
 
 #[event]
@@ -36,6 +38,16 @@ pub struct EvtEscrowCreate {
     pub total_escrow_count: u64,
 }
 
+#[event]
+pub struct EvtEscrowDepositCapUpdate {
+    pub presale: Pubkey,
+    pub escrow: Pubkey,
+    pub owner: Pubkey,
+    pub whitelist_mode: u8,
+    pub previous_deposit_cap: u64,
+    pub deposit_cap: u64,
+}
+
 #[event]
 pub struct EvtMerkleRootConfigCreate {
     pub owner: Pubkey,
@@ -61,6 +73,7 @@ pub struct EvtWithdraw {
     pub presale: Pubkey,
     pub escrow: Pubkey,
     pub withdraw_amount: u64,
+    pub withdraw_penalty: u64,
     pub escrow_total_deposit_amount: u64,
     pub presale_total_deposit_amount: u64,
     pub owner: Pubkey,
@@ -122,6 +135,20 @@ pub struct EvtOperatorCreate {
     pub creator: Pubkey,
     pub operator: Pubkey,
     pub operator_owner: Pubkey,
+    pub presale: Pubkey,
+    pub expiry_timestamp: u64,
+    pub max_escrow_count: u32,
+    pub max_total_deposit_cap: u64,
+}
+
+#[event]
+pub struct EvtOperatorUpdate {
+    pub creator: Pubkey,
+    pub operator: Pubkey,
+    pub operator_owner: Pubkey,
+    pub expiry_timestamp: u64,
+    pub max_escrow_count: u32,
+    pub max_total_deposit_cap: u64,
 }
 
 #[event]
@@ -150,3 +177,86 @@ pub struct EvtCloseMerkleRootConfig {
     pub merkle_root_config: Pubkey,
     pub owner: Pubkey,
 }
+
+#[event]
+pub struct EvtRevokeMerkleRootConfig {
+    pub presale: Pubkey,
+    pub merkle_root_config: Pubkey,
+    pub owner: Pubkey,
+    pub version: u64,
+}
+
+#[event]
+pub struct EvtPresaleFinalize {
+    pub presale: Pubkey,
+    pub finalization_callback_program: Pubkey,
+    pub presale_progress: u8,
+}
+
+#[event]
+pub struct EvtQuoteMintConfigCreate {
+    pub presale: Pubkey,
+    pub quote_mint_config: Pubkey,
+    pub quote_mint: Pubkey,
+    pub quote_token_vault: Pubkey,
+    pub conversion_ratio_numerator: u64,
+    pub conversion_ratio_denominator: u64,
+}
+
+#[event]
+pub struct EvtReferralCreate {
+    pub presale: Pubkey,
+    pub referral: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct EvtReferralDeposit {
+    pub presale: Pubkey,
+    pub referral: Pubkey,
+    pub referrer: Pubkey,
+    pub escrow: Pubkey,
+    pub deposit_amount: u64,
+    pub deposit_fee: u64,
+    pub referral_total_referred_deposit: u64,
+}
+
+#[event]
+pub struct EvtReferralFeeClaim {
+    pub presale: Pubkey,
+    pub referral: Pubkey,
+    pub referrer: Pubkey,
+    pub fee: u64,
+}
+
+#[event]
+pub struct EvtTokenGateConfigCreate {
+    pub presale: Pubkey,
+    pub config: Pubkey,
+    pub gating_mint: Pubkey,
+    pub registry_index: u8,
+    pub minimum_holding_amount: u64,
+    pub q_deposit_cap_per_holding: u128,
+}
+
+#[event]
+pub struct EvtGatingTokenWithdraw {
+    pub presale: Pubkey,
+    pub escrow: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtDenylistAdd {
+    pub presale: Pubkey,
+    pub denylist_entry: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct EvtDenylistRemove {
+    pub presale: Pubkey,
+    pub denylist_entry: Pubkey,
+    pub wallet: Pubkey,
+}
@@ -0,0 +1,91 @@
+use anchor_lang::solana_program::{hash::hash, instruction::Instruction, program::invoke_signed};
+
+use crate::*;
+
+/// Serialized after the `on_presale_finalized` anchor discriminator as the callback instruction data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PresaleFinalizedCallbackArgs {
+    pub presale_progress: u8,
+    pub presale_supply: u64,
+    pub total_deposit: u64,
+    pub total_escrow: u64,
+    pub total_deposit_fee: u64,
+}
+
+impl PresaleFinalizedCallbackArgs {
+    pub fn new(presale: &Presale, presale_progress: PresaleProgress) -> Self {
+        Self {
+            presale_progress: presale_progress.into(),
+            presale_supply: presale.presale_supply,
+            total_deposit: presale.total_deposit,
+            total_escrow: presale.total_escrow,
+            total_deposit_fee: presale.total_deposit_fee,
+        }
+    }
+}
+
+/// Callback program must expose an `on_presale_finalized(args: PresaleFinalizedCallbackArgs)` instruction with anchor discriminator, and the following accounts:
+/// presale, finalization callback authority (signer), base token vault, quote token vault.
+/// The finalization callback authority is a per presale PDA which owns nothing, it only proves the callback is invoked by the presale program.
+/// Remaining accounts are forwarded for callback specific accounts, without signer privilege.
+pub fn invoke_finalization_callback<'info>(
+    accounts: &FinalizePresaleCtx<'info>,
+    finalization_callback_authority_bump: u8,
+    callback_accounts: &[AccountInfo<'info>],
+    args: PresaleFinalizedCallbackArgs,
+) -> Result<()> {
+    let FinalizePresaleCtx {
+        presale,
+        finalization_callback_authority,
+        base_token_vault,
+        quote_token_vault,
+        finalization_callback_program,
+        ..
+    } = accounts;
+    let presale = presale.to_account_info();
+    let finalization_callback_authority = finalization_callback_authority.to_account_info();
+    let base_token_vault = base_token_vault.to_account_info();
+    let quote_token_vault = quote_token_vault.to_account_info();
+
+    let mut data = hash(b"global:on_presale_finalized").to_bytes()[..8].to_vec();
+    args.serialize(&mut data)?;
+
+    let presale_key = presale.key();
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(presale_key, false),
+        AccountMeta::new_readonly(finalization_callback_authority.key(), true),
+        AccountMeta::new_readonly(base_token_vault.key(), false),
+        AccountMeta::new_readonly(quote_token_vault.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        presale,
+        finalization_callback_authority,
+        base_token_vault,
+        quote_token_vault,
+    ];
+
+    for account in callback_accounts {
+        account_metas.push(AccountMeta {
+            pubkey: account.key(),
+            is_signer: false,
+            is_writable: account.is_writable,
+        });
+        account_infos.push(account.clone());
+    }
+
+    let instruction = Instruction {
+        program_id: finalization_callback_program.key(),
+        accounts: account_metas,
+        data,
+    };
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        crate::constants::seeds::FINALIZATION_CALLBACK_AUTHORITY_PREFIX,
+        presale_key.as_ref(),
+        &[finalization_callback_authority_bump],
+    ]];
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+
+    Ok(())
+}
@@ -8,3 +8,9 @@ mod process_create_escrow;
 
 mod process_create_permissioned_escrow_with_creator;
 pub use process_create_permissioned_escrow_with_creator::*;
+
+mod process_create_permissioned_escrow_with_signature;
+pub use process_create_permissioned_escrow_with_signature::*;
+
+mod process_create_permissioned_escrow_with_token_gate;
+pub use process_create_permissioned_escrow_with_token_gate::*;
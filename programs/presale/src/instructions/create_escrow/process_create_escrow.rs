@@ -7,6 +7,8 @@ pub struct HandleCreateEscrowArgs<'a, 'b, 'c> {
     pub owner_pubkey: Pubkey,
     pub registry_index: u8,
     pub deposit_cap: Option<u64>,
+    pub terms_override: Option<EscrowTermsOverride>,
+    pub locked_gating_amount: u64,
 }
 
 pub fn process_create_escrow(args: HandleCreateEscrowArgs) -> Result<()> {
@@ -17,6 +19,8 @@ pub fn process_create_escrow(args: HandleCreateEscrowArgs) -> Result<()> {
         owner_pubkey,
         registry_index,
         deposit_cap,
+        terms_override,
+        locked_gating_amount,
     } = args;
 
     // 1. Ensure presale is open for deposit
@@ -36,6 +40,7 @@ pub fn process_create_escrow(args: HandleCreateEscrowArgs) -> Result<()> {
     // 3. Within the global deposit cap
     // Integrator have to verify this offchain. If mistake was made, they have to use latest merkle config version + 1 and reconstruct the new tree with the valid cap
     let registry = presale.get_presale_registry(registry_index.into())?;
+    registry.ensure_within_deposit_window(current_timestamp)?;
 
     if let Some(deposit_cap) = deposit_cap {
         require!(deposit_cap > 0, PresaleError::InvalidDepositCap);
@@ -49,6 +54,10 @@ pub fn process_create_escrow(args: HandleCreateEscrowArgs) -> Result<()> {
 
     let deposit_cap = deposit_cap.unwrap_or(registry.buyer_maximum_deposit_cap);
 
+    if let Some(terms_override) = terms_override.as_ref() {
+        terms_override.validate(presale)?;
+    }
+
     // 4. Initialize the escrow account
     let mut escrow = escrow.load_init()?;
     escrow.initialize(
@@ -59,6 +68,12 @@ pub fn process_create_escrow(args: HandleCreateEscrowArgs) -> Result<()> {
         deposit_cap,
     )?;
 
+    if let Some(terms_override) = terms_override.as_ref() {
+        escrow.apply_terms_override(terms_override);
+    }
+
+    escrow.lock_gating_token(locked_gating_amount);
+
     // 4. Update the presale state
     presale.increase_escrow_count(registry_index)?;
 
@@ -36,6 +36,7 @@ pub struct CreatePermissionedEscrowWithCreatorCtx<'info> {
     /// CHECK: Owner of the escrow account
     pub owner: UncheckedAccount<'info>,
 
+    #[account(mut)]
     pub operator: AccountLoader<'info, Operator>,
 
     pub operator_owner: Signer<'info>,
@@ -52,27 +53,34 @@ pub fn handle_create_permissioned_escrow_with_creator(
 ) -> Result<()> {
     let mut presale = ctx.accounts.presale.load_mut()?;
 
-    // 1. Ensure presale is permissioned with authority
-    let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
+    let CreatePermissionedEscrowWithCreatorParams {
+        registry_index,
+        deposit_cap,
+        ..
+    } = params;
+
+    // 1. Ensure presale registry is permissioned with authority
+    let whitelist_mode = presale.get_registry_whitelist_mode(registry_index.into())?;
     require!(
         whitelist_mode == WhitelistMode::PermissionWithAuthority,
         PresaleError::InvalidPresaleWhitelistMode
     );
 
     // 2. Ensure rightful operator is provided
-    let operator = ctx.accounts.operator.load()?;
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let mut operator = ctx.accounts.operator.load_mut()?;
     ensure_operator_belongs_to_presale_creator(
+        &ctx.accounts.presale.key(),
         &presale,
-        &operator,
+        &mut operator,
         &ctx.accounts.operator_owner.key(),
+        OperatorGrant {
+            escrow_count: 1,
+            deposit_cap,
+        },
+        current_timestamp,
     )?;
 
-    let CreatePermissionedEscrowWithCreatorParams {
-        registry_index,
-        deposit_cap,
-        ..
-    } = params;
-
     process_create_escrow(HandleCreateEscrowArgs {
         presale: &mut presale,
         escrow: &ctx.accounts.escrow,
@@ -80,22 +88,27 @@ pub fn handle_create_permissioned_escrow_with_creator(
         owner_pubkey: ctx.accounts.owner.key(),
         registry_index,
         deposit_cap: Some(deposit_cap),
+        terms_override: None,
+        locked_gating_amount: 0,
     })?;
 
     emit_cpi!(EvtEscrowCreate {
         presale: ctx.accounts.presale.key(),
         owner: ctx.accounts.owner.key(),
-        whitelist_mode: presale.whitelist_mode,
+        whitelist_mode: whitelist_mode.into(),
         total_escrow_count: presale.total_escrow,
     });
 
     Ok(())
 }
 
-fn ensure_operator_belongs_to_presale_creator(
+pub fn ensure_operator_belongs_to_presale_creator(
+    presale_pubkey: &Pubkey,
     presale: &Presale,
-    operator: &Operator,
+    operator: &mut Operator,
     operator_owner: &Pubkey,
+    grant: OperatorGrant,
+    current_timestamp: u64,
 ) -> Result<()> {
     require!(
         operator.owner == *operator_owner,
@@ -106,5 +119,16 @@ fn ensure_operator_belongs_to_presale_creator(
         operator.creator == presale.owner,
         PresaleError::InvalidOperator
     );
-    Ok(())
+
+    require!(
+        operator.is_allowed_for_presale(presale_pubkey),
+        PresaleError::InvalidOperator
+    );
+
+    require!(
+        !operator.is_expired(current_timestamp),
+        PresaleError::OperatorExpired
+    );
+
+    operator.grant(grant)
 }
@@ -5,17 +5,79 @@ use crate::{
     *,
 };
 use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
 // We need to discern between leaf and intermediate nodes to prevent trivial second
 // pre-image attacks.
 // https://flawed.net.nz/2018/02/21/attacking-merkle-trees-with-a-second-preimage-attack
 const LEAF_PREFIX: &[u8] = &[0];
 
+/// Vesting terms of the escrow, replacing the presale immediate release bps and vest duration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VestingOverride {
+    pub immediate_release_bps: u16,
+    pub vest_duration: u64,
+}
+
+/// Personal terms of the escrow owner carried by the merkle leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EscrowTermsOverride {
+    /// Discount on the registry token price in bps. Fixed price presale only.
+    pub price_discount_bps: u16,
+    pub vesting: Option<VestingOverride>,
+}
+
+impl EscrowTermsOverride {
+    pub fn validate(&self, presale: &Presale) -> Result<()> {
+        if self.price_discount_bps > 0 {
+            let presale_mode: PresaleMode = presale.presale_mode.safe_cast()?;
+            require!(
+                presale_mode == PresaleMode::FixedPrice
+                    && self.price_discount_bps < MAX_FEE_BASIS_POINTS,
+                PresaleError::InvalidEscrowTermsOverride
+            );
+        }
+
+        if let Some(VestingOverride {
+            immediate_release_bps,
+            vest_duration,
+        }) = self.vesting
+        {
+            require!(
+                immediate_release_bps <= MAX_FEE_BASIS_POINTS
+                    && vest_duration.safe_add(presale.lock_duration)?
+                        < MAXIMUM_LOCK_AND_VEST_DURATION,
+                PresaleError::InvalidEscrowTermsOverride
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Bytes appended to the merkle leaf. Leaf without terms override keeps the original layout.
+    pub fn to_leaf_bytes(&self) -> Vec<u8> {
+        let VestingOverride {
+            immediate_release_bps,
+            vest_duration,
+        } = self.vesting.unwrap_or_default();
+
+        [
+            self.price_discount_bps.to_le_bytes().as_ref(),
+            &[u8::from(self.vesting.is_some())],
+            immediate_release_bps.to_le_bytes().as_ref(),
+            vest_duration.to_le_bytes().as_ref(),
+        ]
+        .concat()
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct CreatePermissionedEscrowWithMerkleProofParams {
     pub proof: Vec<[u8; 32]>,
     pub registry_index: u8,
     pub deposit_cap: u64,
+    /// Personal terms of the owner. Must match the merkle leaf.
+    pub terms_override: Option<EscrowTermsOverride>,
     pub padding: [u8; 32],
 }
 
@@ -58,30 +120,36 @@ pub fn handle_create_permissioned_escrow_with_merkle_proof(
 ) -> Result<()> {
     let mut presale = ctx.accounts.presale.load_mut()?;
 
-    // 1. Ensure presale is permissioned with merkle proof
-    let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
-    require!(
-        whitelist_mode == WhitelistMode::PermissionWithMerkleProof,
-        PresaleError::InvalidPresaleWhitelistMode
-    );
-
     let CreatePermissionedEscrowWithMerkleProofParams {
         registry_index,
         proof,
         deposit_cap,
+        terms_override,
         ..
     } = params;
 
-    // 2. Verify the merkle proof
+    // 1. Ensure presale registry is permissioned with merkle proof
+    let whitelist_mode = presale.get_registry_whitelist_mode(registry_index.into())?;
+    require!(
+        whitelist_mode == WhitelistMode::PermissionWithMerkleProof,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    // 2. Verify the merkle proof against a non revoked config
     let merkle_root_config = ctx.accounts.merkle_root_config.load()?;
-    let node = hashv(&[
-        &ctx.accounts.owner.key().to_bytes(),
-        registry_index.to_le_bytes().as_ref(),
-        deposit_cap.to_le_bytes().as_ref(),
-    ]);
-    let node = hashv(&[LEAF_PREFIX, &node.to_bytes()]);
     require!(
-        verify(proof, merkle_root_config.root, node.to_bytes()),
+        !merkle_root_config.is_revoked(),
+        PresaleError::MerkleRootConfigRevoked
+    );
+
+    let node = get_merkle_leaf(
+        &ctx.accounts.owner.key(),
+        registry_index,
+        deposit_cap,
+        terms_override.as_ref(),
+    );
+    require!(
+        verify(proof, merkle_root_config.root, node),
         PresaleError::InvalidMerkleProof
     );
 
@@ -92,18 +160,45 @@ pub fn handle_create_permissioned_escrow_with_merkle_proof(
         owner_pubkey: ctx.accounts.owner.key(),
         registry_index,
         deposit_cap: Some(deposit_cap),
+        terms_override,
+        locked_gating_amount: 0,
     })?;
 
+    ctx.accounts
+        .escrow
+        .load_mut()?
+        .set_merkle_root_config_version(merkle_root_config.version);
+
     emit_cpi!(EvtEscrowCreate {
         presale: ctx.accounts.presale.key(),
         owner: ctx.accounts.owner.key(),
-        whitelist_mode: presale.whitelist_mode,
+        whitelist_mode: whitelist_mode.into(),
         total_escrow_count: presale.total_escrow,
     });
 
     Ok(())
 }
 
+/// Leaf of the escrow owner in the merkle tree, prefixed to be distinguished from intermediate nodes
+pub fn get_merkle_leaf(
+    owner: &Pubkey,
+    registry_index: u8,
+    deposit_cap: u64,
+    terms_override: Option<&EscrowTermsOverride>,
+) -> [u8; 32] {
+    let terms_override_bytes = terms_override
+        .map(|terms_override| terms_override.to_leaf_bytes())
+        .unwrap_or_default();
+    let node = hashv(&[
+        &owner.to_bytes(),
+        registry_index.to_le_bytes().as_ref(),
+        deposit_cap.to_le_bytes().as_ref(),
+        &terms_override_bytes,
+    ]);
+
+    hashv(&[LEAF_PREFIX, &node.to_bytes()]).to_bytes()
+}
+
 /// Modified version of https://github.com/saber-hq/merkle-distributor/blob/ac937d1901033ecb7fa3b0db22f7b39569c8e052/programs/merkle-distributor/src/merkle_proof.rs#L8
 /// This function deals with verification of Merkle trees (hash trees).
 /// Direct port of https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v3.4.0/contracts/cryptography/MerkleProof.sol
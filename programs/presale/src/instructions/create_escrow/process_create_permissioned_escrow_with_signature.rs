@@ -0,0 +1,246 @@
+use crate::{
+    instructions::create_escrow::process_create_escrow::{
+        process_create_escrow, HandleCreateEscrowArgs,
+    },
+    *,
+};
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+// Layout of the ed25519 program instruction data. https://docs.anza.xyz/runtime/programs#ed25519-program
+const ED25519_SIGNATURE_OFFSETS_START: usize = 2;
+const ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const ED25519_PUBKEY_SERIALIZED_SIZE: usize = 32;
+// Instruction index referring to the ed25519 instruction itself
+const ED25519_CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct CreatePermissionedEscrowWithSignatureParams {
+    pub registry_index: u8,
+    pub deposit_cap: u64,
+    /// Timestamp after which the signature voucher can no longer be used
+    pub expiry: u64,
+    /// Nonce of the signature voucher. A voucher can only be used once per presale and owner.
+    pub nonce: u64,
+    pub padding: [u8; 24],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreatePermissionedEscrowWithSignatureParams)]
+pub struct CreatePermissionedEscrowWithSignatureCtx<'info> {
+    #[account(mut)]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::ESCROW_PREFIX,
+            presale.key().as_ref(),
+            owner.key().as_ref(),
+            params.registry_index.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    /// CHECK: Owner of the escrow account
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::SIGNATURE_VOUCHER_RECEIPT_PREFIX,
+            presale.key().as_ref(),
+            owner.key().as_ref(),
+            params.nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + SignatureVoucherReceipt::INIT_SPACE
+    )]
+    pub signature_voucher_receipt: AccountLoader<'info, SignatureVoucherReceipt>,
+
+    /// Operator whose owner signed the voucher
+    #[account(mut)]
+    pub operator: AccountLoader<'info, Operator>,
+
+    /// CHECK: Instructions sysvar account
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Message signed by the operator owner to authorize the escrow creation. Prefixed with the program id and a tag to be bound to this program and purpose.
+pub fn get_signature_voucher_message(
+    presale: &Pubkey,
+    owner: &Pubkey,
+    registry_index: u8,
+    deposit_cap: u64,
+    expiry: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    [
+        crate::ID.as_ref(),
+        SIGNATURE_VOUCHER_TAG,
+        presale.as_ref(),
+        owner.as_ref(),
+        registry_index.to_le_bytes().as_ref(),
+        deposit_cap.to_le_bytes().as_ref(),
+        expiry.to_le_bytes().as_ref(),
+        nonce.to_le_bytes().as_ref(),
+    ]
+    .concat()
+}
+
+pub fn handle_create_permissioned_escrow_with_signature(
+    ctx: Context<CreatePermissionedEscrowWithSignatureCtx>,
+    params: CreatePermissionedEscrowWithSignatureParams,
+) -> Result<()> {
+    let mut presale = ctx.accounts.presale.load_mut()?;
+
+    let CreatePermissionedEscrowWithSignatureParams {
+        registry_index,
+        deposit_cap,
+        expiry,
+        nonce,
+        ..
+    } = params;
+
+    // 1. Ensure presale registry is permissioned with signature
+    let whitelist_mode = presale.get_registry_whitelist_mode(registry_index.into())?;
+    require!(
+        whitelist_mode == WhitelistMode::PermissionWithSignature,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    // 2. Ensure the voucher is not expired
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    require!(
+        current_timestamp <= expiry,
+        PresaleError::SignatureVoucherExpired
+    );
+
+    // 3. Verify the voucher was signed by the rightful operator
+    let message = get_signature_voucher_message(
+        &ctx.accounts.presale.key(),
+        &ctx.accounts.owner.key(),
+        registry_index,
+        deposit_cap,
+        expiry,
+        nonce,
+    );
+    let voucher_signer = get_ed25519_verified_signer(&ctx.accounts.instructions_sysvar, &message)?;
+
+    let mut operator = ctx.accounts.operator.load_mut()?;
+    ensure_operator_belongs_to_presale_creator(
+        &ctx.accounts.presale.key(),
+        &presale,
+        &mut operator,
+        &voucher_signer,
+        OperatorGrant {
+            escrow_count: 1,
+            deposit_cap,
+        },
+        current_timestamp,
+    )?;
+
+    // 4. Consume the voucher
+    let mut signature_voucher_receipt = ctx.accounts.signature_voucher_receipt.load_init()?;
+    signature_voucher_receipt.initialize(
+        ctx.accounts.presale.key(),
+        ctx.accounts.owner.key(),
+        nonce,
+    );
+
+    process_create_escrow(HandleCreateEscrowArgs {
+        presale: &mut presale,
+        escrow: &ctx.accounts.escrow,
+        presale_pubkey: ctx.accounts.presale.key(),
+        owner_pubkey: ctx.accounts.owner.key(),
+        registry_index,
+        deposit_cap: Some(deposit_cap),
+        terms_override: None,
+        locked_gating_amount: 0,
+    })?;
+
+    emit_cpi!(EvtEscrowCreate {
+        presale: ctx.accounts.presale.key(),
+        owner: ctx.accounts.owner.key(),
+        whitelist_mode: whitelist_mode.into(),
+        total_escrow_count: presale.total_escrow,
+    });
+
+    Ok(())
+}
+
+/// Returns the signer of the message verified by the ed25519 program instruction right before the current instruction.
+/// The ed25519 program fails the whole transaction when the signature is invalid, therefore only the signed content is checked here.
+fn get_ed25519_verified_signer(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+) -> Result<Pubkey> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let ed25519_ix_index = current_index
+        .checked_sub(1)
+        .ok_or(PresaleError::InvalidSignatureVoucher)?;
+    let ed25519_ix = load_instruction_at_checked(ed25519_ix_index.into(), instructions_sysvar)?;
+
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID,
+        PresaleError::InvalidSignatureVoucher
+    );
+
+    let data = ed25519_ix.data.as_slice();
+    let read_u16 = |offset: usize| -> Result<u16> {
+        let bytes = data
+            .get(offset..offset + 2)
+            .ok_or(PresaleError::InvalidSignatureVoucher)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+
+    // Only a single signature is allowed, so the verified signer is not ambiguous
+    require!(
+        data.len() >= ED25519_SIGNATURE_OFFSETS_START + ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE
+            && data[0] == 1,
+        PresaleError::InvalidSignatureVoucher
+    );
+
+    let offsets_start = ED25519_SIGNATURE_OFFSETS_START;
+    let signature_instruction_index = read_u16(offsets_start + 2)?;
+    let public_key_offset = usize::from(read_u16(offsets_start + 4)?);
+    let public_key_instruction_index = read_u16(offsets_start + 6)?;
+    let message_data_offset = usize::from(read_u16(offsets_start + 8)?);
+    let message_data_size = usize::from(read_u16(offsets_start + 10)?);
+    let message_instruction_index = read_u16(offsets_start + 12)?;
+
+    // Signature, public key and message must be within the ed25519 instruction itself
+    require!(
+        signature_instruction_index == ED25519_CURRENT_INSTRUCTION_INDEX
+            && public_key_instruction_index == ED25519_CURRENT_INSTRUCTION_INDEX
+            && message_instruction_index == ED25519_CURRENT_INSTRUCTION_INDEX,
+        PresaleError::InvalidSignatureVoucher
+    );
+
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(PresaleError::InvalidSignatureVoucher)?;
+    require!(
+        signed_message == message,
+        PresaleError::InvalidSignatureVoucher
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + ED25519_PUBKEY_SERIALIZED_SIZE)
+        .ok_or(PresaleError::InvalidSignatureVoucher)?;
+
+    Ok(Pubkey::try_from(public_key).map_err(|_| PresaleError::InvalidSignatureVoucher)?)
+}
@@ -0,0 +1,155 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    instructions::create_escrow::process_create_escrow::{
+        process_create_escrow, HandleCreateEscrowArgs,
+    },
+    *,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct CreatePermissionedEscrowWithTokenGateParams {
+    pub registry_index: u8,
+    /// Amount of the gating mint locked until the presale ends. The deposit cap is derived from it.
+    pub holding_amount: u64,
+    pub padding: [u8; 24],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreatePermissionedEscrowWithTokenGateParams)]
+pub struct CreatePermissionedEscrowWithTokenGateCtx<'info> {
+    #[account(mut)]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::ESCROW_PREFIX,
+            presale.key().as_ref(),
+            owner.key().as_ref(),
+            params.registry_index.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = presale,
+        has_one = gating_mint @ PresaleError::InvalidTokenGateConfig,
+        seeds = [
+            crate::constants::seeds::TOKEN_GATE_CONFIG_PREFIX,
+            presale.key().as_ref(),
+            params.registry_index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub token_gate_config: AccountLoader<'info, TokenGateConfig>,
+
+    pub gating_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of the escrow owner holding the gating mint
+    #[account(
+        mut,
+        token::authority = owner,
+        token::mint = gating_mint,
+        token::token_program = token_program,
+    )]
+    pub holding_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Vault locking the gating token of the escrow, so the same holding can't be presented by another wallet
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::GATING_TOKEN_VAULT_PREFIX,
+            escrow.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        token::mint = gating_mint,
+        token::authority = presale_authority,
+        token::token_program = token_program,
+    )]
+    pub gating_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: presale_authority
+    #[account(
+        address = crate::presale_authority::ID
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_permissioned_escrow_with_token_gate(
+    ctx: Context<CreatePermissionedEscrowWithTokenGateCtx>,
+    params: CreatePermissionedEscrowWithTokenGateParams,
+) -> Result<()> {
+    let mut presale = ctx.accounts.presale.load_mut()?;
+
+    let CreatePermissionedEscrowWithTokenGateParams {
+        registry_index,
+        holding_amount,
+        ..
+    } = params;
+
+    // 1. Ensure presale registry is permissioned with token gate
+    let whitelist_mode = presale.get_registry_whitelist_mode(registry_index.into())?;
+    require!(
+        whitelist_mode == WhitelistMode::PermissionWithTokenGate,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    // 2. Lock the holding until the presale ends. Deposit cap is derived from the amount received by the vault.
+    transfer_from_user(
+        &ctx.accounts.owner,
+        &ctx.accounts.gating_mint,
+        &ctx.accounts.holding_token_account,
+        &ctx.accounts.gating_token_vault,
+        &ctx.accounts.token_program,
+        holding_amount,
+        None,
+        None,
+    )?;
+
+    let locked_gating_amount =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.gating_mint, holding_amount)?.amount;
+
+    // 3. Derive the deposit cap from the locked holding. A locked holding is required, so the vault always outlives the escrow.
+    require!(
+        locked_gating_amount > 0,
+        PresaleError::InsufficientGatingTokenHolding
+    );
+
+    let token_gate_config = ctx.accounts.token_gate_config.load()?;
+    let presale_registry = presale.get_presale_registry(registry_index.into())?;
+    let deposit_cap = token_gate_config.get_deposit_cap(locked_gating_amount, presale_registry)?;
+
+    process_create_escrow(HandleCreateEscrowArgs {
+        presale: &mut presale,
+        escrow: &ctx.accounts.escrow,
+        presale_pubkey: ctx.accounts.presale.key(),
+        owner_pubkey: ctx.accounts.owner.key(),
+        registry_index,
+        deposit_cap: Some(deposit_cap),
+        terms_override: None,
+        locked_gating_amount,
+    })?;
+
+    emit_cpi!(EvtEscrowCreate {
+        presale: ctx.accounts.presale.key(),
+        owner: ctx.accounts.owner.key(),
+        whitelist_mode: whitelist_mode.into(),
+        total_escrow_count: presale.total_escrow,
+    });
+
+    Ok(())
+}
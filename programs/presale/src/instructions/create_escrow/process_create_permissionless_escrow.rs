@@ -5,8 +5,15 @@ use crate::{
     *,
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct CreatePermissionlessEscrowParams {
+    pub registry_index: u8,
+    pub padding: [u8; 32],
+}
+
 #[event_cpi]
 #[derive(Accounts)]
+#[instruction(params: CreatePermissionlessEscrowParams)]
 pub struct CreatePermissionlessEscrowCtx<'info> {
     #[account(mut)]
     pub presale: AccountLoader<'info, Presale>,
@@ -17,7 +24,7 @@ pub struct CreatePermissionlessEscrowCtx<'info> {
             crate::constants::seeds::ESCROW_PREFIX,
             presale.key().as_ref(),
             owner.key().as_ref(),
-            &crate::constants::DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.to_le_bytes(),
+            params.registry_index.to_le_bytes().as_ref(),
         ],
         bump,
         payer = payer,
@@ -36,29 +43,41 @@ pub struct CreatePermissionlessEscrowCtx<'info> {
 
 pub fn handle_create_permissionless_escrow(
     ctx: Context<CreatePermissionlessEscrowCtx>,
+    params: CreatePermissionlessEscrowParams,
 ) -> Result<()> {
     let mut presale = ctx.accounts.presale.load_mut()?;
 
-    // Ensure presale is permissionless
-    let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
+    // Ensure presale registry is permissionless
+    let CreatePermissionlessEscrowParams { registry_index, .. } = params;
+    let whitelist_mode = presale.get_registry_whitelist_mode(registry_index.into())?;
     require!(
         !whitelist_mode.is_permissioned(),
         PresaleError::InvalidPresaleWhitelistMode
     );
 
+    // Ensure owner is not denylisted
+    ensure_not_denylisted(
+        &presale,
+        &ctx.accounts.presale.key(),
+        &ctx.accounts.owner.key(),
+        ctx.remaining_accounts,
+    )?;
+
     process_create_escrow(HandleCreateEscrowArgs {
         presale: &mut presale,
         escrow: &ctx.accounts.escrow,
         presale_pubkey: ctx.accounts.presale.key(),
         owner_pubkey: ctx.accounts.owner.key(),
-        registry_index: crate::constants::DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        registry_index,
         deposit_cap: None,
+        terms_override: None,
+        locked_gating_amount: 0,
     })?;
 
     emit_cpi!(EvtEscrowCreate {
         presale: ctx.accounts.presale.key(),
         owner: ctx.accounts.owner.key(),
-        whitelist_mode: presale.whitelist_mode,
+        whitelist_mode: whitelist_mode.into(),
         total_escrow_count: presale.total_escrow,
     });
 
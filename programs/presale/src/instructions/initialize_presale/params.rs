@@ -12,10 +12,36 @@ fn validate_presale_registries(
     );
 
     let mut presale_supply = 0u128;
+    let mut total_registry_maximum_cap = 0u128;
+    let mut is_all_registries_capped = true;
 
     for registry in presale_registries {
         registry.validate(presale_params)?;
         presale_supply = presale_supply.safe_add(u128::from(registry.presale_supply))?;
+
+        if registry.registry_maximum_cap == 0 {
+            is_all_registries_capped = false;
+        }
+        total_registry_maximum_cap =
+            total_registry_maximum_cap.safe_add(u128::from(registry.registry_maximum_cap))?;
+    }
+
+    // Unsold rollover registry must be one of the registries, and there must be other registries to roll over from
+    if presale_params.enable_unsold_rollover == u8::from(BoolType::True) {
+        require!(
+            presale_registries.len() > 1
+                && usize::from(presale_params.unsold_rollover_registry_index)
+                    < presale_registries.len(),
+            PresaleError::InvalidPresaleInfo
+        );
+    }
+
+    // Presale minimum cap must be reachable when every registry is capped
+    if is_all_registries_capped {
+        require!(
+            total_registry_maximum_cap >= u128::from(presale_params.presale_minimum_cap),
+            PresaleError::InvalidPresaleInfo
+        );
     }
 
     require!(
@@ -26,17 +52,6 @@ fn validate_presale_registries(
     // Must have at least 1 presale registry
     require!(presale_supply > 0, PresaleError::InvalidTokenSupply);
 
-    // If presale have multiple registries. Whitelist mode must be Permissioned mode.
-    // Reason: It make no sense for a single user to deposit to multiple registries which might have different price when it's dynamic price mode.
-    // Note: Presale creator have to make sure user doesn't duplicate across registries.
-    if presale_registries.len() > 1 {
-        let whitelist_mode: WhitelistMode = presale_params.whitelist_mode.safe_cast()?;
-        require!(
-            whitelist_mode.is_permissioned(),
-            PresaleError::MultiplePresaleRegistriesNotAllowed
-        );
-    }
-
     Ok(())
 }
 
@@ -44,7 +59,8 @@ fn validate_presale_registries(
 pub struct InitializePresaleArgs {
     pub presale_params: PresaleArgs,
     pub locked_vesting_params: OptionalNonZeroLockedVestingArgs,
-    pub padding: [u8; 32],
+    /// Program invoked via CPI when the presale is finalized. Default pubkey means no callback.
+    pub finalization_callback_program: Pubkey,
     pub presale_registries: Vec<PresaleRegistryArgs>,
 }
 
@@ -66,6 +82,12 @@ impl InitializePresaleArgs {
 
         validate_presale_registries(&self.presale_registries, &self.presale_params)?;
 
+        // Presale program can't be its own callback
+        require!(
+            self.finalization_callback_program != crate::ID,
+            PresaleError::InvalidPresaleInfo
+        );
+
         let locked_vesting_params = self.locked_vesting_params.option();
 
         if let Some(locked_vesting) = locked_vesting_params {
@@ -82,7 +104,19 @@ pub struct PresaleRegistryArgs {
     pub buyer_maximum_deposit_cap: u64,
     pub presale_supply: u64,
     pub deposit_fee_bps: u16,
-    pub padding: [u8; 32],
+    /// When deposit to the registry opens. 0 means it follows the presale start time.
+    pub deposit_start_time: u64,
+    /// When deposit to the registry closes. 0 means it follows the presale end time.
+    pub deposit_end_time: u64,
+    /// Whether the registry use its own whitelist mode instead of the presale whitelist mode
+    pub override_whitelist_mode: u8,
+    /// Registry whitelist mode. Only used when override_whitelist_mode is set
+    pub whitelist_mode: u8,
+    /// Maximum amount of quote token the registry can raise. 0 means it's only bounded by the presale maximum cap.
+    pub registry_maximum_cap: u64,
+    /// Share of the deposit fee claimable by the referrer of the deposit, in bps of the deposit fee.
+    pub referral_fee_bps: u16,
+    pub padding: [u8; 4],
 }
 
 impl PresaleRegistryArgs {
@@ -93,6 +127,15 @@ impl PresaleRegistryArgs {
             && self.deposit_fee_bps == 0
     }
 
+    pub fn get_whitelist_mode(&self, presale_whitelist_mode: u8) -> Result<WhitelistMode> {
+        let whitelist_mode = if self.override_whitelist_mode == u8::from(BoolType::True) {
+            self.whitelist_mode
+        } else {
+            presale_whitelist_mode
+        };
+        Ok(whitelist_mode.safe_cast()?)
+    }
+
     pub fn validate(&self, presale_args: &PresaleArgs) -> Result<()> {
         require!(
             self.buyer_maximum_deposit_cap >= self.buyer_minimum_deposit_cap,
@@ -116,6 +159,51 @@ impl PresaleRegistryArgs {
             PresaleError::InvalidPresaleInfo
         );
 
+        require!(
+            self.referral_fee_bps <= MAX_FEE_BASIS_POINTS,
+            PresaleError::InvalidPresaleInfo
+        );
+
+        let maybe_override_whitelist_mode = BoolType::try_from(self.override_whitelist_mode);
+        require!(
+            maybe_override_whitelist_mode.is_ok(),
+            PresaleError::InvalidType
+        );
+
+        if self.override_whitelist_mode == u8::from(BoolType::True) {
+            let maybe_whitelist_mode = WhitelistMode::try_from(self.whitelist_mode);
+            require!(
+                maybe_whitelist_mode.is_ok(),
+                PresaleError::InvalidPresaleInfo
+            );
+        }
+
+        if self.registry_maximum_cap > 0 {
+            require!(
+                self.registry_maximum_cap >= self.buyer_minimum_deposit_cap
+                    && self.registry_maximum_cap <= presale_args.presale_maximum_cap,
+                PresaleError::InvalidPresaleInfo
+            );
+        }
+
+        // Registry deposit window must be within the presale window
+        if self.deposit_start_time > 0 {
+            require!(
+                self.deposit_start_time >= presale_args.presale_start_time
+                    && self.deposit_start_time < presale_args.presale_end_time,
+                PresaleError::InvalidPresaleInfo
+            );
+        }
+
+        if self.deposit_end_time > 0 {
+            require!(
+                self.deposit_end_time > presale_args.presale_start_time
+                    && self.deposit_end_time > self.deposit_start_time
+                    && self.deposit_end_time <= presale_args.presale_end_time,
+                PresaleError::InvalidPresaleInfo
+            );
+        }
+
         Ok(())
     }
 }
@@ -132,7 +220,21 @@ pub struct PresaleArgs {
     pub unsold_token_action: u8,
     // Only applicable to fcfs and fixed price
     pub disable_earlier_presale_end_once_cap_reached: u8,
-    pub padding: [u8; 30],
+    /// Roll over unsold base token of other registries to the unsold rollover registry
+    pub enable_unsold_rollover: u8,
+    /// Registry which receives the unsold base token of other registries
+    pub unsold_rollover_registry_index: u8,
+    /// Only applicable to prorata. Withdraw penalty rises linearly from 0 at presale start to this value at presale end.
+    pub withdraw_penalty_bps: u16,
+    /// Only applicable to fixed price and prorata. Withdraw is rejected during the final N seconds of the presale.
+    pub withdraw_cutoff_duration: u64,
+    /// Deposit within this duration before presale end extends the presale end time by anti_snipe_extension. 0 means disabled.
+    pub anti_snipe_window: u32,
+    /// Duration the presale end time is extended by for each deposit within the anti-snipe window
+    pub anti_snipe_extension: u32,
+    /// Maximum total duration the presale end time can be extended by
+    pub anti_snipe_max_total_extension: u32,
+    pub padding: [u8; 6],
 }
 
 impl PresaleArgs {
@@ -195,6 +297,45 @@ impl PresaleArgs {
             PresaleError::InvalidType
         );
 
+        let maybe_enable_unsold_rollover = BoolType::try_from(self.enable_unsold_rollover);
+        require!(
+            maybe_enable_unsold_rollover.is_ok(),
+            PresaleError::InvalidType
+        );
+
+        require!(
+            self.withdraw_penalty_bps <= MAX_WITHDRAW_PENALTY_BPS
+                && (self.withdraw_penalty_bps == 0
+                    || self.presale_mode == u8::from(PresaleMode::Prorata)),
+            PresaleError::InvalidPresaleInfo
+        );
+
+        require!(
+            self.withdraw_cutoff_duration < presale_duration
+                && (self.withdraw_cutoff_duration == 0
+                    || self.presale_mode != u8::from(PresaleMode::Fcfs)),
+            PresaleError::InvalidPresaleInfo
+        );
+
+        // Anti-snipe extension is either disabled or fully configured, and the extended presale must not exceed the maximum presale duration
+        let is_anti_snipe_enabled = self.anti_snipe_window > 0
+            || self.anti_snipe_extension > 0
+            || self.anti_snipe_max_total_extension > 0;
+
+        if is_anti_snipe_enabled {
+            let extended_presale_duration =
+                presale_duration.safe_add(self.anti_snipe_max_total_extension.into())?;
+
+            require!(
+                self.anti_snipe_window > 0
+                    && self.anti_snipe_extension > 0
+                    && self.anti_snipe_extension <= self.anti_snipe_max_total_extension
+                    && u64::from(self.anti_snipe_window) < presale_duration
+                    && extended_presale_duration <= MAXIMUM_PRESALE_DURATION,
+                PresaleError::InvalidPresaleInfo
+            );
+        }
+
         Ok(())
     }
 }
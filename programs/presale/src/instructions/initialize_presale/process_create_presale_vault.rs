@@ -7,6 +7,7 @@ pub struct ProcessCreatePresaleVaultArgs<'a, 'c: 'info, 'd, 'e, 'info> {
     pub locked_vesting_params: Option<&'d LockedVestingArgs>,
     pub remaining_accounts: &'e mut &'c [AccountInfo<'info>],
     pub mint_pubkeys: InitializePresaleVaultAccountPubkeys,
+    pub finalization_callback_program: Pubkey,
 }
 
 pub fn process_create_presale_vault(params: ProcessCreatePresaleVaultArgs) -> Result<()> {
@@ -17,6 +18,7 @@ pub fn process_create_presale_vault(params: ProcessCreatePresaleVaultArgs) -> Re
         locked_vesting_params,
         remaining_accounts,
         mint_pubkeys,
+        finalization_callback_program,
     } = params;
 
     let mut presale_state = presale.load_init()?;
@@ -47,6 +49,7 @@ pub fn process_create_presale_vault(params: ProcessCreatePresaleVaultArgs) -> Re
         base,
         base_token_program,
         quote_token_program,
+        finalization_callback_program,
     })?;
 
     // 2. Initialize presale mode specific fields
@@ -98,7 +98,7 @@ pub fn handle_initialize_presale<'a, 'b, 'c: 'info, 'info>(
         presale_params,
         locked_vesting_params,
         presale_registries,
-        ..
+        finalization_callback_program,
     } = args;
 
     // 3. Initialize vault
@@ -122,6 +122,7 @@ pub fn handle_initialize_presale<'a, 'b, 'c: 'info, 'info>(
         locked_vesting_params: locked_vesting_params.as_ref(),
         mint_pubkeys,
         remaining_accounts: &mut remaining_account_slice,
+        finalization_callback_program,
     })?;
 
     let presale_pool_supply = presale_registries
@@ -168,6 +169,7 @@ pub fn handle_initialize_presale<'a, 'b, 'c: 'info, 'info>(
         presale_end_time: presale_params.presale_end_time,
         presale_maximum_cap: presale_params.presale_maximum_cap,
         presale_minimum_cap: presale_params.presale_minimum_cap,
+        finalization_callback_program,
     });
 
     Ok(())
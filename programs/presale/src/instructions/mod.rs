@@ -10,6 +10,9 @@ pub use process_close_extra_presale_params::*;
 mod create_escrow;
 pub use create_escrow::*;
 
+mod update_escrow_deposit_cap;
+pub use update_escrow_deposit_cap::*;
+
 mod process_create_merkle_root_config;
 pub use process_create_merkle_root_config::*;
 
@@ -40,6 +43,9 @@ pub use process_refresh_escrow::*;
 mod process_create_operator;
 pub use process_create_operator::*;
 
+mod process_update_operator;
+pub use process_update_operator::*;
+
 mod process_revoke_operator;
 pub use process_revoke_operator::*;
 
@@ -54,3 +60,57 @@ pub use process_creator_collect_fee::*;
 
 mod process_close_merkle_root_config;
 pub use process_close_merkle_root_config::*;
+
+mod process_revoke_merkle_root_config;
+pub use process_revoke_merkle_root_config::*;
+
+mod process_finalize_presale;
+pub use process_finalize_presale::*;
+
+mod process_deposit_native;
+pub use process_deposit_native::*;
+
+mod process_withdraw_native;
+pub use process_withdraw_native::*;
+
+mod process_withdraw_remaining_quote_native;
+pub use process_withdraw_remaining_quote_native::*;
+
+mod process_creator_withdraw_native;
+pub use process_creator_withdraw_native::*;
+
+mod process_create_quote_mint_config;
+pub use process_create_quote_mint_config::*;
+
+mod process_deposit_with_quote_mint;
+pub use process_deposit_with_quote_mint::*;
+
+mod process_withdraw_with_quote_mint;
+pub use process_withdraw_with_quote_mint::*;
+
+mod process_withdraw_remaining_quote_with_quote_mint;
+pub use process_withdraw_remaining_quote_with_quote_mint::*;
+
+mod process_creator_withdraw_quote_mint;
+pub use process_creator_withdraw_quote_mint::*;
+
+mod process_creator_collect_quote_mint_fee;
+pub use process_creator_collect_quote_mint_fee::*;
+
+mod process_create_referral;
+pub use process_create_referral::*;
+
+mod process_claim_referral_fee;
+pub use process_claim_referral_fee::*;
+
+mod process_create_token_gate_config;
+pub use process_create_token_gate_config::*;
+
+mod process_withdraw_gating_token;
+pub use process_withdraw_gating_token::*;
+
+mod process_add_to_denylist;
+pub use process_add_to_denylist::*;
+
+mod process_remove_from_denylist;
+pub use process_remove_from_denylist::*;
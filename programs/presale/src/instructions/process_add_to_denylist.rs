@@ -0,0 +1,77 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddToDenylistCtx<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::DENYLIST_PREFIX,
+            presale.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = 8 + DenylistEntry::INIT_SPACE
+    )]
+    pub denylist_entry: AccountLoader<'info, DenylistEntry>,
+
+    /// CHECK: Wallet to be excluded
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts are the escrow PDAs of the wallet in every permissionless registry, whether created or not
+pub fn handle_add_to_denylist(ctx: Context<AddToDenylistCtx>) -> Result<()> {
+    let mut presale = ctx.accounts.presale.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+
+    // 1. Ensure presale is still in deposit phase
+    require!(
+        presale_progress == PresaleProgress::NotStarted
+            || presale_progress == PresaleProgress::Ongoing,
+        PresaleError::PresaleEnded
+    );
+
+    // 2. Ensure presale has permissionless registry
+    require!(
+        presale.has_registry_with_whitelist_mode(|whitelist_mode| {
+            !whitelist_mode.is_permissioned()
+        })?,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    let mut denylist_entry = ctx.accounts.denylist_entry.load_init()?;
+    denylist_entry.initialize(ctx.accounts.presale.key(), ctx.accounts.wallet.key());
+
+    presale.enable_denylist();
+
+    // 3. Stop further deposit of the escrows created before the wallet is denylisted
+    set_escrows_denied(
+        &presale,
+        &ctx.accounts.presale.key(),
+        &ctx.accounts.wallet.key(),
+        ctx.remaining_accounts,
+        true,
+    )?;
+
+    emit_cpi!(EvtDenylistAdd {
+        presale: ctx.accounts.presale.key(),
+        denylist_entry: ctx.accounts.denylist_entry.key(),
+        wallet: ctx.accounts.wallet.key(),
+    });
+
+    Ok(())
+}
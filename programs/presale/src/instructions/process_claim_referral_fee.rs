@@ -0,0 +1,103 @@
+use anchor_spl::{
+    memo::Memo,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralFeeCtx<'info> {
+    #[account(
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = referrer,
+    )]
+    pub referral: AccountLoader<'info, Referral>,
+
+    /// CHECK: presale_authority
+    #[account(
+       address = presale_authority::ID
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub referrer_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub referrer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handle_claim_referral_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ClaimReferralFeeCtx<'info>>,
+    remaining_accounts_info: RemainingAccountsInfo,
+) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+    let mut referral = ctx.accounts.referral.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+
+    // 1. Ensure presale is completed. Deposit fee of failed presale is refunded to the buyers.
+    require!(
+        presale.get_presale_progress(current_timestamp) == PresaleProgress::Completed,
+        PresaleError::PresaleNotCompleted
+    );
+
+    // 2. Ensure referrer haven't claimed yet
+    require!(
+        !referral.is_fee_claimed(),
+        PresaleError::ReferralFeeAlreadyClaimed
+    );
+
+    referral.set_fee_claimed();
+
+    // 3. Claim. Round down in favor of the presale.
+    let referral_fee =
+        presale.get_referral_fee(&referral.registry_referred_deposit_fees, Rounding::Down)?;
+
+    if referral_fee > 0 {
+        let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
+            &mut &ctx.remaining_accounts[..],
+            &remaining_accounts_info.slices,
+            &[AccountsType::TransferHookQuote],
+        )?;
+
+        transfer_from_presale_to_user(
+            &ctx.accounts.presale_authority,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.quote_token_vault,
+            &ctx.accounts.referrer_quote_token,
+            &ctx.accounts.token_program,
+            referral_fee,
+            Some(MemoTransferContext {
+                memo_program: &ctx.accounts.memo_program,
+                memo: PRESALE_MEMO,
+            }),
+            transfer_hook_accounts.transfer_hook_quote,
+        )?;
+    }
+
+    let transfer_fee_excluded_referral_fee =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.quote_mint, referral_fee)?.amount;
+
+    emit_cpi!(EvtReferralFeeClaim {
+        presale: ctx.accounts.presale.key(),
+        referral: ctx.accounts.referral.key(),
+        referrer: ctx.accounts.referrer.key(),
+        fee: transfer_fee_excluded_referral_fee,
+    });
+
+    Ok(())
+}
@@ -28,6 +28,12 @@ pub fn handle_close_escrow(ctx: Context<CloseEscrowCtx>) -> Result<()> {
     let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
     let presale_progress = presale.get_presale_progress(current_timestamp);
 
+    // Locked gating token must be withdrawn first, since its vault is derived from the escrow
+    require!(
+        !escrow.has_locked_gating_token(),
+        PresaleError::EscrowNotEmpty
+    );
+
     match presale_progress {
         PresaleProgress::Ongoing => {
             ensure_escrow_no_deposit_and_potential_refundable(&escrow)?;
@@ -94,7 +100,11 @@ fn ensure_escrow_done_claim_and_withdraw_remaining_quote(
     // 2. Ensure the escrow has claimed all bought tokens
     let presale_handler = get_presale_mode_handler(&presale)?;
 
-    let vesting_end_time = presale.vesting_start_time.safe_add(presale.vest_duration)?;
+    // Escrow with vesting override might end vesting before the immediate release timestamp
+    let vesting_end_time = presale
+        .vesting_start_time
+        .safe_add(escrow.get_vest_duration(presale))?
+        .max(presale.immediate_release_timestamp);
 
     // Get total dripped bought token at vesting end time
     let escrow_total_claimable_amount: u64 =
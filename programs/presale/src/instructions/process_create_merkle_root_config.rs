@@ -52,10 +52,11 @@ pub fn handle_create_merkle_root_config(
         PresaleError::PresaleEnded
     );
 
-    // 2. Ensure presale is permissioned with merkle proof
-    let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
+    // 2. Ensure presale has registry permissioned with merkle proof
     require!(
-        whitelist_mode == WhitelistMode::PermissionWithMerkleProof,
+        presale.has_registry_with_whitelist_mode(|whitelist_mode| {
+            whitelist_mode == WhitelistMode::PermissionWithMerkleProof
+        })?,
         PresaleError::InvalidPresaleWhitelistMode
     );
 
@@ -1,14 +1,40 @@
 use crate::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct CreateOperatorParams {
+    /// Presale the operator is scoped to. Default pubkey allows every presale of the creator
+    pub presale: Pubkey,
+    /// Operator can't create escrow after this timestamp. 0 means no expiry
+    pub expiry_timestamp: u64,
+    /// Maximum number of escrows created by the operator. 0 means unlimited
+    pub max_escrow_count: u32,
+    /// Maximum total deposit cap of the escrows created by the operator. 0 means unlimited
+    pub max_total_deposit_cap: u64,
+    pub padding: [u8; 32],
+}
+
+impl CreateOperatorParams {
+    pub fn validate(&self, current_timestamp: u64) -> Result<()> {
+        require!(
+            self.expiry_timestamp == 0 || self.expiry_timestamp > current_timestamp,
+            PresaleError::InvalidOperatorParams
+        );
+
+        Ok(())
+    }
+}
+
 #[event_cpi]
 #[derive(Accounts)]
+#[instruction(params: CreateOperatorParams)]
 pub struct CreateOperatorCtx<'info> {
     #[account(
         init,
         seeds = [
             crate::constants::seeds::OPERATOR_PREFIX,
             creator.key().as_ref(),
-            operator_owner.key().as_ref()
+            operator_owner.key().as_ref(),
+            params.presale.as_ref()
         ],
         space = 8 + Operator::INIT_SPACE,
         payer = creator,
@@ -25,19 +51,29 @@ pub struct CreateOperatorCtx<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_create_operator(ctx: Context<CreateOperatorCtx>) -> Result<()> {
+pub fn handle_create_operator(
+    ctx: Context<CreateOperatorCtx>,
+    params: CreateOperatorParams,
+) -> Result<()> {
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    params.validate(current_timestamp)?;
+
     let operator = &mut ctx.accounts.operator.load_init()?;
 
-    // TODO: Should further scope down by including presale pubkey for higher security?
     operator.initialize(
         ctx.accounts.operator_owner.key(),
         ctx.accounts.creator.key(),
+        &params,
     );
 
     emit_cpi!(EvtOperatorCreate {
         creator: ctx.accounts.creator.key(),
         operator: ctx.accounts.operator.key(),
-        operator_owner: ctx.accounts.operator_owner.key()
+        operator_owner: ctx.accounts.operator_owner.key(),
+        presale: params.presale,
+        expiry_timestamp: params.expiry_timestamp,
+        max_escrow_count: params.max_escrow_count,
+        max_total_deposit_cap: params.max_total_deposit_cap,
     });
 
     Ok(())
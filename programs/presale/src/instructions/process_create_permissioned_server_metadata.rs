@@ -32,10 +32,9 @@ pub fn handle_create_permissioned_server_metadata(
 ) -> Result<()> {
     let presale = ctx.accounts.presale.load()?;
 
-    let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
     require!(
-        whitelist_mode == WhitelistMode::PermissionWithMerkleProof
-            || whitelist_mode == WhitelistMode::PermissionWithAuthority,
+        presale
+            .has_registry_with_whitelist_mode(|whitelist_mode| whitelist_mode.is_permissioned())?,
         PresaleError::InvalidPresaleWhitelistMode
     );
 
@@ -0,0 +1,117 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct CreateQuoteMintConfigParams {
+    /// accounting amount = quote mint amount * conversion_ratio_numerator / conversion_ratio_denominator
+    pub conversion_ratio_numerator: u64,
+    pub conversion_ratio_denominator: u64,
+}
+
+impl CreateQuoteMintConfigParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.conversion_ratio_numerator > 0 && self.conversion_ratio_denominator > 0,
+            PresaleError::InvalidQuoteMintConfig
+        );
+
+        Ok(())
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateQuoteMintConfigCtx<'info> {
+    #[account(
+        has_one = owner,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::QUOTE_MINT_CONFIG_PREFIX,
+            presale.key().as_ref(),
+            quote_mint.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = 8 + QuoteMintConfig::INIT_SPACE
+    )]
+    pub quote_mint_config: AccountLoader<'info, QuoteMintConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::QUOTE_VAULT_PREFIX,
+            quote_mint_config.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        token::mint = quote_mint,
+        token::authority = presale_authority,
+        token::token_program = quote_token_program
+    )]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: The presale authority is the PDA of the presale.
+    #[account(
+        address = crate::presale_authority::ID,
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_quote_mint_config(
+    ctx: Context<CreateQuoteMintConfigCtx>,
+    params: CreateQuoteMintConfigParams,
+) -> Result<()> {
+    params.validate()?;
+
+    let presale = ctx.accounts.presale.load()?;
+
+    // 1. Conversion ratio must be fixed before anyone deposit
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    require!(
+        presale.get_presale_progress(current_timestamp) == PresaleProgress::NotStarted,
+        PresaleError::PresaleAlreadyStarted
+    );
+
+    // 2. Ensure the quote mint is an alternative of the presale quote mint
+    let quote_mint = ctx.accounts.quote_mint.key();
+    require!(
+        quote_mint != presale.quote_mint && quote_mint != presale.base_mint,
+        PresaleError::InvalidQuoteMint
+    );
+
+    ensure_supported_token2022_extensions(&ctx.accounts.quote_mint)?;
+
+    let mut quote_mint_config = ctx.accounts.quote_mint_config.load_init()?;
+    quote_mint_config.initialize(
+        ctx.accounts.presale.key(),
+        quote_mint,
+        ctx.accounts.quote_token_vault.key(),
+        params.conversion_ratio_numerator,
+        params.conversion_ratio_denominator,
+        ctx.accounts.quote_token_program.key(),
+    );
+
+    emit_cpi!(EvtQuoteMintConfigCreate {
+        presale: ctx.accounts.presale.key(),
+        quote_mint_config: ctx.accounts.quote_mint_config.key(),
+        quote_mint,
+        quote_token_vault: ctx.accounts.quote_token_vault.key(),
+        conversion_ratio_numerator: params.conversion_ratio_numerator,
+        conversion_ratio_denominator: params.conversion_ratio_denominator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferralCtx<'info> {
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::REFERRAL_PREFIX,
+            presale.key().as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Referral::INIT_SPACE
+    )]
+    pub referral: AccountLoader<'info, Referral>,
+
+    /// CHECK: Referrer who receive the referral fee
+    pub referrer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_referral(ctx: Context<CreateReferralCtx>) -> Result<()> {
+    let mut referral = ctx.accounts.referral.load_init()?;
+    referral.initialize(ctx.accounts.presale.key(), ctx.accounts.referrer.key());
+
+    emit_cpi!(EvtReferralCreate {
+        presale: ctx.accounts.presale.key(),
+        referral: ctx.accounts.referral.key(),
+        referrer: ctx.accounts.referrer.key(),
+    });
+
+    Ok(())
+}
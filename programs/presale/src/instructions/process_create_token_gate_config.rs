@@ -0,0 +1,103 @@
+use anchor_spl::token_interface::Mint;
+
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct CreateTokenGateConfigParams {
+    pub registry_index: u8,
+    /// Minimum amount of the gating mint that escrow owner must hold
+    pub minimum_holding_amount: u64,
+    /// Deposit cap granted per held gating token, in Q64.64. 0 means the registry buyer maximum deposit cap is granted.
+    pub q_deposit_cap_per_holding: u128,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateTokenGateConfigParams)]
+pub struct CreateTokenGateConfigCtx<'info> {
+    #[account(
+        has_one = owner,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::TOKEN_GATE_CONFIG_PREFIX,
+            presale.key().as_ref(),
+            params.registry_index.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = 8 + TokenGateConfig::INIT_SPACE
+    )]
+    pub token_gate_config: AccountLoader<'info, TokenGateConfig>,
+
+    pub gating_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_token_gate_config(
+    ctx: Context<CreateTokenGateConfigCtx>,
+    params: CreateTokenGateConfigParams,
+) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+
+    let CreateTokenGateConfigParams {
+        registry_index,
+        minimum_holding_amount,
+        q_deposit_cap_per_holding,
+    } = params;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+
+    // 1. Ensure presale is still in deposit phase
+    require!(
+        presale_progress == PresaleProgress::NotStarted
+            || presale_progress == PresaleProgress::Ongoing,
+        PresaleError::PresaleEnded
+    );
+
+    // 2. Ensure presale registry is permissioned with token gate
+    let whitelist_mode = presale.get_registry_whitelist_mode(registry_index.into())?;
+    require!(
+        whitelist_mode == WhitelistMode::PermissionWithTokenGate,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    require!(
+        minimum_holding_amount > 0,
+        PresaleError::InvalidTokenGateConfig
+    );
+
+    // 3. Gating token is locked upon escrow creation, which doesn't carry transfer hook accounts
+    require!(
+        get_transfer_hook_program_id(&ctx.accounts.gating_mint)?.is_none(),
+        PresaleError::InvalidTokenGateConfig
+    );
+
+    let mut token_gate_config = ctx.accounts.token_gate_config.load_init()?;
+    token_gate_config.initialize(
+        ctx.accounts.presale.key(),
+        ctx.accounts.gating_mint.key(),
+        registry_index,
+        minimum_holding_amount,
+        q_deposit_cap_per_holding,
+    );
+
+    emit_cpi!(EvtTokenGateConfigCreate {
+        presale: ctx.accounts.presale.key(),
+        config: ctx.accounts.token_gate_config.key(),
+        gating_mint: ctx.accounts.gating_mint.key(),
+        registry_index,
+        minimum_holding_amount,
+        q_deposit_cap_per_holding,
+    });
+
+    Ok(())
+}
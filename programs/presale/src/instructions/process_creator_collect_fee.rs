@@ -40,16 +40,17 @@ pub fn handle_creator_collect_fee<'a, 'b, 'c: 'info, 'info>(
     remaining_accounts_info: RemainingAccountsInfo,
 ) -> Result<()> {
     let mut presale = ctx.accounts.presale.load_mut()?;
-    let current_timestamp = Clock::get()?.unix_timestamp;
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
 
     // 1. Validate is deposit fee allowed or not
     require!(
-        ensure_allow_collect_deposit_fee(&presale, current_timestamp.safe_cast()?),
+        ensure_allow_collect_deposit_fee(&presale, presale_progress),
         PresaleError::PresaleNotOpenForCollectFee
     );
 
     // 2. Collect fee
-    let total_deposit_fee = presale.get_total_collected_fee()?;
+    let total_deposit_fee = presale.get_creator_collectable_fee(presale_progress)?;
 
     // 3. Mark deposit fee as collected
     presale.set_deposit_fee_collected();
@@ -88,7 +89,8 @@ pub fn handle_creator_collect_fee<'a, 'b, 'c: 'info, 'info>(
     Ok(())
 }
 
-fn ensure_allow_collect_deposit_fee(presale: &Presale, current_timestamp: u64) -> bool {
-    let presale_progress = presale.get_presale_progress(current_timestamp);
-    presale_progress == PresaleProgress::Completed && !presale.is_deposit_fee_collected()
+/// Failed presale only allows collecting the withdraw penalty, deposit fee is refunded to the escrows
+fn ensure_allow_collect_deposit_fee(presale: &Presale, presale_progress: PresaleProgress) -> bool {
+    (presale_progress == PresaleProgress::Completed || presale_progress == PresaleProgress::Failed)
+        && !presale.is_deposit_fee_collected()
 }
@@ -0,0 +1,114 @@
+use anchor_spl::{
+    memo::Memo,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatorCollectQuoteMintFeeCtx<'info> {
+    #[account(
+        has_one = owner,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub quote_mint_config: AccountLoader<'info, QuoteMintConfig>,
+
+    /// CHECK: presale_authority
+    #[account(
+       address = presale_authority::ID
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub fee_receiving_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handle_creator_collect_quote_mint_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreatorCollectQuoteMintFeeCtx<'info>>,
+    remaining_accounts_info: RemainingAccountsInfo,
+) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+    let mut quote_mint_config = ctx.accounts.quote_mint_config.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+
+    // 1. Validate is deposit fee allowed or not. Failed presale only allows collecting the withdraw penalty.
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+    require!(
+        (presale_progress == PresaleProgress::Completed
+            || presale_progress == PresaleProgress::Failed)
+            && !quote_mint_config.is_deposit_fee_collected(),
+        PresaleError::PresaleNotOpenForCollectFee
+    );
+
+    // 2. Collect fee. Prorata fee refunds are rounded up, so the vault can always cover the escrow refunds
+    let collected_fee = if presale_progress == PresaleProgress::Completed {
+        let QuoteDepositShare { collected_fee, .. } = presale.get_quote_deposit_share(
+            &quote_mint_config.registry_total_deposits,
+            &quote_mint_config.registry_total_deposit_fees,
+            Rounding::Up,
+        )?;
+        collected_fee
+    } else {
+        0
+    };
+
+    let total_deposit_fee = quote_mint_config.to_quote_mint_amount(
+        collected_fee.safe_add(quote_mint_config.total_withdraw_penalty)?,
+        Rounding::Down,
+    )?;
+
+    // 3. Mark deposit fee as collected
+    quote_mint_config.set_deposit_fee_collected();
+
+    if total_deposit_fee > 0 {
+        let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
+            &mut &ctx.remaining_accounts[..],
+            &remaining_accounts_info.slices,
+            &[AccountsType::TransferHookQuote],
+        )?;
+
+        transfer_from_presale_to_user(
+            &ctx.accounts.presale_authority,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.quote_token_vault,
+            &ctx.accounts.fee_receiving_account,
+            &ctx.accounts.token_program,
+            total_deposit_fee,
+            Some(MemoTransferContext {
+                memo_program: &ctx.accounts.memo_program,
+                memo: PRESALE_MEMO,
+            }),
+            transfer_hook_accounts.transfer_hook_quote,
+        )?;
+    }
+
+    let transfer_fee_excluded_deposit_fee =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.quote_mint, total_deposit_fee)?.amount;
+
+    emit_cpi!(EvtCreatorCollectFee {
+        presale: ctx.accounts.presale.key(),
+        owner: ctx.accounts.owner.key(),
+        total_collected_fee: transfer_fee_excluded_deposit_fee,
+    });
+
+    Ok(())
+}
@@ -115,6 +115,12 @@ pub fn handle_creator_withdraw<'a, 'b, 'c: 'info, 'info>(
         PresaleError::PresaleNotOpenForWithdraw
     );
 
+    // Finalization callback must observe the raised funds before they leave the vaults
+    require!(
+        !presale.has_finalization_callback() || presale.is_finalized(),
+        PresaleError::PresaleNotFinalized
+    );
+
     // 2. Ensure creator haven't withdrawn yet
     require!(
         !presale.has_creator_withdrawn(),
@@ -137,8 +143,7 @@ pub fn handle_creator_withdraw<'a, 'b, 'c: 'info, 'info>(
             )?;
 
             (
-                // Prorata can have total_deposit > presale_maximum_cap
-                presale.total_deposit.min(presale.presale_maximum_cap),
+                presale.get_quote_token_vault_raised_quote()?,
                 quote_token_vault,
                 quote_mint,
                 [AccountsType::TransferHookQuote],
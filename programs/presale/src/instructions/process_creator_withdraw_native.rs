@@ -0,0 +1,100 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatorWithdrawNativeCtx<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: This is the presale authority
+    #[account(
+        address = crate::presale_authority::ID,
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    /// Temporary token account to unwrap the native mint token. Closed at the end of the instruction.
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::NATIVE_UNWRAP_PREFIX,
+            owner.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        token::mint = quote_mint,
+        token::authority = presale_authority,
+        token::token_program = token_program,
+    )]
+    pub native_unwrap_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Only for completed presale. Base token of failed presale is withdrawn through creator_withdraw.
+pub fn handle_creator_withdraw_native<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreatorWithdrawNativeCtx<'info>>,
+) -> Result<()> {
+    ensure_native_quote_mint(&ctx.accounts.quote_mint)?;
+
+    let mut presale = ctx.accounts.presale.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+
+    // 1. Ensure presale is completed
+    require!(
+        presale_progress == PresaleProgress::Completed,
+        PresaleError::PresaleNotCompleted
+    );
+
+    // Finalization callback must observe the raised funds before they leave the vaults
+    require!(
+        !presale.has_finalization_callback() || presale.is_finalized(),
+        PresaleError::PresaleNotFinalized
+    );
+
+    // 2. Ensure creator haven't withdrawn yet
+    require!(
+        !presale.has_creator_withdrawn(),
+        PresaleError::CreatorAlreadyWithdrawn
+    );
+
+    presale.update_creator_withdrawn()?;
+
+    // 3. Unwrap the raised quote token
+    let amount = presale.get_quote_token_vault_raised_quote()?;
+
+    unwrap_native_from_vault(
+        &ctx.accounts.presale_authority,
+        &ctx.accounts.quote_mint,
+        &ctx.accounts.quote_token_vault,
+        &ctx.accounts.native_unwrap_token,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit_cpi!(EvtCreatorWithdraw {
+        presale: ctx.accounts.presale.key(),
+        amount,
+        presale_progress: presale_progress.into(),
+        creator: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
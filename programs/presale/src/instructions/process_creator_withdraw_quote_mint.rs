@@ -0,0 +1,115 @@
+use anchor_spl::{
+    memo::Memo,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatorWithdrawQuoteMintCtx<'info> {
+    #[account(
+        has_one = owner,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub quote_mint_config: AccountLoader<'info, QuoteMintConfig>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: This is the presale authority
+    #[account(
+        address = crate::presale_authority::ID,
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handle_creator_withdraw_quote_mint<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreatorWithdrawQuoteMintCtx<'info>>,
+    remaining_accounts_info: RemainingAccountsInfo,
+) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+    let mut quote_mint_config = ctx.accounts.quote_mint_config.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+
+    // 1. Ensure presale is completed. Base token of failed presale is withdrawn through creator_withdraw.
+    require!(
+        presale_progress == PresaleProgress::Completed,
+        PresaleError::PresaleNotCompleted
+    );
+
+    // Finalization callback must observe the raised funds before they leave the vaults
+    require!(
+        !presale.has_finalization_callback() || presale.is_finalized(),
+        PresaleError::PresaleNotFinalized
+    );
+
+    // 2. Ensure creator haven't withdrawn yet
+    require!(
+        !quote_mint_config.has_creator_withdrawn(),
+        PresaleError::CreatorAlreadyWithdrawn
+    );
+
+    quote_mint_config.update_creator_withdrawn();
+
+    // 3. Prorata refunds are rounded up, so the vault can always cover the escrow refunds
+    let QuoteDepositShare { raised_quote, .. } = presale.get_quote_deposit_share(
+        &quote_mint_config.registry_total_deposits,
+        &quote_mint_config.registry_total_deposit_fees,
+        Rounding::Up,
+    )?;
+
+    let amount = quote_mint_config.to_quote_mint_amount(raised_quote, Rounding::Down)?;
+
+    let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
+        &mut &ctx.remaining_accounts[..],
+        &remaining_accounts_info.slices,
+        &[AccountsType::TransferHookQuote],
+    )?;
+
+    if amount > 0 {
+        transfer_from_presale_to_user(
+            &ctx.accounts.presale_authority,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.quote_token_vault,
+            &ctx.accounts.owner_quote_token,
+            &ctx.accounts.token_program,
+            amount,
+            Some(MemoTransferContext {
+                memo_program: &ctx.accounts.memo_program,
+                memo: PRESALE_MEMO,
+            }),
+            transfer_hook_accounts.transfer_hook_quote,
+        )?;
+    }
+
+    let exclude_fee_amount =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.quote_mint, amount)?.amount;
+
+    emit_cpi!(EvtCreatorWithdraw {
+        presale: ctx.accounts.presale.key(),
+        amount: exclude_fee_amount,
+        presale_progress: presale_progress.into(),
+        creator: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
@@ -22,6 +22,13 @@ pub struct DepositCtx<'info> {
     )]
     pub escrow: AccountLoader<'info, Escrow>,
 
+    /// Referral of the deposit. Optional.
+    #[account(
+        mut,
+        has_one = presale @ PresaleError::InvalidReferral,
+    )]
+    pub referral: Option<AccountLoader<'info, Referral>>,
+
     #[account(mut)]
     pub payer_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
     pub payer: Signer<'info>,
@@ -38,36 +45,18 @@ pub fn handle_deposit<'a, 'b, 'c: 'info, 'info>(
     let mut presale = ctx.accounts.presale.load_mut()?;
     let mut escrow = ctx.accounts.escrow.load_mut()?;
 
-    // 1. Ensure presale is open for deposit
     let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
-    let progress = presale.get_presale_progress(current_timestamp);
-    require!(
-        progress == PresaleProgress::Ongoing,
-        PresaleError::PresaleNotOpenForDeposit
-    );
-
-    // 2. Ensure deposit amount is within the cap
-    let presale_handler = get_presale_mode_handler(&presale)?;
-    let remaining_deposit_quota = presale_handler.get_remaining_deposit_quota(&presale, &escrow)?;
-    let max_capped_deposit_amount = remaining_deposit_quota.min(max_amount);
-
-    require!(max_capped_deposit_amount > 0, PresaleError::ZeroTokenAmount);
-
-    let suggested_deposit_amount =
-        presale_handler.suggest_deposit_amount(max_capped_deposit_amount)?;
-
-    require!(suggested_deposit_amount > 0, PresaleError::ZeroTokenAmount);
-
-    let DepositFeeIncludedCalculation {
-        fee,
+    let EscrowDepositResult {
+        deposit_amount,
+        deposit_fee,
         amount_included_fee: included_fee_deposit_amount,
-    } = presale.deposit(&mut escrow, suggested_deposit_amount)?;
-
-    let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
-    presale_registry.validate_escrow_deposit(&escrow)?;
-
-    // 3. Update presale and escrow state
-    presale_handler.end_presale_if_max_cap_reached(&mut presale, current_timestamp)?;
+    } = process_escrow_deposit(
+        &mut presale,
+        &mut escrow,
+        Pubkey::default(),
+        max_amount,
+        current_timestamp,
+    )?;
 
     // 4. Transfer
     let include_transfer_fee_deposit_amount = calculate_transfer_fee_included_amount(
@@ -76,8 +65,10 @@ pub fn handle_deposit<'a, 'b, 'c: 'info, 'info>(
     )?
     .amount;
 
+    let mut remaining_account_slice = ctx.remaining_accounts;
+
     let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
-        &mut &ctx.remaining_accounts[..],
+        &mut remaining_account_slice,
         &remaining_account_info.slices,
         &[AccountsType::TransferHookQuote],
     )?;
@@ -96,12 +87,118 @@ pub fn handle_deposit<'a, 'b, 'c: 'info, 'info>(
     emit_cpi!(EvtDeposit {
         presale: ctx.accounts.presale.key(),
         escrow: ctx.accounts.escrow.key(),
-        deposit_amount: suggested_deposit_amount,
+        deposit_amount,
         escrow_total_deposit_amount: escrow.total_deposit,
         presale_total_deposit_amount: presale.total_deposit,
         owner: ctx.accounts.payer.key(),
-        deposit_fee: fee,
+        deposit_fee,
     });
 
+    // 5. Track the referred deposit
+    if let Some(referral) = &ctx.accounts.referral {
+        let mut referral_state = referral.load_mut()?;
+        process_referral_deposit(
+            &mut presale,
+            &escrow,
+            &mut referral_state,
+            deposit_amount,
+            deposit_fee,
+        )?;
+
+        emit_cpi!(EvtReferralDeposit {
+            presale: ctx.accounts.presale.key(),
+            referral: referral.key(),
+            referrer: referral_state.referrer,
+            escrow: ctx.accounts.escrow.key(),
+            deposit_amount,
+            deposit_fee,
+            referral_total_referred_deposit: referral_state.total_referred_deposit,
+        });
+    }
+
+    Ok(())
+}
+
+/// Track the referred deposit and deposit fee. Shared by all deposit paths.
+/// Self referral by the escrow owner is not allowed, regardless of who pays the deposit.
+pub fn process_referral_deposit(
+    presale: &mut Presale,
+    escrow: &Escrow,
+    referral: &mut Referral,
+    deposit_amount: u64,
+    referred_deposit_fee: u64,
+) -> Result<()> {
+    require!(
+        referral.referrer != escrow.owner,
+        PresaleError::InvalidReferral
+    );
+
+    referral.deposit(escrow.registry_index, deposit_amount, referred_deposit_fee)?;
+
+    presale
+        .get_presale_registry_mut(escrow.registry_index.into())?
+        .deposit_referred_fee(referred_deposit_fee)?;
+
     Ok(())
 }
+
+pub struct EscrowDepositResult {
+    pub deposit_amount: u64,
+    pub deposit_fee: u64,
+    pub amount_included_fee: u64,
+}
+
+/// Validate the deposit and update presale and escrow state. Shared by all deposit paths.
+/// Quote mint config is the default pubkey for deposit with the presale quote mint. Amounts are in accounting unit.
+pub fn process_escrow_deposit(
+    presale: &mut Presale,
+    escrow: &mut Escrow,
+    quote_mint_config: Pubkey,
+    max_amount: u64,
+    current_timestamp: u64,
+) -> Result<EscrowDepositResult> {
+    // 1. Ensure presale is open for deposit
+    require!(!escrow.is_denied(), PresaleError::WalletDenylisted);
+    escrow.bind_quote_mint_config(quote_mint_config)?;
+
+    let progress = presale.get_presale_progress(current_timestamp);
+    require!(
+        progress == PresaleProgress::Ongoing,
+        PresaleError::PresaleNotOpenForDeposit
+    );
+
+    presale
+        .get_presale_registry(escrow.registry_index.into())?
+        .ensure_within_deposit_window(current_timestamp)?;
+
+    // 2. Ensure deposit amount is within the cap
+    let presale_handler = get_presale_mode_handler(presale)?;
+    let remaining_deposit_quota =
+        presale_handler.get_remaining_deposit_quota(presale, escrow, current_timestamp)?;
+    let max_capped_deposit_amount = remaining_deposit_quota.min(max_amount);
+
+    require!(max_capped_deposit_amount > 0, PresaleError::ZeroTokenAmount);
+
+    let suggested_deposit_amount =
+        presale_handler.suggest_deposit_amount(presale, escrow, max_capped_deposit_amount)?;
+
+    require!(suggested_deposit_amount > 0, PresaleError::ZeroTokenAmount);
+
+    let DepositFeeIncludedCalculation {
+        fee,
+        amount_included_fee,
+    } = presale.deposit(escrow, suggested_deposit_amount)?;
+
+    let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+    presale_registry.validate_escrow_deposit(escrow)?;
+
+    // 3. Update presale and escrow state
+    presale.extend_presale_end_time_if_within_anti_snipe_window(current_timestamp)?;
+    presale_handler.end_presale_if_max_cap_reached(presale, current_timestamp)?;
+
+    Ok(EscrowDepositResult {
+        deposit_amount: suggested_deposit_amount,
+        deposit_fee: fee,
+        amount_included_fee,
+    })
+}
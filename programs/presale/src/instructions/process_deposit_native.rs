@@ -0,0 +1,101 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositNativeCtx<'info> {
+    #[account(
+        mut,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = presale,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    /// Referral of the deposit. Optional.
+    #[account(
+        mut,
+        has_one = presale @ PresaleError::InvalidReferral,
+    )]
+    pub referral: Option<AccountLoader<'info, Referral>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Deposit lamports directly for presale with native mint as quote mint. The lamports are wrapped into the quote token vault.
+pub fn handle_deposit_native(ctx: Context<DepositNativeCtx>, max_amount: u64) -> Result<()> {
+    ensure_native_quote_mint(&ctx.accounts.quote_mint)?;
+
+    let mut presale = ctx.accounts.presale.load_mut()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let EscrowDepositResult {
+        deposit_amount,
+        deposit_fee,
+        amount_included_fee,
+    } = process_escrow_deposit(
+        &mut presale,
+        &mut escrow,
+        Pubkey::default(),
+        max_amount,
+        current_timestamp,
+    )?;
+
+    // Native mint doesn't have transfer fee
+    wrap_native_to_vault(
+        &ctx.accounts.payer,
+        &ctx.accounts.quote_token_vault,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        amount_included_fee,
+    )?;
+
+    emit_cpi!(EvtDeposit {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        deposit_amount,
+        escrow_total_deposit_amount: escrow.total_deposit,
+        presale_total_deposit_amount: presale.total_deposit,
+        owner: ctx.accounts.payer.key(),
+        deposit_fee,
+    });
+
+    // Track the referred deposit
+    if let Some(referral) = &ctx.accounts.referral {
+        let mut referral_state = referral.load_mut()?;
+        process_referral_deposit(
+            &mut presale,
+            &escrow,
+            &mut referral_state,
+            deposit_amount,
+            deposit_fee,
+        )?;
+
+        emit_cpi!(EvtReferralDeposit {
+            presale: ctx.accounts.presale.key(),
+            referral: referral.key(),
+            referrer: referral_state.referrer,
+            escrow: ctx.accounts.escrow.key(),
+            deposit_amount,
+            deposit_fee,
+            referral_total_referred_deposit: referral_state.total_referred_deposit,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositWithQuoteMintCtx<'info> {
+    #[account(mut)]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub quote_mint_config: AccountLoader<'info, QuoteMintConfig>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = presale,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    /// Referral of the deposit. Must be empty, as the deposit fee of alternative quote mints isn't referred.
+    pub referral: Option<AccountLoader<'info, Referral>>,
+
+    #[account(mut)]
+    pub payer_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Max amount is in accounting unit, and doesn't include the transfer fees and deposit fees.
+pub fn handle_deposit_with_quote_mint<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DepositWithQuoteMintCtx<'info>>,
+    max_amount: u64,
+    remaining_account_info: RemainingAccountsInfo,
+) -> Result<()> {
+    // Referral fee is paid from the deposit fee held by the presale quote token vault
+    require!(
+        ctx.accounts.referral.is_none(),
+        PresaleError::ReferralNotSupportedWithQuoteMint
+    );
+
+    let mut presale = ctx.accounts.presale.load_mut()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+    let mut quote_mint_config = ctx.accounts.quote_mint_config.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let EscrowDepositResult {
+        deposit_amount,
+        deposit_fee,
+        amount_included_fee,
+    } = process_escrow_deposit(
+        &mut presale,
+        &mut escrow,
+        ctx.accounts.quote_mint_config.key(),
+        max_amount,
+        current_timestamp,
+    )?;
+
+    // 1. Track the deposit of the alternative quote mint, in accounting unit
+    presale
+        .get_presale_registry_mut(escrow.registry_index.into())?
+        .deposit_alternative_quote(deposit_amount, deposit_fee)?;
+    quote_mint_config.deposit(escrow.registry_index, deposit_amount, deposit_fee)?;
+
+    // 2. Transfer. Round up in favor of the presale.
+    let quote_mint_deposit_amount =
+        quote_mint_config.to_quote_mint_amount(amount_included_fee, Rounding::Up)?;
+
+    let include_transfer_fee_deposit_amount = calculate_transfer_fee_included_amount(
+        &ctx.accounts.quote_mint,
+        quote_mint_deposit_amount,
+    )?
+    .amount;
+
+    let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
+        &mut &ctx.remaining_accounts[..],
+        &remaining_account_info.slices,
+        &[AccountsType::TransferHookQuote],
+    )?;
+
+    transfer_from_user(
+        &ctx.accounts.payer,
+        &ctx.accounts.quote_mint,
+        &ctx.accounts.payer_quote_token,
+        &ctx.accounts.quote_token_vault,
+        &ctx.accounts.token_program,
+        include_transfer_fee_deposit_amount,
+        None,
+        transfer_hook_accounts.transfer_hook_quote,
+    )?;
+
+    emit_cpi!(EvtDeposit {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        deposit_amount,
+        escrow_total_deposit_amount: escrow.total_deposit,
+        presale_total_deposit_amount: presale.total_deposit,
+        owner: ctx.accounts.payer.key(),
+        deposit_fee,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizePresaleCtx<'info> {
+    #[account(
+        mut,
+        has_one = base_token_vault,
+        has_one = quote_token_vault,
+        has_one = finalization_callback_program @ PresaleError::InvalidFinalizationCallbackProgram,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    /// CHECK: Signer of the callback. Owns nothing, so the callback program can't move any presale funds with it.
+    #[account(
+        seeds = [
+            crate::constants::seeds::FINALIZATION_CALLBACK_AUTHORITY_PREFIX,
+            presale.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub finalization_callback_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Presale base token vault
+    pub base_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Presale quote token vault
+    pub quote_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Finalization callback program
+    pub finalization_callback_program: UncheckedAccount<'info>,
+}
+
+pub fn handle_finalize_presale<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FinalizePresaleCtx<'info>>,
+) -> Result<()> {
+    let mut presale = ctx.accounts.presale.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+
+    // 1. Ensure presale is ended
+    require!(
+        presale_progress == PresaleProgress::Completed
+            || presale_progress == PresaleProgress::Failed,
+        PresaleError::PresaleNotEnded
+    );
+
+    // 2. Ensure presale have callback and not finalized yet
+    require!(
+        presale.has_finalization_callback(),
+        PresaleError::InvalidFinalizationCallbackProgram
+    );
+
+    require!(
+        !presale.is_finalized(),
+        PresaleError::PresaleAlreadyFinalized
+    );
+
+    presale.set_finalized();
+
+    let callback_args = PresaleFinalizedCallbackArgs::new(&presale, presale_progress);
+
+    // Release the presale borrow, it's passed to the callback program
+    drop(presale);
+
+    // 3. Notify the callback program
+    invoke_finalization_callback(
+        ctx.accounts,
+        ctx.bumps.finalization_callback_authority,
+        ctx.remaining_accounts,
+        callback_args,
+    )?;
+
+    emit_cpi!(EvtPresaleFinalize {
+        presale: ctx.accounts.presale.key(),
+        finalization_callback_program: ctx.accounts.finalization_callback_program.key(),
+        presale_progress: presale_progress.into(),
+    });
+
+    Ok(())
+}
@@ -29,7 +29,8 @@ pub struct InitializeFixedPricePresaleExtraArgs {
     pub presale: Pubkey,
     pub disable_withdraw: u8,
     pub q_price: u128,
-    pub padding1: [u64; 8],
+    /// Price of registry 1 onwards. Registry 0 is priced at q_price. All 0 means every registry is priced at q_price.
+    pub registry_q_prices: [u128; MAX_REGISTRY_Q_PRICE_COUNT],
 }
 
 impl InitializeFixedPricePresaleExtraArgs {
@@ -53,7 +54,7 @@ pub fn handle_initialize_fixed_price_presale_args(
         presale,
         q_price,
         disable_withdraw,
-        ..
+        registry_q_prices,
     } = params;
 
     let fixed_price_presale_params = &mut ctx.accounts.fixed_price_presale_params.load_init()?;
@@ -62,9 +63,14 @@ pub fn handle_initialize_fixed_price_presale_args(
         ctx.accounts.owner.key(),
         presale,
         disable_withdraw.safe_cast()?,
+        registry_q_prices,
     )?;
 
-    emit_cpi!(EvtFixedPricePresaleArgsCreate { presale, q_price });
+    emit_cpi!(EvtFixedPricePresaleArgsCreate {
+        presale,
+        q_price,
+        registry_q_prices,
+    });
 
     Ok(())
 }
@@ -0,0 +1,45 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveFromDenylistCtx<'info> {
+    #[account(
+        has_one = owner,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = wallet,
+        close = owner
+    )]
+    pub denylist_entry: AccountLoader<'info, DenylistEntry>,
+
+    /// CHECK: Wallet to be included back
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Remaining accounts are the escrow PDAs of the wallet in every permissionless registry, whether created or not
+pub fn handle_remove_from_denylist(ctx: Context<RemoveFromDenylistCtx>) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+
+    set_escrows_denied(
+        &presale,
+        &ctx.accounts.presale.key(),
+        &ctx.accounts.wallet.key(),
+        ctx.remaining_accounts,
+        false,
+    )?;
+
+    emit_cpi!(EvtDenylistRemove {
+        presale: ctx.accounts.presale.key(),
+        denylist_entry: ctx.accounts.denylist_entry.key(),
+        wallet: ctx.accounts.wallet.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeMerkleRootConfigCtx<'info> {
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+    )]
+    pub merkle_root_config: AccountLoader<'info, MerkleRootConfig>,
+
+    #[account(
+        constraint = presale.load()?.owner == creator.key() @ PresaleError::InvalidCreatorAccount
+    )]
+    pub creator: Signer<'info>,
+}
+
+pub fn handle_revoke_merkle_root_config(ctx: Context<RevokeMerkleRootConfigCtx>) -> Result<()> {
+    let mut merkle_root_config = ctx.accounts.merkle_root_config.load_mut()?;
+
+    // Unlike closing, revoking is allowed while presale is ongoing to disable a tree published by mistake
+    require!(
+        !merkle_root_config.is_revoked(),
+        PresaleError::MerkleRootConfigRevoked
+    );
+
+    merkle_root_config.revoke();
+
+    emit_cpi!(EvtRevokeMerkleRootConfig {
+        presale: ctx.accounts.presale.key(),
+        owner: ctx.accounts.creator.key(),
+        merkle_root_config: ctx.accounts.merkle_root_config.key(),
+        version: merkle_root_config.version,
+    });
+
+    Ok(())
+}
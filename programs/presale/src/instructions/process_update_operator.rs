@@ -0,0 +1,70 @@
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct UpdateOperatorParams {
+    /// Operator can't create escrow after this timestamp. 0 means no expiry
+    pub expiry_timestamp: u64,
+    /// Maximum number of escrows created by the operator. 0 means unlimited
+    pub max_escrow_count: u32,
+    /// Maximum total deposit cap of the escrows created by the operator. 0 means unlimited
+    pub max_total_deposit_cap: u64,
+    pub padding: [u8; 32],
+}
+
+impl UpdateOperatorParams {
+    pub fn validate(&self, operator: &Operator, current_timestamp: u64) -> Result<()> {
+        require!(
+            self.expiry_timestamp == 0 || self.expiry_timestamp > current_timestamp,
+            PresaleError::InvalidOperatorParams
+        );
+
+        // Quota can't be lowered below what the operator already granted
+        require!(
+            self.max_escrow_count == 0 || self.max_escrow_count >= operator.escrow_count,
+            PresaleError::InvalidOperatorParams
+        );
+
+        require!(
+            self.max_total_deposit_cap == 0
+                || self.max_total_deposit_cap >= operator.total_deposit_cap,
+            PresaleError::InvalidOperatorParams
+        );
+
+        Ok(())
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateOperatorCtx<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+    )]
+    pub operator: AccountLoader<'info, Operator>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handle_update_operator(
+    ctx: Context<UpdateOperatorCtx>,
+    params: UpdateOperatorParams,
+) -> Result<()> {
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+
+    let mut operator = ctx.accounts.operator.load_mut()?;
+    params.validate(&operator, current_timestamp)?;
+
+    operator.update(&params);
+
+    emit_cpi!(EvtOperatorUpdate {
+        creator: ctx.accounts.creator.key(),
+        operator: ctx.accounts.operator.key(),
+        operator_owner: operator.owner,
+        expiry_timestamp: params.expiry_timestamp,
+        max_escrow_count: params.max_escrow_count,
+        max_total_deposit_cap: params.max_total_deposit_cap,
+    });
+
+    Ok(())
+}
@@ -48,39 +48,18 @@ pub fn handle_withdraw<'a, 'b, 'c: 'info, 'info>(
     let mut presale = ctx.accounts.presale.load_mut()?;
     let mut escrow = ctx.accounts.escrow.load_mut()?;
 
-    // 1. Ensure presale is ongoing
     let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
-    let presale_progress = presale.get_presale_progress(current_timestamp);
-    require!(
-        presale_progress == PresaleProgress::Ongoing,
-        PresaleError::PresaleNotOpenForWithdraw
-    );
-
-    // 2. Ensure withdraw amount > 0
-    require!(max_amount > 0, PresaleError::ZeroTokenAmount);
-
-    // 3. Have enough balance to withdraw
-    require!(
-        escrow.total_deposit >= max_amount,
-        PresaleError::InsufficientEscrowBalance
-    );
-
-    // 4. Ensure presale mode allows withdraw
-    let presale_mode_handler = get_presale_mode_handler(&presale)?;
-    require!(
-        presale_mode_handler.can_withdraw(),
-        PresaleError::PresaleNotOpenForWithdraw
-    );
-
-    let suggested_withdraw_amount =
-        presale_mode_handler.suggest_withdraw_amount(&escrow, max_amount)?;
-    require!(suggested_withdraw_amount > 0, PresaleError::ZeroTokenAmount);
-
-    // 5. Update escrow and presale state
-    presale_mode_handler.process_withdraw(&mut presale, &mut escrow, suggested_withdraw_amount)?;
-
-    let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
-    presale_registry.validate_escrow_deposit(&escrow)?;
+    let EscrowWithdrawResult {
+        amount_excluded_penalty,
+        withdraw_penalty,
+        ..
+    } = process_escrow_withdraw(
+        &mut presale,
+        &mut escrow,
+        Pubkey::default(),
+        max_amount,
+        current_timestamp,
+    )?;
 
     let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
         &mut &ctx.remaining_accounts[..],
@@ -94,7 +73,7 @@ pub fn handle_withdraw<'a, 'b, 'c: 'info, 'info>(
         &ctx.accounts.quote_token_vault,
         &ctx.accounts.owner_quote_token,
         &ctx.accounts.token_program,
-        suggested_withdraw_amount,
+        amount_excluded_penalty,
         Some(MemoTransferContext {
             memo_program: &ctx.accounts.memo_program,
             memo: PRESALE_MEMO,
@@ -102,20 +81,97 @@ pub fn handle_withdraw<'a, 'b, 'c: 'info, 'info>(
         transfer_hook_accounts.transfer_hook_quote,
     )?;
 
-    let exclude_transfer_fee_amount_withdrawn = calculate_transfer_fee_excluded_amount(
-        &ctx.accounts.quote_mint,
-        suggested_withdraw_amount,
-    )?
-    .amount;
+    let exclude_transfer_fee_amount_withdrawn =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.quote_mint, amount_excluded_penalty)?
+            .amount;
 
     emit_cpi!(EvtWithdraw {
         presale: ctx.accounts.presale.key(),
         escrow: ctx.accounts.escrow.key(),
         owner: ctx.accounts.owner.key(),
         withdraw_amount: exclude_transfer_fee_amount_withdrawn,
+        withdraw_penalty,
         escrow_total_deposit_amount: escrow.total_deposit,
         presale_total_deposit_amount: presale.total_deposit,
     });
 
     Ok(())
 }
+
+pub struct EscrowWithdrawResult {
+    pub withdraw_amount: u64,
+    pub withdraw_penalty: u64,
+    pub amount_excluded_penalty: u64,
+}
+
+/// Validate the withdrawal and update presale and escrow state. Shared by all withdraw paths.
+/// Quote mint config is the default pubkey for the presale quote mint. Amounts are in accounting unit.
+pub fn process_escrow_withdraw(
+    presale: &mut Presale,
+    escrow: &mut Escrow,
+    quote_mint_config: Pubkey,
+    max_amount: u64,
+    current_timestamp: u64,
+) -> Result<EscrowWithdrawResult> {
+    // 1. Ensure presale is ongoing
+    escrow.ensure_quote_mint_config(quote_mint_config)?;
+
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+    require!(
+        presale_progress == PresaleProgress::Ongoing,
+        PresaleError::PresaleNotOpenForWithdraw
+    );
+
+    presale
+        .get_presale_registry(escrow.registry_index.into())?
+        .ensure_within_deposit_window(current_timestamp)?;
+
+    // 2. Ensure withdraw amount > 0
+    require!(max_amount > 0, PresaleError::ZeroTokenAmount);
+
+    // 3. Have enough balance to withdraw
+    require!(
+        escrow.total_deposit >= max_amount,
+        PresaleError::InsufficientEscrowBalance
+    );
+
+    // 4. Ensure presale mode allows withdraw
+    let presale_mode_handler = get_presale_mode_handler(presale)?;
+    require!(
+        presale_mode_handler.can_withdraw(),
+        PresaleError::PresaleNotOpenForWithdraw
+    );
+
+    // Reject last second withdrawals which could flip the presale from completed to failed
+    let withdraw_cutoff_time = presale
+        .presale_end_time
+        .saturating_sub(presale_mode_handler.get_withdraw_cutoff_duration());
+    require!(
+        current_timestamp < withdraw_cutoff_time,
+        PresaleError::WithdrawCutoffReached
+    );
+
+    let suggested_withdraw_amount =
+        presale_mode_handler.suggest_withdraw_amount(presale, escrow, max_amount)?;
+    require!(suggested_withdraw_amount > 0, PresaleError::ZeroTokenAmount);
+
+    // 5. Update escrow and presale state
+    presale_mode_handler.process_withdraw(presale, escrow, suggested_withdraw_amount)?;
+
+    let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+    presale_registry.validate_escrow_deposit(escrow)?;
+
+    // 6. Charge withdraw penalty
+    let withdraw_penalty = presale_mode_handler.calculate_withdraw_penalty(
+        presale,
+        suggested_withdraw_amount,
+        current_timestamp,
+    )?;
+    presale.charge_withdraw_penalty(escrow.registry_index, withdraw_penalty)?;
+
+    Ok(EscrowWithdrawResult {
+        withdraw_amount: suggested_withdraw_amount,
+        withdraw_penalty,
+        amount_excluded_penalty: suggested_withdraw_amount.safe_sub(withdraw_penalty)?,
+    })
+}
@@ -0,0 +1,104 @@
+use anchor_spl::{
+    memo::Memo,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawGatingTokenCtx<'info> {
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = owner,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [
+            crate::constants::seeds::GATING_TOKEN_VAULT_PREFIX,
+            escrow.key().as_ref(),
+        ],
+        bump,
+        token::mint = gating_mint,
+        token::token_program = token_program,
+    )]
+    pub gating_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub gating_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner_gating_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: presale_authority
+    #[account(
+        address = crate::presale_authority::ID
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handle_withdraw_gating_token(ctx: Context<WithdrawGatingTokenCtx>) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let presale_progress = presale.get_presale_progress(current_timestamp);
+
+    // 1. Ensure presale ended. The locked holding backs the deposit cap until then.
+    require!(
+        presale_progress == PresaleProgress::Completed
+            || presale_progress == PresaleProgress::Failed,
+        PresaleError::GatingTokenLocked
+    );
+
+    // 2. Return the whole vault, and close it to refund the rent
+    let amount = ctx.accounts.gating_token_vault.amount;
+    escrow.unlock_gating_token();
+
+    if amount > 0 {
+        transfer_from_presale_to_user(
+            &ctx.accounts.presale_authority,
+            &ctx.accounts.gating_mint,
+            &ctx.accounts.gating_token_vault,
+            &ctx.accounts.owner_gating_token,
+            &ctx.accounts.token_program,
+            amount,
+            Some(MemoTransferContext {
+                memo_program: &ctx.accounts.memo_program,
+                memo: PRESALE_MEMO,
+            }),
+            None,
+        )?;
+    }
+
+    let signer_seeds = &[&presale_authority_seeds!()[..]];
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.gating_token_vault.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.presale_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit_cpi!(EvtGatingTokenWithdraw {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+    });
+
+    Ok(())
+}
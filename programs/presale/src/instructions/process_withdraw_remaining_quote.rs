@@ -48,25 +48,13 @@ pub fn handle_withdraw_remaining_quote<'a, 'b, 'c: 'info, 'info>(
     let mut presale = ctx.accounts.presale.load_mut()?;
     let mut escrow = ctx.accounts.escrow.load_mut()?;
 
-    // 1. Ensure escrow haven't withdrawn remaining quote yet
-    require!(
-        !escrow.is_remaining_quote_withdrawn(),
-        PresaleError::RemainingQuoteAlreadyWithdrawn
-    );
-
     let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
-
-    // 2. Ensure the presale is in a state that allows withdrawing remaining quote
-    let EscrowRemainingQuoteResult {
-        refund_deposit_amount,
-        refund_fee_amount,
-    } = presale.validate_and_get_escrow_remaining_quote(&escrow, current_timestamp)?;
-
-    let total_refund_amount = refund_deposit_amount.safe_add(refund_fee_amount)?;
-
-    // 3. Update presale and escrow state
-    presale.update_total_refunded_quote_token(total_refund_amount, escrow.registry_index)?;
-    escrow.update_remaining_quote_withdrawn()?;
+    let total_refund_amount = process_escrow_withdraw_remaining_quote(
+        &mut presale,
+        &mut escrow,
+        Pubkey::default(),
+        current_timestamp,
+    )?;
 
     let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
         &mut &ctx.remaining_accounts[..],
@@ -104,3 +92,34 @@ pub fn handle_withdraw_remaining_quote<'a, 'b, 'c: 'info, 'info>(
 
     Ok(())
 }
+
+/// Validate the refund and update presale and escrow state. Shared by all refund paths.
+/// Quote mint config is the default pubkey for the presale quote mint. Amounts are in accounting unit.
+pub fn process_escrow_withdraw_remaining_quote(
+    presale: &mut Presale,
+    escrow: &mut Escrow,
+    quote_mint_config: Pubkey,
+    current_timestamp: u64,
+) -> Result<u64> {
+    // 1. Ensure escrow haven't withdrawn remaining quote yet
+    escrow.ensure_quote_mint_config(quote_mint_config)?;
+
+    require!(
+        !escrow.is_remaining_quote_withdrawn(),
+        PresaleError::RemainingQuoteAlreadyWithdrawn
+    );
+
+    // 2. Ensure the presale is in a state that allows withdrawing remaining quote
+    let EscrowRemainingQuoteResult {
+        refund_deposit_amount,
+        refund_fee_amount,
+    } = presale.validate_and_get_escrow_remaining_quote(escrow, current_timestamp)?;
+
+    let total_refund_amount = refund_deposit_amount.safe_add(refund_fee_amount)?;
+
+    // 3. Update presale and escrow state
+    presale.update_total_refunded_quote_token(total_refund_amount, escrow.registry_index)?;
+    escrow.update_remaining_quote_withdrawn()?;
+
+    Ok(total_refund_amount)
+}
@@ -0,0 +1,89 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawRemainingQuoteNativeCtx<'info> {
+    #[account(
+        mut,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: The presale authority is the PDA of the presale.
+    #[account(
+        address = crate::const_pda::presale_authority::ID,
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = owner
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    /// Temporary token account to unwrap the native mint token. Closed at the end of the instruction.
+    #[account(
+        init,
+        seeds = [
+            crate::constants::seeds::NATIVE_UNWRAP_PREFIX,
+            owner.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        token::mint = quote_mint,
+        token::authority = presale_authority,
+        token::token_program = token_program,
+    )]
+    pub native_unwrap_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_withdraw_remaining_quote_native(
+    ctx: Context<WithdrawRemainingQuoteNativeCtx>,
+) -> Result<()> {
+    ensure_native_quote_mint(&ctx.accounts.quote_mint)?;
+
+    let mut presale = ctx.accounts.presale.load_mut()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let total_refund_amount = process_escrow_withdraw_remaining_quote(
+        &mut presale,
+        &mut escrow,
+        Pubkey::default(),
+        current_timestamp,
+    )?;
+
+    unwrap_native_from_vault(
+        &ctx.accounts.presale_authority,
+        &ctx.accounts.quote_mint,
+        &ctx.accounts.quote_token_vault,
+        &ctx.accounts.native_unwrap_token,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.token_program,
+        total_refund_amount,
+    )?;
+
+    emit_cpi!(EvtWithdrawRemainingQuote {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        owner: ctx.accounts.owner.key(),
+        amount_refunded: total_refund_amount,
+        presale_total_refunded_quote_token: presale.total_refunded_quote_token,
+    });
+
+    Ok(())
+}
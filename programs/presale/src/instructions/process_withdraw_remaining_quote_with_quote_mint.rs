@@ -0,0 +1,99 @@
+use anchor_spl::{
+    memo::Memo,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawRemainingQuoteWithQuoteMintCtx<'info> {
+    #[account(mut)]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        has_one = presale,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub quote_mint_config: AccountLoader<'info, QuoteMintConfig>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: The presale authority is the PDA of the presale.
+    #[account(
+        address = crate::const_pda::presale_authority::ID,
+    )]
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = owner
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(mut)]
+    pub owner_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handle_withdraw_remaining_quote_with_quote_mint<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, WithdrawRemainingQuoteWithQuoteMintCtx<'info>>,
+    remaining_accounts_info: RemainingAccountsInfo,
+) -> Result<()> {
+    let mut presale = ctx.accounts.presale.load_mut()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+    let quote_mint_config = ctx.accounts.quote_mint_config.load()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let total_refund_amount = process_escrow_withdraw_remaining_quote(
+        &mut presale,
+        &mut escrow,
+        ctx.accounts.quote_mint_config.key(),
+        current_timestamp,
+    )?;
+
+    // Refund in the quote mint the escrow deposited with. Round down in favor of the presale.
+    let quote_mint_refund_amount =
+        quote_mint_config.to_quote_mint_amount(total_refund_amount, Rounding::Down)?;
+
+    let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
+        &mut &ctx.remaining_accounts[..],
+        &remaining_accounts_info.slices,
+        &[AccountsType::TransferHookQuote],
+    )?;
+
+    if quote_mint_refund_amount > 0 {
+        transfer_from_presale_to_user(
+            &ctx.accounts.presale_authority,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.quote_token_vault,
+            &ctx.accounts.owner_quote_token,
+            &ctx.accounts.token_program,
+            quote_mint_refund_amount,
+            Some(MemoTransferContext {
+                memo_program: &ctx.accounts.memo_program,
+                memo: PRESALE_MEMO,
+            }),
+            transfer_hook_accounts.transfer_hook_quote,
+        )?;
+    }
+
+    emit_cpi!(EvtWithdrawRemainingQuote {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        owner: ctx.accounts.owner.key(),
+        amount_refunded: total_refund_amount,
+        presale_total_refunded_quote_token: presale.total_refunded_quote_token,
+    });
+
+    Ok(())
+}
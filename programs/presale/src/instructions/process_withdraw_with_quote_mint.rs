@@ -0,0 +1,115 @@
+use anchor_spl::{
+    memo::Memo,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawWithQuoteMintCtx<'info> {
+    #[account(mut)]
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = quote_token_vault,
+        has_one = quote_mint,
+    )]
+    pub quote_mint_config: AccountLoader<'info, QuoteMintConfig>,
+
+    #[account(mut)]
+    pub quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = crate::const_pda::presale_authority::ID,
+    )]
+    /// CHECK: The presale authority is the PDA of the presale.
+    pub presale_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = owner
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(mut)]
+    pub owner_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+// Max amount is in accounting unit
+pub fn handle_withdraw_with_quote_mint<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, WithdrawWithQuoteMintCtx<'info>>,
+    max_amount: u64,
+    remaining_accounts_info: RemainingAccountsInfo,
+) -> Result<()> {
+    let mut presale = ctx.accounts.presale.load_mut()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+    let mut quote_mint_config = ctx.accounts.quote_mint_config.load_mut()?;
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let EscrowWithdrawResult {
+        withdraw_amount,
+        withdraw_penalty,
+        amount_excluded_penalty,
+    } = process_escrow_withdraw(
+        &mut presale,
+        &mut escrow,
+        ctx.accounts.quote_mint_config.key(),
+        max_amount,
+        current_timestamp,
+    )?;
+
+    // 1. Track the withdrawal of the alternative quote mint, in accounting unit. Withdraw penalty is retained by the alternative quote mint vault.
+    let presale_registry = presale.get_presale_registry_mut(escrow.registry_index.into())?;
+    presale_registry.withdraw_alternative_quote(withdraw_amount)?;
+    presale.charge_alternative_quote_withdraw_penalty(withdraw_penalty)?;
+
+    quote_mint_config.withdraw(escrow.registry_index, withdraw_amount)?;
+    quote_mint_config.charge_withdraw_penalty(withdraw_penalty)?;
+
+    // 2. Transfer. Round down in favor of the presale.
+    let quote_mint_withdraw_amount =
+        quote_mint_config.to_quote_mint_amount(amount_excluded_penalty, Rounding::Down)?;
+
+    let transfer_hook_accounts = parse_remaining_accounts_for_transfer_hook(
+        &mut &ctx.remaining_accounts[..],
+        &remaining_accounts_info.slices,
+        &[AccountsType::TransferHookQuote],
+    )?;
+
+    if quote_mint_withdraw_amount > 0 {
+        transfer_from_presale_to_user(
+            &ctx.accounts.presale_authority,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.quote_token_vault,
+            &ctx.accounts.owner_quote_token,
+            &ctx.accounts.token_program,
+            quote_mint_withdraw_amount,
+            Some(MemoTransferContext {
+                memo_program: &ctx.accounts.memo_program,
+                memo: PRESALE_MEMO,
+            }),
+            transfer_hook_accounts.transfer_hook_quote,
+        )?;
+    }
+
+    emit_cpi!(EvtWithdraw {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        owner: ctx.accounts.owner.key(),
+        withdraw_amount: amount_excluded_penalty,
+        withdraw_penalty,
+        escrow_total_deposit_amount: escrow.total_deposit,
+        presale_total_deposit_amount: presale.total_deposit,
+    });
+
+    Ok(())
+}
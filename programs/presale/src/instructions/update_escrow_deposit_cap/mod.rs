@@ -0,0 +1,7 @@
+mod process_update_escrow_deposit_cap;
+
+mod process_update_escrow_deposit_cap_with_creator;
+pub use process_update_escrow_deposit_cap_with_creator::*;
+
+mod process_update_escrow_deposit_cap_with_merkle_proof;
+pub use process_update_escrow_deposit_cap_with_merkle_proof::*;
@@ -0,0 +1,42 @@
+use crate::*;
+
+pub struct HandleUpdateEscrowDepositCapArgs<'a, 'b> {
+    pub presale: &'a Presale,
+    pub escrow: &'b mut Escrow,
+    pub deposit_cap: u64,
+    pub current_timestamp: u64,
+}
+
+/// Returns the previous deposit cap of the escrow
+pub fn process_update_escrow_deposit_cap(args: HandleUpdateEscrowDepositCapArgs) -> Result<u64> {
+    let HandleUpdateEscrowDepositCapArgs {
+        presale,
+        escrow,
+        deposit_cap,
+        current_timestamp,
+    } = args;
+
+    // 1. Ensure presale is open for deposit
+    let progress = presale.get_presale_progress(current_timestamp);
+    require!(
+        progress == PresaleProgress::Ongoing,
+        PresaleError::PresaleNotOpenForDeposit
+    );
+
+    let registry = presale.get_presale_registry(escrow.registry_index.into())?;
+    registry.ensure_within_deposit_window(current_timestamp)?;
+
+    // 2. Within the registry deposit cap
+    require!(
+        deposit_cap > 0
+            && deposit_cap >= registry.buyer_minimum_deposit_cap
+            && deposit_cap <= registry.buyer_maximum_deposit_cap,
+        PresaleError::InvalidDepositCap
+    );
+
+    // 3. Update the escrow deposit cap, which can't be lowered below the deposited amount
+    let previous_deposit_cap = escrow.deposit_max_cap;
+    escrow.update_deposit_max_cap(deposit_cap)?;
+
+    Ok(previous_deposit_cap)
+}
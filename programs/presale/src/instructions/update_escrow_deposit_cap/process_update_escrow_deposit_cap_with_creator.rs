@@ -0,0 +1,80 @@
+use crate::{
+    instructions::update_escrow_deposit_cap::process_update_escrow_deposit_cap::{
+        process_update_escrow_deposit_cap, HandleUpdateEscrowDepositCapArgs,
+    },
+    *,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct UpdateEscrowDepositCapWithCreatorParams {
+    pub deposit_cap: u64,
+    pub padding: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateEscrowDepositCapWithCreatorCtx<'info> {
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(mut)]
+    pub operator: AccountLoader<'info, Operator>,
+
+    pub operator_owner: Signer<'info>,
+}
+
+pub fn handle_update_escrow_deposit_cap_with_creator(
+    ctx: Context<UpdateEscrowDepositCapWithCreatorCtx>,
+    params: UpdateEscrowDepositCapWithCreatorParams,
+) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+
+    let UpdateEscrowDepositCapWithCreatorParams { deposit_cap, .. } = params;
+
+    // 1. Ensure presale registry is permissioned with authority
+    let whitelist_mode = presale.get_registry_whitelist_mode(escrow.registry_index.into())?;
+    require!(
+        whitelist_mode == WhitelistMode::PermissionWithAuthority,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    // 2. Ensure rightful operator is provided. Only the raised deposit cap counts toward the operator quota
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let mut operator = ctx.accounts.operator.load_mut()?;
+    ensure_operator_belongs_to_presale_creator(
+        &ctx.accounts.presale.key(),
+        &presale,
+        &mut operator,
+        &ctx.accounts.operator_owner.key(),
+        OperatorGrant {
+            escrow_count: 0,
+            deposit_cap: deposit_cap.saturating_sub(escrow.deposit_max_cap),
+        },
+        current_timestamp,
+    )?;
+
+    let previous_deposit_cap =
+        process_update_escrow_deposit_cap(HandleUpdateEscrowDepositCapArgs {
+            presale: &presale,
+            escrow: &mut escrow,
+            deposit_cap,
+            current_timestamp,
+        })?;
+
+    emit_cpi!(EvtEscrowDepositCapUpdate {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        owner: escrow.owner,
+        whitelist_mode: whitelist_mode.into(),
+        previous_deposit_cap,
+        deposit_cap,
+    });
+
+    Ok(())
+}
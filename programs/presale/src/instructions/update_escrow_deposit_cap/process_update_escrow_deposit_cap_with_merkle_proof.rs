@@ -0,0 +1,106 @@
+use crate::{
+    instructions::update_escrow_deposit_cap::process_update_escrow_deposit_cap::{
+        process_update_escrow_deposit_cap, HandleUpdateEscrowDepositCapArgs,
+    },
+    *,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct UpdateEscrowDepositCapWithMerkleProofParams {
+    pub proof: Vec<[u8; 32]>,
+    pub deposit_cap: u64,
+    /// Personal terms of the owner. Must match the merkle leaf and the terms of the escrow.
+    pub terms_override: Option<EscrowTermsOverride>,
+    pub padding: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateEscrowDepositCapWithMerkleProofCtx<'info> {
+    pub presale: AccountLoader<'info, Presale>,
+
+    #[account(
+        mut,
+        has_one = presale,
+        has_one = owner,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    /// Owner signs so that a proof of another tree version can't be used to change the deposit cap against their will.
+    /// Lowering the deposit cap through a new tree version therefore needs the owner signature.
+    pub owner: Signer<'info>,
+
+    #[account(has_one = presale)]
+    pub merkle_root_config: AccountLoader<'info, MerkleRootConfig>,
+}
+
+pub fn handle_update_escrow_deposit_cap_with_merkle_proof(
+    ctx: Context<UpdateEscrowDepositCapWithMerkleProofCtx>,
+    params: UpdateEscrowDepositCapWithMerkleProofParams,
+) -> Result<()> {
+    let presale = ctx.accounts.presale.load()?;
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+
+    let UpdateEscrowDepositCapWithMerkleProofParams {
+        proof,
+        deposit_cap,
+        terms_override,
+        ..
+    } = params;
+
+    // 1. Ensure presale registry is permissioned with merkle proof
+    let whitelist_mode = presale.get_registry_whitelist_mode(escrow.registry_index.into())?;
+    require!(
+        whitelist_mode == WhitelistMode::PermissionWithMerkleProof,
+        PresaleError::InvalidPresaleWhitelistMode
+    );
+
+    // 2. Terms override can't be changed after the escrow is created
+    require!(
+        escrow.matches_terms_override(terms_override.as_ref()),
+        PresaleError::InvalidEscrowTermsOverride
+    );
+
+    // 3. Verify the merkle proof against a non revoked config, newer than the one which last set the deposit cap
+    let merkle_root_config = ctx.accounts.merkle_root_config.load()?;
+    require!(
+        !merkle_root_config.is_revoked(),
+        PresaleError::MerkleRootConfigRevoked
+    );
+    require!(
+        merkle_root_config.version > escrow.merkle_root_config_version,
+        PresaleError::MerkleRootConfigOutdated
+    );
+
+    let node = get_merkle_leaf(
+        &escrow.owner,
+        escrow.registry_index,
+        deposit_cap,
+        terms_override.as_ref(),
+    );
+    require!(
+        verify(proof, merkle_root_config.root, node),
+        PresaleError::InvalidMerkleProof
+    );
+
+    let current_timestamp: u64 = Clock::get()?.unix_timestamp.safe_cast()?;
+    let previous_deposit_cap =
+        process_update_escrow_deposit_cap(HandleUpdateEscrowDepositCapArgs {
+            presale: &presale,
+            escrow: &mut escrow,
+            deposit_cap,
+            current_timestamp,
+        })?;
+    escrow.set_merkle_root_config_version(merkle_root_config.version);
+
+    emit_cpi!(EvtEscrowDepositCapUpdate {
+        presale: ctx.accounts.presale.key(),
+        escrow: ctx.accounts.escrow.key(),
+        owner: escrow.owner,
+        whitelist_mode: whitelist_mode.into(),
+        previous_deposit_cap,
+        deposit_cap,
+    });
+
+    Ok(())
+}
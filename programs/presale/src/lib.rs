@@ -34,6 +34,12 @@ pub use token2022::*;
 mod presale_mode_handler;
 pub use presale_mode_handler::*;
 
+mod finalization_callback;
+pub use finalization_callback::*;
+
+mod native_sol;
+pub use native_sol::*;
+
 declare_id!("presSVxnf9UU8jMxhgSMqaRwNiT36qeBdNeTRKjTdbj");
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -84,8 +90,15 @@ pub mod presale {
         instructions::handle_close_merkle_root_config(ctx)
     }
 
-    pub fn create_permissionless_escrow(ctx: Context<CreatePermissionlessEscrowCtx>) -> Result<()> {
-        instructions::handle_create_permissionless_escrow(ctx)
+    pub fn revoke_merkle_root_config(ctx: Context<RevokeMerkleRootConfigCtx>) -> Result<()> {
+        instructions::handle_revoke_merkle_root_config(ctx)
+    }
+
+    pub fn create_permissionless_escrow(
+        ctx: Context<CreatePermissionlessEscrowCtx>,
+        params: CreatePermissionlessEscrowParams,
+    ) -> Result<()> {
+        instructions::handle_create_permissionless_escrow(ctx, params)
     }
 
     pub fn create_permissioned_escrow_with_creator(
@@ -102,8 +115,46 @@ pub mod presale {
         instructions::handle_create_permissioned_escrow_with_merkle_proof(ctx, params)
     }
 
-    pub fn create_operator(ctx: Context<CreateOperatorCtx>) -> Result<()> {
-        instructions::handle_create_operator(ctx)
+    pub fn create_permissioned_escrow_with_signature(
+        ctx: Context<CreatePermissionedEscrowWithSignatureCtx>,
+        params: CreatePermissionedEscrowWithSignatureParams,
+    ) -> Result<()> {
+        instructions::handle_create_permissioned_escrow_with_signature(ctx, params)
+    }
+
+    pub fn create_permissioned_escrow_with_token_gate(
+        ctx: Context<CreatePermissionedEscrowWithTokenGateCtx>,
+        params: CreatePermissionedEscrowWithTokenGateParams,
+    ) -> Result<()> {
+        instructions::handle_create_permissioned_escrow_with_token_gate(ctx, params)
+    }
+
+    pub fn update_escrow_deposit_cap_with_creator(
+        ctx: Context<UpdateEscrowDepositCapWithCreatorCtx>,
+        params: UpdateEscrowDepositCapWithCreatorParams,
+    ) -> Result<()> {
+        instructions::handle_update_escrow_deposit_cap_with_creator(ctx, params)
+    }
+
+    pub fn update_escrow_deposit_cap_with_merkle_proof(
+        ctx: Context<UpdateEscrowDepositCapWithMerkleProofCtx>,
+        params: UpdateEscrowDepositCapWithMerkleProofParams,
+    ) -> Result<()> {
+        instructions::handle_update_escrow_deposit_cap_with_merkle_proof(ctx, params)
+    }
+
+    pub fn create_operator(
+        ctx: Context<CreateOperatorCtx>,
+        params: CreateOperatorParams,
+    ) -> Result<()> {
+        instructions::handle_create_operator(ctx, params)
+    }
+
+    pub fn update_operator(
+        ctx: Context<UpdateOperatorCtx>,
+        params: UpdateOperatorParams,
+    ) -> Result<()> {
+        instructions::handle_update_operator(ctx, params)
     }
 
     pub fn revoke_operator(_ctx: Context<RevokeOperatorCtx>) -> Result<()> {
@@ -181,4 +232,104 @@ pub mod presale {
     ) -> Result<()> {
         instructions::handle_creator_collect_fee(ctx, remaining_accounts_info)
     }
+
+    pub fn finalize_presale<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FinalizePresaleCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_finalize_presale(ctx)
+    }
+
+    pub fn deposit_native(ctx: Context<DepositNativeCtx>, max_amount: u64) -> Result<()> {
+        instructions::handle_deposit_native(ctx, max_amount)
+    }
+
+    pub fn withdraw_native(ctx: Context<WithdrawNativeCtx>, amount: u64) -> Result<()> {
+        instructions::handle_withdraw_native(ctx, amount)
+    }
+
+    pub fn withdraw_remaining_quote_native(
+        ctx: Context<WithdrawRemainingQuoteNativeCtx>,
+    ) -> Result<()> {
+        instructions::handle_withdraw_remaining_quote_native(ctx)
+    }
+
+    pub fn creator_withdraw_native<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreatorWithdrawNativeCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_creator_withdraw_native(ctx)
+    }
+
+    pub fn create_quote_mint_config(
+        ctx: Context<CreateQuoteMintConfigCtx>,
+        params: CreateQuoteMintConfigParams,
+    ) -> Result<()> {
+        instructions::handle_create_quote_mint_config(ctx, params)
+    }
+
+    pub fn deposit_with_quote_mint<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DepositWithQuoteMintCtx<'info>>,
+        max_amount: u64,
+        remaining_account_info: RemainingAccountsInfo,
+    ) -> Result<()> {
+        instructions::handle_deposit_with_quote_mint(ctx, max_amount, remaining_account_info)
+    }
+
+    pub fn withdraw_with_quote_mint<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, WithdrawWithQuoteMintCtx<'info>>,
+        amount: u64,
+        remaining_account_info: RemainingAccountsInfo,
+    ) -> Result<()> {
+        instructions::handle_withdraw_with_quote_mint(ctx, amount, remaining_account_info)
+    }
+
+    pub fn withdraw_remaining_quote_with_quote_mint<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, WithdrawRemainingQuoteWithQuoteMintCtx<'info>>,
+        remaining_accounts_info: RemainingAccountsInfo,
+    ) -> Result<()> {
+        instructions::handle_withdraw_remaining_quote_with_quote_mint(ctx, remaining_accounts_info)
+    }
+
+    pub fn creator_withdraw_quote_mint<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreatorWithdrawQuoteMintCtx<'info>>,
+        remaining_accounts_info: RemainingAccountsInfo,
+    ) -> Result<()> {
+        instructions::handle_creator_withdraw_quote_mint(ctx, remaining_accounts_info)
+    }
+
+    pub fn creator_collect_quote_mint_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreatorCollectQuoteMintFeeCtx<'info>>,
+        remaining_accounts_info: RemainingAccountsInfo,
+    ) -> Result<()> {
+        instructions::handle_creator_collect_quote_mint_fee(ctx, remaining_accounts_info)
+    }
+
+    pub fn create_referral(ctx: Context<CreateReferralCtx>) -> Result<()> {
+        instructions::handle_create_referral(ctx)
+    }
+
+    pub fn claim_referral_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClaimReferralFeeCtx<'info>>,
+        remaining_accounts_info: RemainingAccountsInfo,
+    ) -> Result<()> {
+        instructions::handle_claim_referral_fee(ctx, remaining_accounts_info)
+    }
+
+    pub fn create_token_gate_config(
+        ctx: Context<CreateTokenGateConfigCtx>,
+        params: CreateTokenGateConfigParams,
+    ) -> Result<()> {
+        instructions::handle_create_token_gate_config(ctx, params)
+    }
+
+    pub fn withdraw_gating_token(ctx: Context<WithdrawGatingTokenCtx>) -> Result<()> {
+        instructions::handle_withdraw_gating_token(ctx)
+    }
+
+    pub fn add_to_denylist(ctx: Context<AddToDenylistCtx>) -> Result<()> {
+        instructions::handle_add_to_denylist(ctx)
+    }
+
+    pub fn remove_from_denylist(ctx: Context<RemoveFromDenylistCtx>) -> Result<()> {
+        instructions::handle_remove_from_denylist(ctx)
+    }
 }
@@ -6,6 +6,20 @@ pub struct TokenReleaseResult {
     pub vested_amount: u64,
 }
 
+/// Extra quote token a deposit is worth when bought at a discounted token price. Round down in favor of the presale.
+pub fn calculate_price_discount_bonus(deposit_amount: u64, price_discount_bps: u16) -> Result<u64> {
+    if price_discount_bps == 0 {
+        return Ok(0);
+    }
+
+    mul_div(
+        deposit_amount,
+        price_discount_bps.into(),
+        MAX_FEE_BASIS_POINTS.safe_sub(price_discount_bps)?.into(),
+        Rounding::Down,
+    )
+}
+
 pub fn calculate_immediate_release_token(
     total_sold_token: u64,
     immediate_release_bps: u16,
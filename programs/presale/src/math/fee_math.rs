@@ -1,6 +1,7 @@
 use crate::*;
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
+#[derive(Clone, Copy)]
 pub enum Rounding {
     Up,
     Down,
@@ -34,6 +35,20 @@ pub fn calculate_deposit_fee_included_amount(
     })
 }
 
+pub fn mul_div(x: u64, y: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    let prod = u128::from(x).safe_mul(y.into())?;
+    let denominator = u128::from(denominator);
+
+    let result = match rounding {
+        Rounding::Up => prod
+            .safe_add(denominator.safe_sub(1)?)?
+            .safe_div(denominator)?,
+        Rounding::Down => prod.safe_div(denominator)?,
+    };
+
+    Ok(result.safe_cast()?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +84,11 @@ mod tests {
 
         assert_eq!(u128::from(fee), computed_fee);
     }
+
+    #[test]
+    fn test_mul_div_rounding() {
+        assert_eq!(mul_div(10, 1, 3, Rounding::Down).unwrap(), 3);
+        assert_eq!(mul_div(10, 1, 3, Rounding::Up).unwrap(), 4);
+        assert_eq!(mul_div(9, 1, 3, Rounding::Up).unwrap(), 3);
+    }
 }
@@ -0,0 +1,83 @@
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::{
+    token::spl_token,
+    token_interface::{self, CloseAccount, Mint, SyncNative, TokenAccount, TokenInterface},
+};
+
+use crate::*;
+
+pub fn ensure_native_quote_mint(quote_mint: &InterfaceAccount<Mint>) -> Result<()> {
+    require!(
+        quote_mint.key() == spl_token::native_mint::ID,
+        PresaleError::QuoteMintNotNative
+    );
+    Ok(())
+}
+
+/// Transfer lamports from the payer into the native mint token vault, and sync the vault token balance
+pub fn wrap_native_to_vault<'info>(
+    payer: &Signer<'info>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: token_vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    token_interface::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        SyncNative {
+            account: token_vault.to_account_info(),
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// Transfer native mint token from the vault into a temporary token account owned by the presale authority,
+/// then close it so the receiver get the lamports. The rent of the temporary token account is returned to the receiver as well.
+pub fn unwrap_native_from_vault<'info>(
+    presale_authority: &UncheckedAccount<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    native_unwrap_token: &InterfaceAccount<'info, TokenAccount>,
+    receiver: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if amount > 0 {
+        transfer_from_presale_to_user(
+            presale_authority,
+            token_mint,
+            token_vault,
+            native_unwrap_token,
+            token_program,
+            amount,
+            None,
+            None,
+        )?;
+    }
+
+    let signer_seeds = &[&presale_authority_seeds!()[..]];
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: native_unwrap_token.to_account_info(),
+            destination: receiver.clone(),
+            authority: presale_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(())
+}
@@ -38,10 +38,7 @@ impl PresaleModeHandler for FcfsPresaleHandler {
         presale_params: &PresaleArgs,
         _remaining_accounts: &'e mut &'c [AccountInfo<'info>],
     ) -> Result<()> {
-        let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
-        if whitelist_mode.is_permissioned() {
-            enforce_dynamic_price_registries_max_buyer_cap_range(&presale)?;
-        }
+        enforce_dynamic_price_registries_max_buyer_cap_range(&presale)?;
 
         FcfsPresaleHandler::initialize_data(
             &mut presale.presale_mode_raw_data,
@@ -52,13 +49,20 @@ impl PresaleModeHandler for FcfsPresaleHandler {
     }
 
     /// FCFS presale cannot deposit more than the presale maximum cap.
-    fn get_remaining_deposit_quota(&self, presale: &Presale, escrow: &Escrow) -> Result<u64> {
+    fn get_remaining_deposit_quota(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        _current_timestamp: u64,
+    ) -> Result<u64> {
         let global_remaining_quota = presale.get_remaining_deposit_quota()?;
         let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
         let personal_remaining_quota =
             escrow.get_remaining_deposit_quota(presale_registry.buyer_maximum_deposit_cap)?;
 
-        Ok(global_remaining_quota.min(personal_remaining_quota))
+        Ok(global_remaining_quota
+            .min(personal_remaining_quota)
+            .min(presale_registry.get_remaining_deposit_quota()))
     }
 
     fn end_presale_if_max_cap_reached(
@@ -78,6 +82,19 @@ impl PresaleModeHandler for FcfsPresaleHandler {
         false
     }
 
+    fn get_withdraw_cutoff_duration(&self) -> u64 {
+        0
+    }
+
+    fn calculate_withdraw_penalty(
+        &self,
+        _presale: &Presale,
+        _amount: u64,
+        _current_timestamp: u64,
+    ) -> Result<u64> {
+        Ok(0)
+    }
+
     fn process_withdraw(
         &self,
         _presale: &mut Presale,
@@ -103,12 +120,17 @@ impl PresaleModeHandler for FcfsPresaleHandler {
         current_timestamp: u64,
     ) -> Result<u64> {
         let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+        let presale_registry_supply = get_dynamic_price_registry_effective_supply(
+            presale,
+            escrow.registry_index.into(),
+            current_timestamp,
+        )?;
         calculate_cumulative_claimable_amount_for_user(
-            presale.immediate_release_bps,
+            escrow.get_immediate_release_bps(presale),
             presale.immediate_release_timestamp,
-            presale_registry.presale_supply,
+            presale_registry_supply,
             presale.vesting_start_time,
-            presale.vest_duration,
+            escrow.get_vest_duration(presale),
             current_timestamp,
             escrow.total_deposit,
             presale_registry.total_deposit,
@@ -119,11 +141,21 @@ impl PresaleModeHandler for FcfsPresaleHandler {
         get_dynamic_price_based_total_base_token_sold(presale)
     }
 
-    fn suggest_deposit_amount(&self, max_deposit_amount: u64) -> Result<u64> {
+    fn suggest_deposit_amount(
+        &self,
+        _presale: &Presale,
+        _escrow: &Escrow,
+        max_deposit_amount: u64,
+    ) -> Result<u64> {
         Ok(max_deposit_amount)
     }
 
-    fn suggest_withdraw_amount(&self, _escrow: &Escrow, _max_withdraw_amount: u64) -> Result<u64> {
+    fn suggest_withdraw_amount(
+        &self,
+        _presale: &Presale,
+        _escrow: &Escrow,
+        _max_withdraw_amount: u64,
+    ) -> Result<u64> {
         Ok(0)
     }
 }
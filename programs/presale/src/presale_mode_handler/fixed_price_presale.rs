@@ -1,5 +1,6 @@
 use crate::PresaleModeHandler;
 use crate::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
 // Calculate min quote amount needed to purchase at least 1 base lamport. If price < 1 quote token, min quote amount will be > 1
 fn calculate_min_quote_amount_for_base_lamport(q_price: u128) -> Result<u64> {
@@ -53,6 +54,13 @@ fn ensure_gap_between_min_and_max_presale_cap(
     Ok(())
 }
 
+// Quote token raised when the whole presale supply is sold. Saturate since it only used for comparison with the presale maximum cap.
+fn calculate_max_quote_raisable(q_price: u128, presale_supply: u64) -> Result<u128> {
+    Ok(u128::from(presale_supply)
+        .saturating_mul(q_price)
+        .safe_shr(SCALE_OFFSET)?)
+}
+
 fn calculate_quote_token_without_surplus(q_price: u128, amount: u64) -> Result<u64> {
     let base_token_amount = calculate_token_bought(q_price, amount)?;
 
@@ -77,7 +85,8 @@ pub struct FixedPricePresaleHandler {
     pub q_price: u128,
     pub disable_withdraw: u8,
     pub disable_earlier_presale_end_once_cap_reached: u8,
-    pub padding0: [u8; 14],
+    pub padding0: [u8; 6],
+    pub withdraw_cutoff_duration: u64,
     pub padding1: u128,
 }
 
@@ -87,6 +96,7 @@ impl FixedPricePresaleHandler {
         q_price: u128,
         disable_earlier_presale_end_once_cap_reached: u8,
         disable_withdraw: u8,
+        withdraw_cutoff_duration: u64,
     ) -> Result<()> {
         let presale_raw_data_slice = bytemuck::try_cast_slice_mut::<u128, u8>(presale_raw_data)
             .map_err(|_| PresaleError::UndeterminedError)?;
@@ -99,6 +109,7 @@ impl FixedPricePresaleHandler {
             disable_earlier_presale_end_once_cap_reached;
         handler.q_price = q_price;
         handler.disable_withdraw = disable_withdraw;
+        handler.withdraw_cutoff_duration = withdraw_cutoff_duration;
 
         Ok(())
     }
@@ -110,6 +121,60 @@ impl FixedPricePresaleHandler {
     pub fn is_withdraw_disabled(&self) -> bool {
         self.disable_withdraw != 0
     }
+
+    /// Registry token price. Presale initialized before registry token price has no registry price, thus fallback to the presale token price.
+    pub fn get_registry_q_price(&self, presale_registry: &PresaleRegistry) -> u128 {
+        if presale_registry.q_price == 0 {
+            self.q_price
+        } else {
+            presale_registry.q_price
+        }
+    }
+
+    /// Escrow token price. Escrow with price discount buys at the discounted registry token price.
+    fn get_escrow_q_price(
+        &self,
+        presale_registry: &PresaleRegistry,
+        escrow: &Escrow,
+    ) -> Result<u128> {
+        let escrow_q_price = u128::from(MAX_FEE_BASIS_POINTS.safe_sub(escrow.price_discount_bps)?)
+            .safe_mul(self.get_registry_q_price(presale_registry))?
+            .safe_div(MAX_FEE_BASIS_POINTS.into())?;
+
+        Ok(escrow_q_price)
+    }
+
+    fn get_registry_base_token_sold(
+        &self,
+        presale_registry: &PresaleRegistry,
+        presale_supply: u64,
+    ) -> Result<u64> {
+        if presale_registry.total_deposit == 0 {
+            return Ok(0);
+        }
+
+        let sold_token: u64 = calculate_token_bought(
+            self.get_registry_q_price(presale_registry),
+            presale_registry.get_price_discount_included_total_deposit()?,
+        )?
+        .safe_cast()?;
+
+        Ok(sold_token.min(presale_supply))
+    }
+
+    fn get_registry_effective_supply(
+        &self,
+        presale: &Presale,
+        registry_index: usize,
+        current_timestamp: u64,
+    ) -> Result<u64> {
+        get_presale_registry_effective_supply(
+            presale,
+            registry_index,
+            current_timestamp,
+            |registry| self.get_registry_base_token_sold(registry, registry.presale_supply),
+        )
+    }
 }
 
 impl PresaleModeHandler for FixedPricePresaleHandler {
@@ -138,30 +203,45 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
             PresaleError::MissingPresaleExtraParams
         );
 
-        let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
+        // 2. Resolve token price of each registry
+        let mut is_uniform_price = true;
+        let mut total_max_quote_raisable: u128 = 0;
+
+        let registry_q_prices = presale_extra_param
+            .get_registry_q_prices(presale.total_presale_registry_count.into())?;
+
+        for (registry, q_price) in presale.presale_registries.iter_mut().zip(registry_q_prices) {
+            if registry.is_uninitialized() {
+                continue;
+            }
 
-        // 2. Validate fixed price presale parameters
+            registry.q_price = q_price;
+
+            if q_price != presale_extra_param.q_price {
+                is_uniform_price = false;
+            }
+
+            total_max_quote_raisable = total_max_quote_raisable.saturating_add(
+                calculate_max_quote_raisable(q_price, registry.presale_supply)?,
+            );
+        }
+
+        // 3. Validate fixed price presale parameters
         // TODO: Should we make sure there's no impossible to fill gap?
         // For example: 1 token = 1 USDC, presale_maximum_cap = 100 USDC, buyer_minimum_deposit_cap = 20 USDC, buyer_maximum_deposit_cap = 90 USDC
         // User 1 deposit 90 USDC, remaining_presale_cap = 100 - 90 = 10
         // But buyer_minimum_deposit_cap = 20, thus it's impossible to fill the gap
-        for registry in presale.presale_registries.iter() {
+        for (idx, registry) in presale.presale_registries.iter().enumerate() {
             if !registry.is_uninitialized() {
                 // ensure buyer_minimum_deposit_cap and buyer_maximum_deposit_cap can buy at least 1 token and not exceed u64::MAX token
-                ensure_token_buyable(
-                    presale_extra_param.q_price,
-                    registry.buyer_minimum_deposit_cap,
-                )?;
-                ensure_token_buyable(
-                    presale_extra_param.q_price,
-                    registry.buyer_maximum_deposit_cap,
-                )?;
+                ensure_token_buyable(registry.q_price, registry.buyer_minimum_deposit_cap)?;
+                ensure_token_buyable(registry.q_price, registry.buyer_maximum_deposit_cap)?;
 
-                // In permissioned whitelist mode, ensure buyer min/max cap is set to minimum and maximum allowed range
+                // In permissioned registry, ensure buyer min/max cap is set to minimum and maximum allowed range
                 // This reduces the mistake of setting unusable buyer cap in permissioned presale at offchain
-                if whitelist_mode.is_permissioned() {
+                if presale.get_registry_whitelist_mode(idx)?.is_permissioned() {
                     let min_quote_amount =
-                        calculate_min_quote_amount_for_base_lamport(presale_extra_param.q_price)?;
+                        calculate_min_quote_amount_for_base_lamport(registry.q_price)?;
 
                     require!(
                         registry.buyer_minimum_deposit_cap == min_quote_amount,
@@ -173,29 +253,37 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
                         PresaleError::InvalidBuyerCapRange
                     );
                 }
+
+                // Ensure there's a gap between presale minimum cap and presale maximum cap
+                // This is to prevent presale progress stuck when both min and max cap are unreachable (e.g. both are the same)
+                ensure_gap_between_min_and_max_presale_cap(
+                    registry.q_price,
+                    presale.presale_minimum_cap,
+                    presale.presale_maximum_cap,
+                )?;
             }
         }
 
         // Ensure presale supply is enough to fulfill presale maximum cap
-        ensure_enough_presale_supply(
-            presale_extra_param.q_price,
-            presale.presale_supply,
-            presale.presale_maximum_cap,
-        )?;
-
-        // Ensure there's a gap between presale minimum cap and presale maximum cap
-        // This is to prevent presale progress stuck when both min and max cap are unreachable (e.g. both are the same)
-        ensure_gap_between_min_and_max_presale_cap(
-            presale_extra_param.q_price,
-            presale.presale_minimum_cap,
-            presale.presale_maximum_cap,
-        )?;
+        if is_uniform_price {
+            ensure_enough_presale_supply(
+                presale_extra_param.q_price,
+                presale.presale_supply,
+                presale.presale_maximum_cap,
+            )?;
+        } else {
+            require!(
+                total_max_quote_raisable >= u128::from(presale.presale_maximum_cap),
+                PresaleError::InvalidTokenPrice
+            );
+        }
 
         FixedPricePresaleHandler::initialize_data(
             &mut presale.presale_mode_raw_data,
             presale_extra_param.q_price,
             presale_params.disable_earlier_presale_end_once_cap_reached,
             presale_extra_param.disable_withdraw,
+            presale_params.withdraw_cutoff_duration,
         )?;
 
         Ok(())
@@ -203,33 +291,42 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
 
     /// Returns the remaining deposit quota for a fixed price presale.
     /// Fixed price presale cannot deposit more than the presale maximum cap.
-    fn get_remaining_deposit_quota(&self, presale: &Presale, escrow: &Escrow) -> Result<u64> {
+    /// Presale caps bound the quote token actually raised, thus they're compared against the raw total deposit. The price discount bonus only
+    /// weighs the base token allocation, which is bounded by the registry supply below, so a discounted escrow can't buy over the supply.
+    fn get_remaining_deposit_quota(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        current_timestamp: u64,
+    ) -> Result<u64> {
         let global_remaining_quota = presale.get_remaining_deposit_quota()?;
         let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+        let presale_registry_supply = self.get_registry_effective_supply(
+            presale,
+            escrow.registry_index.into(),
+            current_timestamp,
+        )?;
 
-        let total_token_sold: u64 = if presale_registry.total_deposit > 0 {
-            calculate_token_bought(self.q_price, presale_registry.total_deposit)?
-                .safe_cast()?
-                // Reason for min: Due to deposit amount is rounding up, it's possible total_token_sold > presale_supply
-                // Example: presale_supply = 100, q_price = 0.333, registry.total_deposit 33, total_token_sold = 99 (round down)
-                // registry_remaining_base_token = 100 - 99 = 1
-                // registry_remaining_deposit_quota = 1 * 0.333 = 0.333 -> 1 (round up)
-                // base_token_purchasable_with_remaining_deposit_quota = 1 / 0.333 = 3 (round down)
-                // base_token_purchasable_with_remaining_deposit_quota = 3 > registry_remaining_base_token = 1
-                .min(presale_registry.presale_supply)
-        } else {
-            0
-        };
+        // Reason for min: Due to deposit amount is rounding up, it's possible total_token_sold > presale_supply
+        // Example: presale_supply = 100, q_price = 0.333, registry.total_deposit 33, total_token_sold = 99 (round down)
+        // registry_remaining_base_token = 100 - 99 = 1
+        // registry_remaining_deposit_quota = 1 * 0.333 = 0.333 -> 1 (round up)
+        // base_token_purchasable_with_remaining_deposit_quota = 1 / 0.333 = 3 (round down)
+        // base_token_purchasable_with_remaining_deposit_quota = 3 > registry_remaining_base_token = 1
+        let total_token_sold =
+            self.get_registry_base_token_sold(presale_registry, presale_registry_supply)?;
 
-        if total_token_sold == presale_registry.presale_supply {
+        if total_token_sold == presale_registry_supply {
             return Ok(0);
         }
 
-        let registry_remaining_base_token =
-            presale_registry.presale_supply.safe_sub(total_token_sold)?;
+        let registry_remaining_base_token = presale_registry_supply.safe_sub(total_token_sold)?;
+
+        // Escrow with price discount buys the remaining base token at the discounted price
+        let escrow_q_price = self.get_escrow_q_price(presale_registry, escrow)?;
 
         let registry_remaining_deposit_quota: u64 = u128::from(registry_remaining_base_token)
-            .safe_mul(self.q_price)?
+            .safe_mul(escrow_q_price)?
             .div_ceil(SCALE_MULTIPLIER)
             .safe_cast()?;
 
@@ -238,10 +335,12 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
 
         Ok(global_remaining_quota
             .min(personal_remaining_quota)
-            .min(registry_remaining_deposit_quota))
+            .min(registry_remaining_deposit_quota)
+            .min(presale_registry.get_remaining_deposit_quota()))
     }
 
     /// Fixed price presale stop accept deposit when the presale maximum cap is reached. Therefore, can end presale immediately.
+    /// Like the minimum cap, the maximum cap is compared against the raw quote token raised, excluding the price discount bonus.
     fn end_presale_if_max_cap_reached(
         &self,
         presale: &mut Presale,
@@ -258,6 +357,19 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
         !self.is_withdraw_disabled()
     }
 
+    fn get_withdraw_cutoff_duration(&self) -> u64 {
+        self.withdraw_cutoff_duration
+    }
+
+    fn calculate_withdraw_penalty(
+        &self,
+        _presale: &Presale,
+        _amount: u64,
+        _current_timestamp: u64,
+    ) -> Result<u64> {
+        Ok(0)
+    }
+
     fn process_withdraw(
         &self,
         presale: &mut Presale,
@@ -286,24 +398,23 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
     }
 
     fn get_total_base_token_sold(&self, presale: &Presale) -> Result<u64> {
-        let mut total_sold_token: u128 = 0;
+        let mut total_sold_token: u64 = 0;
 
-        for presale_registry in presale.presale_registries.iter() {
+        for (idx, presale_registry) in presale.presale_registries.iter().enumerate() {
             if presale_registry.is_uninitialized() {
                 break;
             }
 
-            if presale_registry.total_deposit == 0 {
-                continue;
-            }
+            let presale_registry_supply =
+                self.get_registry_effective_supply(presale, idx, presale.presale_end_time)?;
 
-            let sold_token = calculate_token_bought(self.q_price, presale_registry.total_deposit)?
-                .min(presale_registry.presale_supply.into());
+            let sold_token =
+                self.get_registry_base_token_sold(presale_registry, presale_registry_supply)?;
 
             total_sold_token = total_sold_token.safe_add(sold_token)?;
         }
 
-        Ok(total_sold_token.safe_cast()?)
+        Ok(total_sold_token)
     }
 
     fn get_escrow_cumulative_claimable_token(
@@ -314,35 +425,57 @@ impl PresaleModeHandler for FixedPricePresaleHandler {
     ) -> Result<u64> {
         // 1. Calculate how many base tokens were bought
         let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+        let presale_registry_supply = self.get_registry_effective_supply(
+            presale,
+            escrow.registry_index.into(),
+            current_timestamp,
+        )?;
         let total_sold_token =
-            calculate_token_bought(self.q_price, presale_registry.total_deposit)?
-                .safe_cast()?
-                .min(presale_registry.presale_supply);
+            self.get_registry_base_token_sold(presale_registry, presale_registry_supply)?;
 
         // 2. Calculate how many base tokens can be claimed based on vesting schedule
+        // Escrow share is weighted by the deposit valued at the registry token price, so discounted escrow gets more token
         let claimable_bought_token = calculate_cumulative_claimable_amount_for_user(
-            presale.immediate_release_bps,
+            escrow.get_immediate_release_bps(presale),
             presale.immediate_release_timestamp,
             total_sold_token,
             presale.vesting_start_time,
-            presale.vest_duration,
+            escrow.get_vest_duration(presale),
             current_timestamp,
-            escrow.total_deposit,
-            presale_registry.total_deposit,
+            escrow.get_price_discount_included_deposit()?,
+            presale_registry.get_price_discount_included_total_deposit()?,
         )?;
 
         Ok(claimable_bought_token)
     }
 
-    fn suggest_deposit_amount(&self, max_deposit_amount: u64) -> Result<u64> {
-        calculate_quote_token_without_surplus(self.q_price, max_deposit_amount)
+    fn suggest_deposit_amount(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        max_deposit_amount: u64,
+    ) -> Result<u64> {
+        let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+        calculate_quote_token_without_surplus(
+            self.get_escrow_q_price(presale_registry, escrow)?,
+            max_deposit_amount,
+        )
     }
 
-    fn suggest_withdraw_amount(&self, escrow: &Escrow, max_withdraw_amount: u64) -> Result<u64> {
+    fn suggest_withdraw_amount(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        max_withdraw_amount: u64,
+    ) -> Result<u64> {
         if escrow.total_deposit == max_withdraw_amount {
             return Ok(max_withdraw_amount);
         }
-        calculate_quote_token_without_surplus(self.q_price, max_withdraw_amount)
+        let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+        calculate_quote_token_without_surplus(
+            self.get_escrow_q_price(presale_registry, escrow)?,
+            max_withdraw_amount,
+        )
     }
 }
 
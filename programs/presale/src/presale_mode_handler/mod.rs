@@ -4,7 +4,7 @@ mod fixed_price_presale;
 pub use fixed_price_presale::*;
 
 mod prorata_presale;
-use prorata_presale::*;
+pub use prorata_presale::*;
 
 mod fcfs_presale;
 pub use fcfs_presale::*;
@@ -28,13 +28,25 @@ pub trait PresaleModeHandler {
         presale_params: &PresaleArgs,
         remaining_accounts: &'e mut &'c [AccountInfo<'info>],
     ) -> Result<()>;
-    fn get_remaining_deposit_quota(&self, presale: &Presale, escrow: &Escrow) -> Result<u64>;
+    fn get_remaining_deposit_quota(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        current_timestamp: u64,
+    ) -> Result<u64>;
     fn end_presale_if_max_cap_reached(
         &self,
         presale: &mut Presale,
         current_timestamp: u64,
     ) -> Result<()>;
     fn can_withdraw(&self) -> bool;
+    fn get_withdraw_cutoff_duration(&self) -> u64;
+    fn calculate_withdraw_penalty(
+        &self,
+        presale: &Presale,
+        amount: u64,
+        current_timestamp: u64,
+    ) -> Result<u64>;
     fn process_withdraw(
         &self,
         presale: &mut Presale,
@@ -54,13 +66,26 @@ pub trait PresaleModeHandler {
         escrow: &Escrow,
         current_timestamp: u64,
     ) -> Result<u64>;
-    fn suggest_deposit_amount(&self, max_deposit_amount: u64) -> Result<u64>;
-    fn suggest_withdraw_amount(&self, escrow: &Escrow, max_withdraw_amount: u64) -> Result<u64>;
+    fn suggest_deposit_amount(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        max_deposit_amount: u64,
+    ) -> Result<u64>;
+    fn suggest_withdraw_amount(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        max_withdraw_amount: u64,
+    ) -> Result<u64>;
 }
 
+/// In permissioned registries, ensure buyer min/max cap is set to minimum and maximum allowed range
 pub fn enforce_dynamic_price_registries_max_buyer_cap_range(presale: &Presale) -> Result<()> {
-    for registry in presale.presale_registries.iter() {
-        if !registry.is_uninitialized() {
+    for (idx, registry) in presale.presale_registries.iter().enumerate() {
+        if !registry.is_uninitialized()
+            && presale.get_registry_whitelist_mode(idx)?.is_permissioned()
+        {
             require!(
                 registry.buyer_minimum_deposit_cap == 1
                     && registry.buyer_maximum_deposit_cap == presale.presale_maximum_cap,
@@ -82,7 +107,11 @@ pub fn get_presale_mode_handler(presale: &Presale) -> Result<Box<dyn PresaleMode
                 .map_err(|_| PresaleError::UndeterminedError)?;
             Ok(Box::new(*handler))
         }
-        PresaleMode::Prorata => Ok(Box::new(ProrataPresaleHandler)),
+        PresaleMode::Prorata => {
+            let handler = bytemuck::try_from_bytes::<ProrataPresaleHandler>(raw_data_slice)
+                .map_err(|_| PresaleError::UndeterminedError)?;
+            Ok(Box::new(*handler))
+        }
         PresaleMode::Fcfs => {
             let handler = bytemuck::try_from_bytes::<FcfsPresaleHandler>(raw_data_slice)
                 .map_err(|_| PresaleError::UndeterminedError)?;
@@ -107,20 +136,86 @@ pub fn end_presale_if_max_cap_reached(
     Ok(())
 }
 
-pub fn get_dynamic_price_based_total_base_token_sold(presale: &Presale) -> Result<u64> {
-    // FCFS / Prorata presale sells the full supply of base token, but if no one deposit for the particular registry, it consider nothing been sold
-    let mut total_token_sold = 0;
+/// Registry presale supply. The unsold rollover registry also receives the unsold base token of other registries which no longer accept deposit.
+pub fn get_presale_registry_effective_supply<F>(
+    presale: &Presale,
+    registry_index: usize,
+    current_timestamp: u64,
+    get_registry_base_token_sold: F,
+) -> Result<u64>
+where
+    F: Fn(&PresaleRegistry) -> Result<u64>,
+{
+    let presale_registry = presale.get_presale_registry(registry_index)?;
+
+    if !presale.is_unsold_rollover_enabled()
+        || registry_index != usize::from(presale.unsold_rollover_registry_index)
+    {
+        return Ok(presale_registry.presale_supply);
+    }
+
+    let mut effective_supply = presale_registry.presale_supply;
 
-    for registry in presale.presale_registries.iter() {
+    for (idx, registry) in presale.presale_registries.iter().enumerate() {
         if registry.is_uninitialized() {
             break;
         }
 
-        if registry.total_deposit == 0 {
+        if idx == registry_index
+            || !registry.is_deposit_closed(presale.presale_end_time, current_timestamp)
+        {
             continue;
         }
 
-        total_token_sold = total_token_sold.safe_add(registry.presale_supply)?;
+        let unsold_token = registry
+            .presale_supply
+            .safe_sub(get_registry_base_token_sold(registry)?)?;
+
+        effective_supply = effective_supply.safe_add(unsold_token)?;
+    }
+
+    Ok(effective_supply)
+}
+
+/// FCFS / Prorata presale sells the full supply of base token, but if no one deposit for the particular registry, it consider nothing been sold
+fn get_dynamic_price_registry_base_token_sold(
+    presale_registry: &PresaleRegistry,
+    presale_supply: u64,
+) -> u64 {
+    if presale_registry.total_deposit == 0 {
+        0
+    } else {
+        presale_supply
+    }
+}
+
+pub fn get_dynamic_price_registry_effective_supply(
+    presale: &Presale,
+    registry_index: usize,
+    current_timestamp: u64,
+) -> Result<u64> {
+    get_presale_registry_effective_supply(presale, registry_index, current_timestamp, |registry| {
+        Ok(get_dynamic_price_registry_base_token_sold(
+            registry,
+            registry.presale_supply,
+        ))
+    })
+}
+
+pub fn get_dynamic_price_based_total_base_token_sold(presale: &Presale) -> Result<u64> {
+    let mut total_token_sold = 0;
+
+    for (idx, registry) in presale.presale_registries.iter().enumerate() {
+        if registry.is_uninitialized() {
+            break;
+        }
+
+        let effective_supply =
+            get_dynamic_price_registry_effective_supply(presale, idx, presale.presale_end_time)?;
+
+        total_token_sold = total_token_sold.safe_add(
+            get_dynamic_price_registry_base_token_sold(registry, effective_supply),
+        )?;
     }
 
     Ok(total_token_sold)
@@ -132,12 +227,17 @@ pub fn process_claim_full_presale_supply_by_share(
     current_timestamp: u64,
 ) -> Result<()> {
     let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+    let presale_registry_supply = get_dynamic_price_registry_effective_supply(
+        presale,
+        escrow.registry_index.into(),
+        current_timestamp,
+    )?;
     let cumulative_escrow_claimable_token = calculate_cumulative_claimable_amount_for_user(
-        presale.immediate_release_bps,
+        escrow.get_immediate_release_bps(presale),
         presale.immediate_release_timestamp,
-        presale_registry.presale_supply,
+        presale_registry_supply,
         presale.vesting_start_time,
-        presale.vest_duration,
+        escrow.get_vest_duration(presale),
         current_timestamp,
         escrow.total_deposit,
         presale_registry.total_deposit,
@@ -1,28 +1,66 @@
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+
 use crate::PresaleModeHandler;
 use crate::*;
 
-pub struct ProrataPresaleHandler;
+#[zero_copy]
+pub struct ProrataPresaleHandler {
+    pub withdraw_penalty_bps: u16,
+    pub padding0: [u8; 6],
+    pub withdraw_cutoff_duration: u64,
+    pub padding1: [u128; 2],
+}
+
+impl ProrataPresaleHandler {
+    pub fn initialize_data(
+        presale_raw_data: &mut [u128; 3],
+        withdraw_penalty_bps: u16,
+        withdraw_cutoff_duration: u64,
+    ) -> Result<()> {
+        let presale_raw_data_slice = bytemuck::try_cast_slice_mut::<u128, u8>(presale_raw_data)
+            .map_err(|_| PresaleError::UndeterminedError)?;
+
+        let handler = bytemuck::try_from_bytes_mut::<ProrataPresaleHandler>(presale_raw_data_slice)
+            .map_err(|_| PresaleError::UndeterminedError)?;
+
+        handler.withdraw_penalty_bps = withdraw_penalty_bps;
+        handler.withdraw_cutoff_duration = withdraw_cutoff_duration;
+
+        Ok(())
+    }
+}
 
 impl PresaleModeHandler for ProrataPresaleHandler {
     fn initialize_presale<'c: 'info, 'e, 'info>(
         &self,
         _presale_pubkey: Pubkey,
         presale: &mut Presale,
-        _presale_params: &PresaleArgs,
+        presale_params: &PresaleArgs,
         _remaining_accounts: &'e mut &'c [AccountInfo<'info>],
     ) -> Result<()> {
-        let whitelist_mode: WhitelistMode = presale.whitelist_mode.safe_cast()?;
-        if whitelist_mode.is_permissioned() {
-            enforce_dynamic_price_registries_max_buyer_cap_range(&presale)?;
-        }
+        enforce_dynamic_price_registries_max_buyer_cap_range(&presale)?;
+
+        ProrataPresaleHandler::initialize_data(
+            &mut presale.presale_mode_raw_data,
+            presale_params.withdraw_penalty_bps,
+            presale_params.withdraw_cutoff_duration,
+        )?;
 
         Ok(())
     }
 
-    fn get_remaining_deposit_quota(&self, presale: &Presale, escrow: &Escrow) -> Result<u64> {
-        // Prorata can deposit > presale maximum cap. Therefore, the remaining deposit quota is the quote leftover in the escrow.
+    fn get_remaining_deposit_quota(
+        &self,
+        presale: &Presale,
+        escrow: &Escrow,
+        _current_timestamp: u64,
+    ) -> Result<u64> {
+        // Prorata can deposit > presale maximum cap. Therefore, the remaining deposit quota is the quote leftover in the escrow, bounded by the registry maximum cap if any.
         let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
-        escrow.get_remaining_deposit_quota(presale_registry.buyer_maximum_deposit_cap)
+        let personal_remaining_quota =
+            escrow.get_remaining_deposit_quota(presale_registry.buyer_maximum_deposit_cap)?;
+
+        Ok(personal_remaining_quota.min(presale_registry.get_remaining_deposit_quota()))
     }
 
     fn end_presale_if_max_cap_reached(
@@ -39,6 +77,37 @@ impl PresaleModeHandler for ProrataPresaleHandler {
         true
     }
 
+    fn get_withdraw_cutoff_duration(&self) -> u64 {
+        self.withdraw_cutoff_duration
+    }
+
+    /// Penalty rises linearly from 0 at presale start to withdraw_penalty_bps at presale end, to discourage deposit and pull manipulation of the oversubscription.
+    fn calculate_withdraw_penalty(
+        &self,
+        presale: &Presale,
+        amount: u64,
+        current_timestamp: u64,
+    ) -> Result<u64> {
+        if self.withdraw_penalty_bps == 0 {
+            return Ok(0);
+        }
+
+        let presale_duration = presale
+            .presale_end_time
+            .safe_sub(presale.presale_start_time)?;
+        let elapsed = current_timestamp
+            .saturating_sub(presale.presale_start_time)
+            .min(presale_duration);
+
+        // Round up in favor of the presale
+        let penalty = u128::from(amount)
+            .safe_mul(self.withdraw_penalty_bps.into())?
+            .safe_mul(elapsed.into())?
+            .div_ceil(u128::from(MAX_FEE_BASIS_POINTS).safe_mul(presale_duration.into())?);
+
+        Ok(penalty.safe_cast()?)
+    }
+
     fn process_withdraw(
         &self,
         presale: &mut Presale,
@@ -64,12 +133,17 @@ impl PresaleModeHandler for ProrataPresaleHandler {
         current_timestamp: u64,
     ) -> Result<u64> {
         let presale_registry = presale.get_presale_registry(escrow.registry_index.into())?;
+        let presale_registry_supply = get_dynamic_price_registry_effective_supply(
+            presale,
+            escrow.registry_index.into(),
+            current_timestamp,
+        )?;
         calculate_cumulative_claimable_amount_for_user(
-            presale.immediate_release_bps,
+            escrow.get_immediate_release_bps(presale),
             presale.immediate_release_timestamp,
-            presale_registry.presale_supply,
+            presale_registry_supply,
             presale.vesting_start_time,
-            presale.vest_duration,
+            escrow.get_vest_duration(presale),
             current_timestamp,
             escrow.total_deposit,
             presale_registry.total_deposit,
@@ -80,11 +154,21 @@ impl PresaleModeHandler for ProrataPresaleHandler {
         get_dynamic_price_based_total_base_token_sold(presale)
     }
 
-    fn suggest_deposit_amount(&self, max_deposit_amount: u64) -> Result<u64> {
+    fn suggest_deposit_amount(
+        &self,
+        _presale: &Presale,
+        _escrow: &Escrow,
+        max_deposit_amount: u64,
+    ) -> Result<u64> {
         Ok(max_deposit_amount)
     }
 
-    fn suggest_withdraw_amount(&self, _escrow: &Escrow, max_withdraw_amount: u64) -> Result<u64> {
+    fn suggest_withdraw_amount(
+        &self,
+        _presale: &Presale,
+        _escrow: &Escrow,
+        max_withdraw_amount: u64,
+    ) -> Result<u64> {
         Ok(max_withdraw_amount)
     }
 }
@@ -0,0 +1,127 @@
+use crate::*;
+
+/// Wallet excluded from the permissionless registry of a presale
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct DenylistEntry {
+    /// Presale pubkey that the entry is belong
+    pub presale: Pubkey,
+    /// Wallet excluded from creating escrow and depositing
+    pub wallet: Pubkey,
+    /// Padding for further use
+    pub padding: [u64; 4],
+}
+
+static_assertions::const_assert_eq!(DenylistEntry::INIT_SPACE, 96);
+static_assertions::assert_eq_align!(DenylistEntry, u64);
+
+impl DenylistEntry {
+    pub fn initialize(&mut self, presale: Pubkey, wallet: Pubkey) {
+        self.presale = presale;
+        self.wallet = wallet;
+    }
+}
+
+pub fn derive_denylist_entry(presale: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            crate::constants::seeds::DENYLIST_PREFIX,
+            presale.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+fn derive_escrow(presale: &Pubkey, owner: &Pubkey, registry_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            crate::constants::seeds::ESCROW_PREFIX,
+            presale.as_ref(),
+            owner.as_ref(),
+            registry_index.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Update the denied flag of the escrows of the wallet. A wallet can have a permissionless escrow in every permissionless registry,
+/// so the escrow PDA of every permissionless registry must be provided in registry order, whether the escrow has been created or not.
+pub fn set_escrows_denied(
+    presale: &Presale,
+    presale_pubkey: &Pubkey,
+    wallet: &Pubkey,
+    escrows: &[AccountInfo],
+    is_denied: bool,
+) -> Result<()> {
+    let mut escrows = escrows.iter();
+
+    for registry_index in 0..presale.total_presale_registry_count {
+        if presale
+            .get_registry_whitelist_mode(registry_index.into())?
+            .is_permissioned()
+        {
+            continue;
+        }
+
+        let escrow_ai = escrows.next().ok_or(PresaleError::InvalidDenylistEscrow)?;
+        require!(
+            escrow_ai.key() == derive_escrow(presale_pubkey, wallet, registry_index),
+            PresaleError::InvalidDenylistEscrow
+        );
+
+        // Escrow not created yet. Creation is guarded by the denylist entry.
+        if escrow_ai.data_is_empty() {
+            continue;
+        }
+
+        require!(
+            escrow_ai.owner == &crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        require!(escrow_ai.is_writable, ErrorCode::AccountNotMutable);
+
+        let mut data = escrow_ai.try_borrow_mut_data()?;
+        let (discriminator, escrow_data) = data.split_at_mut(Escrow::DISCRIMINATOR.len());
+        require!(
+            discriminator == Escrow::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let escrow = bytemuck::try_from_bytes_mut::<Escrow>(&mut escrow_data[..Escrow::INIT_SPACE])
+            .map_err(|_| PresaleError::UndeterminedError)?;
+        escrow.set_denied(is_denied);
+    }
+
+    Ok(())
+}
+
+/// Ensure the wallet is not denylisted. Once the presale has a denylist, the denylist entry PDA of the wallet must be provided, and it must not exist.
+pub fn ensure_not_denylisted(
+    presale: &Presale,
+    presale_pubkey: &Pubkey,
+    wallet: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !presale.is_denylist_enabled() {
+        return Ok(());
+    }
+
+    let denylist_entry = remaining_accounts
+        .first()
+        .ok_or(PresaleError::InvalidDenylistEntry)?;
+
+    require!(
+        denylist_entry.key() == derive_denylist_entry(presale_pubkey, wallet),
+        PresaleError::InvalidDenylistEntry
+    );
+
+    require!(
+        denylist_entry.data_is_empty(),
+        PresaleError::WalletDenylisted
+    );
+
+    Ok(())
+}
@@ -15,7 +15,9 @@ pub struct Escrow {
     pub is_remaining_quote_withdrawn: u8,
     // The index of the presale registry
     pub registry_index: u8,
-    pub padding0: [u8; 6],
+    // Determine whether the owner is denylisted after the escrow was created. Denied escrow can't deposit.
+    pub is_denied: u8,
+    pub padding0: [u8; 5],
     // Total pending claim token
     pub pending_claim_token: u64,
     // Personal deposit cap. Only available if whitelist mode is permissioned.
@@ -26,7 +28,21 @@ pub struct Escrow {
     pub total_deposit_fee: u64,
     // Timestamp of when the escrow was refreshed
     pub last_refreshed_at: u64,
-    pub padding: [u64; 8],
+    // Quote mint config of the quote mint deposited with. Default pubkey means the presale quote mint.
+    pub quote_mint_config: Pubkey,
+    // Discount on the registry token price in bps. Fixed price presale only.
+    pub price_discount_bps: u16,
+    // Immediate release bps of the escrow. Only used when is_vesting_overridden is set.
+    pub immediate_release_bps: u16,
+    // Determine whether the escrow vests with its own immediate release bps and vest duration
+    pub is_vesting_overridden: u8,
+    pub padding1: [u8; 3],
+    // Vest duration of the escrow. Only used when is_vesting_overridden is set.
+    pub vest_duration: u64,
+    // Gating token locked upon token gated escrow creation. Returned to the owner once the presale ends.
+    pub locked_gating_amount: u64,
+    // Version of the merkle root config which last set the deposit cap. Only available if whitelist mode is permissioned with merkle proof.
+    pub merkle_root_config_version: u64,
 }
 
 static_assertions::const_assert_eq!(Escrow::INIT_SPACE, 192);
@@ -51,6 +67,80 @@ impl Escrow {
         Ok(())
     }
 
+    pub fn apply_terms_override(&mut self, terms_override: &EscrowTermsOverride) {
+        self.price_discount_bps = terms_override.price_discount_bps;
+        if let Some(VestingOverride {
+            immediate_release_bps,
+            vest_duration,
+        }) = terms_override.vesting
+        {
+            self.is_vesting_overridden = 1;
+            self.immediate_release_bps = immediate_release_bps;
+            self.vest_duration = vest_duration;
+        }
+    }
+
+    /// Whether the terms override is the one applied when the escrow was created
+    pub fn matches_terms_override(&self, terms_override: Option<&EscrowTermsOverride>) -> bool {
+        let EscrowTermsOverride {
+            price_discount_bps,
+            vesting,
+        } = terms_override.copied().unwrap_or_default();
+
+        let escrow_vesting = self.is_vesting_overridden().then_some(VestingOverride {
+            immediate_release_bps: self.immediate_release_bps,
+            vest_duration: self.vest_duration,
+        });
+
+        price_discount_bps == self.price_discount_bps && vesting == escrow_vesting
+    }
+
+    pub fn update_deposit_max_cap(&mut self, deposit_cap: u64) -> Result<()> {
+        require!(
+            deposit_cap >= self.total_deposit,
+            PresaleError::DepositCapBelowTotalDeposit
+        );
+        self.deposit_max_cap = deposit_cap;
+
+        Ok(())
+    }
+
+    pub fn set_merkle_root_config_version(&mut self, merkle_root_config_version: u64) {
+        self.merkle_root_config_version = merkle_root_config_version;
+    }
+
+    pub fn is_vesting_overridden(&self) -> bool {
+        self.is_vesting_overridden != 0
+    }
+
+    pub fn get_immediate_release_bps(&self, presale: &Presale) -> u16 {
+        if self.is_vesting_overridden() {
+            self.immediate_release_bps
+        } else {
+            presale.immediate_release_bps
+        }
+    }
+
+    pub fn get_vest_duration(&self, presale: &Presale) -> u64 {
+        if self.is_vesting_overridden() {
+            self.vest_duration
+        } else {
+            presale.vest_duration
+        }
+    }
+
+    /// Extra quote token the deposit is worth at the undiscounted token price
+    pub fn get_price_discount_bonus(&self) -> Result<u64> {
+        calculate_price_discount_bonus(self.total_deposit, self.price_discount_bps)
+    }
+
+    /// Deposit valued at the undiscounted token price. Used as the escrow share of the registry sold token.
+    pub fn get_price_discount_included_deposit(&self) -> Result<u64> {
+        Ok(self
+            .total_deposit
+            .safe_add(self.get_price_discount_bonus()?)?)
+    }
+
     pub fn get_remaining_deposit_quota(&self, buyer_maximum_buy_cap: u64) -> Result<u64> {
         let maximum_buy_cap = buyer_maximum_buy_cap.min(self.deposit_max_cap);
         if self.total_deposit >= maximum_buy_cap {
@@ -61,12 +151,36 @@ impl Escrow {
         Ok(remaining_quota)
     }
 
+    pub fn is_denied(&self) -> bool {
+        self.is_denied != 0
+    }
+
+    pub fn set_denied(&mut self, is_denied: bool) {
+        self.is_denied = is_denied.into();
+    }
+
     pub fn deposit(&mut self, fee_excluded_deposit_amount: u64, fee: u64) -> Result<()> {
         self.total_deposit = self.total_deposit.safe_add(fee_excluded_deposit_amount)?;
         self.total_deposit_fee = self.total_deposit_fee.safe_add(fee)?;
         Ok(())
     }
 
+    /// Escrow deposit with a single quote mint. It can only switch to another quote mint when it has nothing deposited.
+    pub fn bind_quote_mint_config(&mut self, quote_mint_config: Pubkey) -> Result<()> {
+        if self.total_deposit == 0 && self.total_deposit_fee == 0 {
+            self.quote_mint_config = quote_mint_config;
+        }
+        self.ensure_quote_mint_config(quote_mint_config)
+    }
+
+    pub fn ensure_quote_mint_config(&self, quote_mint_config: Pubkey) -> Result<()> {
+        require!(
+            self.quote_mint_config == quote_mint_config,
+            PresaleError::EscrowQuoteMintMismatch
+        );
+        Ok(())
+    }
+
     pub fn withdraw(&mut self, amount: u64) -> Result<()> {
         self.total_deposit = self.total_deposit.safe_sub(amount)?;
         Ok(())
@@ -86,6 +200,18 @@ impl Escrow {
         Ok(())
     }
 
+    pub fn lock_gating_token(&mut self, amount: u64) {
+        self.locked_gating_amount = amount;
+    }
+
+    pub fn unlock_gating_token(&mut self) {
+        self.locked_gating_amount = 0;
+    }
+
+    pub fn has_locked_gating_token(&self) -> bool {
+        self.locked_gating_amount > 0
+    }
+
     pub fn is_remaining_quote_withdrawn(&self) -> bool {
         self.is_remaining_quote_withdrawn == 1
     }
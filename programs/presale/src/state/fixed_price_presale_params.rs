@@ -8,7 +8,8 @@ pub struct FixedPricePresaleExtraArgs {
     pub q_price: u128,
     pub owner: Pubkey,
     pub presale: Pubkey,
-    pub padding1: [u128; 4],
+    /// Price of registry 1 onwards. Registry 0 is priced at q_price. All 0 means every registry is priced at q_price.
+    pub registry_q_prices: [u128; MAX_REGISTRY_Q_PRICE_COUNT],
 }
 
 static_assertions::const_assert_eq!(FixedPricePresaleExtraArgs::INIT_SPACE, 160);
@@ -21,12 +22,50 @@ impl FixedPricePresaleExtraArgs {
         owner: Pubkey,
         presale: Pubkey,
         disable_withdraw: BoolType,
+        registry_q_prices: [u128; MAX_REGISTRY_Q_PRICE_COUNT],
     ) -> Result<()> {
         self.q_price = q_price;
+        self.registry_q_prices = registry_q_prices;
         self.owner = owner;
         self.presale = presale;
         self.disable_withdraw = disable_withdraw.into();
 
         Ok(())
     }
+
+    /// Token price of each registry. Either every registry from 1 onwards has a price, or none of them and every registry is priced at q_price.
+    pub fn get_registry_q_prices(
+        &self,
+        registry_count: usize,
+    ) -> Result<[u128; MAX_PRESALE_REGISTRY_COUNT]> {
+        let priced_registry_count = self
+            .registry_q_prices
+            .iter()
+            .filter(|&&q_price| q_price > 0)
+            .count();
+
+        let has_registry_q_prices = priced_registry_count > 0;
+
+        if has_registry_q_prices {
+            let unpriced_registry_count = registry_count.safe_sub(1)?;
+            require!(
+                priced_registry_count == unpriced_registry_count
+                    && self.registry_q_prices[..unpriced_registry_count]
+                        .iter()
+                        .all(|&q_price| q_price > 0),
+                PresaleError::InvalidTokenPrice
+            );
+        }
+
+        let mut q_prices = [0u128; MAX_PRESALE_REGISTRY_COUNT];
+        for (idx, q_price) in q_prices.iter_mut().enumerate().take(registry_count) {
+            *q_price = if idx == 0 || !has_registry_q_prices {
+                self.q_price
+            } else {
+                self.registry_q_prices[idx - 1]
+            };
+        }
+
+        Ok(q_prices)
+    }
 }
@@ -6,8 +6,10 @@ use static_assertions::const_assert_eq;
 pub struct MerkleRootConfig {
     /// The 256-bit merkle root.
     pub root: [u8; 32],
+    /// Determine whether the config is revoked. Revoked config can no longer be used to create escrow.
+    pub is_revoked: u8,
     /// Padding for future use
-    pub padding0: u64,
+    pub padding0: [u8; 7],
     /// Presale pubkey that config is belong
     pub presale: Pubkey,
     /// Version
@@ -25,6 +27,14 @@ impl MerkleRootConfig {
         self.root = root;
         self.version = version;
     }
+
+    pub fn is_revoked(&self) -> bool {
+        self.is_revoked != 0
+    }
+
+    pub fn revoke(&mut self) {
+        self.is_revoked = 1;
+    }
 }
 
 const_assert_eq!(std::mem::size_of::<MerkleRootConfig>(), 144);
@@ -18,3 +18,18 @@ pub use permissioned_server_metadata::*;
 
 mod presale_registry;
 pub use presale_registry::*;
+
+mod quote_mint_config;
+pub use quote_mint_config::*;
+
+mod referral;
+pub use referral::*;
+
+mod token_gate_config;
+pub use token_gate_config::*;
+
+mod denylist_entry;
+pub use denylist_entry::*;
+
+mod signature_voucher_receipt;
+pub use signature_voucher_receipt::*;
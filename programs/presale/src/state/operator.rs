@@ -5,15 +5,82 @@ use crate::*;
 pub struct Operator {
     pub owner: Pubkey,
     pub creator: Pubkey,
-    pub padding: [u64; 8],
+    /// Presale the operator is scoped to. Default pubkey allows every presale of the creator
+    pub presale: Pubkey,
+    /// Operator can't create escrow after this timestamp. 0 means no expiry
+    pub expiry_timestamp: u64,
+    /// Maximum total deposit cap of the escrows created by the operator. 0 means unlimited
+    pub max_total_deposit_cap: u64,
+    /// Total deposit cap of the escrows created by the operator
+    pub total_deposit_cap: u64,
+    /// Maximum number of escrows created by the operator. 0 means unlimited
+    pub max_escrow_count: u32,
+    /// Number of escrows created by the operator
+    pub escrow_count: u32,
 }
 
 static_assertions::const_assert_eq!(Operator::INIT_SPACE, 128);
 static_assertions::assert_eq_align!(Operator, u64);
 
+/// Escrow count and deposit cap granted by the operator in an instruction
+pub struct OperatorGrant {
+    pub escrow_count: u32,
+    pub deposit_cap: u64,
+}
+
 impl Operator {
-    pub fn initialize(&mut self, owner: Pubkey, creator: Pubkey) {
+    pub fn initialize(&mut self, owner: Pubkey, creator: Pubkey, params: &CreateOperatorParams) {
+        let CreateOperatorParams {
+            presale,
+            expiry_timestamp,
+            max_escrow_count,
+            max_total_deposit_cap,
+            ..
+        } = *params;
+
         self.owner = owner;
         self.creator = creator;
+        self.presale = presale;
+        self.expiry_timestamp = expiry_timestamp;
+        self.max_escrow_count = max_escrow_count;
+        self.max_total_deposit_cap = max_total_deposit_cap;
+    }
+
+    pub fn update(&mut self, params: &UpdateOperatorParams) {
+        let UpdateOperatorParams {
+            expiry_timestamp,
+            max_escrow_count,
+            max_total_deposit_cap,
+            ..
+        } = *params;
+
+        self.expiry_timestamp = expiry_timestamp;
+        self.max_escrow_count = max_escrow_count;
+        self.max_total_deposit_cap = max_total_deposit_cap;
+    }
+
+    pub fn is_allowed_for_presale(&self, presale: &Pubkey) -> bool {
+        self.presale == Pubkey::default() || self.presale == *presale
+    }
+
+    pub fn is_expired(&self, current_timestamp: u64) -> bool {
+        self.expiry_timestamp != 0 && current_timestamp > self.expiry_timestamp
+    }
+
+    pub fn grant(&mut self, grant: OperatorGrant) -> Result<()> {
+        let escrow_count = self.escrow_count.safe_add(grant.escrow_count)?;
+        let total_deposit_cap = self.total_deposit_cap.safe_add(grant.deposit_cap)?;
+
+        require!(
+            (self.max_escrow_count == 0 || escrow_count <= self.max_escrow_count)
+                && (self.max_total_deposit_cap == 0
+                    || total_deposit_cap <= self.max_total_deposit_cap),
+            PresaleError::OperatorQuotaExceeded
+        );
+
+        self.escrow_count = escrow_count;
+        self.total_deposit_cap = total_deposit_cap;
+
+        Ok(())
     }
 }
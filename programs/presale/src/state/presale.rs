@@ -1,4 +1,5 @@
 use crate::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 use num_enum::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
@@ -30,15 +31,20 @@ pub enum WhitelistMode {
     PermissionWithMerkleProof,
     /// Whitelist by allowing only vault's creator to create escrow account
     PermissionWithAuthority,
+    /// Whitelist using ed25519 signature voucher signed by the operator
+    PermissionWithSignature,
+    /// Whitelist by holding at least a threshold of the gating mint
+    PermissionWithTokenGate,
 }
 
 impl WhitelistMode {
     pub fn is_permissioned(&self) -> bool {
         match self {
             WhitelistMode::Permissionless => false,
-            WhitelistMode::PermissionWithMerkleProof | WhitelistMode::PermissionWithAuthority => {
-                true
-            }
+            WhitelistMode::PermissionWithMerkleProof
+            | WhitelistMode::PermissionWithAuthority
+            | WhitelistMode::PermissionWithSignature
+            | WhitelistMode::PermissionWithTokenGate => true,
         }
     }
 }
@@ -98,7 +104,15 @@ pub struct Presale {
     pub presale_mode: u8,
     /// Whitelist mode
     pub whitelist_mode: u8,
-    pub padding1: [u8; 5],
+    /// Whether unsold base token of registries is rolled over to the unsold rollover registry
+    pub is_unsold_rollover_enabled: u8,
+    /// Registry which receives the unsold base token of other registries
+    pub unsold_rollover_registry_index: u8,
+    /// Determine whether the finalization callback has been invoked
+    pub is_finalized: u8,
+    /// Determine whether the presale has denylisted wallets. Permissionless escrow creation requires the denylist entry of the owner once enabled.
+    pub is_denylist_enabled: u8,
+    pub padding1: u8,
     /// Presale target raised capital
     pub presale_maximum_cap: u64,
     /// Presale minimum raised capital. Else, presale consider as failed.
@@ -151,7 +165,20 @@ pub struct Presale {
     /// How many % of the token supply is released immediately
     pub immediate_release_bps: u16,
     pub presale_mode_raw_data: [u128; 3],
-    pub padding4: [u128; 4],
+    /// Program invoked via CPI when the presale is finalized. Default pubkey means no callback.
+    pub finalization_callback_program: Pubkey,
+    /// Deposit within this duration before the presale end time extends the presale end time. 0 means anti-snipe extension is disabled.
+    pub anti_snipe_window: u32,
+    /// Duration the presale end time is extended by for each deposit within the anti-snipe window
+    pub anti_snipe_extension: u32,
+    /// Maximum total duration the presale end time can be extended by
+    pub anti_snipe_max_total_extension: u32,
+    /// Total duration the presale end time has been extended by
+    pub anti_snipe_total_extension: u32,
+    /// Total withdraw penalty charged on withdrawals. It's kept apart from the deposit fee, so it's never refunded to the escrows
+    pub total_withdraw_penalty: u64,
+    /// Part of total_withdraw_penalty held by the alternative quote mint vaults, in accounting unit
+    pub total_alternative_quote_withdraw_penalty: u64,
     /// Presale registries. Note: Supporting more registries will causes increased account size.
     pub presale_registries: [PresaleRegistry; MAX_PRESALE_REGISTRY_COUNT],
 }
@@ -172,9 +199,10 @@ pub struct PresaleInitializeArgs<'a> {
     pub base: Pubkey,
     pub base_token_program: Pubkey,
     pub quote_token_program: Pubkey,
+    pub finalization_callback_program: Pubkey,
 }
 
-fn token_program_to_flag(program: Pubkey) -> TokenProgramFlags {
+pub fn token_program_to_flag(program: Pubkey) -> TokenProgramFlags {
     if program == anchor_spl::token::ID {
         TokenProgramFlags::SplToken
     } else if program == anchor_spl::token_2022::ID {
@@ -224,6 +252,7 @@ impl Presale {
             base,
             base_token_program,
             quote_token_program,
+            finalization_callback_program,
         } = args;
 
         self.owner = owner;
@@ -234,14 +263,10 @@ impl Presale {
         self.base = base;
         self.base_token_program_flag = token_program_to_flag(base_token_program).into();
         self.quote_token_program_flag = token_program_to_flag(quote_token_program).into();
+        self.finalization_callback_program = finalization_callback_program;
 
         for (idx, registry) in presale_registries.iter().enumerate() {
-            self.presale_registries[idx].init(
-                registry.presale_supply,
-                registry.buyer_minimum_deposit_cap,
-                registry.buyer_maximum_deposit_cap,
-                registry.deposit_fee_bps,
-            );
+            self.presale_registries[idx].init(registry);
 
             self.presale_supply = self.presale_supply.safe_add(registry.presale_supply)?;
         }
@@ -255,6 +280,11 @@ impl Presale {
             whitelist_mode,
             presale_mode,
             unsold_token_action,
+            enable_unsold_rollover,
+            unsold_rollover_registry_index,
+            anti_snipe_window,
+            anti_snipe_extension,
+            anti_snipe_max_total_extension,
             ..
         } = presale_params;
 
@@ -266,6 +296,11 @@ impl Presale {
         self.whitelist_mode = whitelist_mode;
         self.presale_mode = presale_mode;
         self.unsold_token_action = unsold_token_action;
+        self.is_unsold_rollover_enabled = enable_unsold_rollover;
+        self.unsold_rollover_registry_index = unsold_rollover_registry_index;
+        self.anti_snipe_window = anti_snipe_window;
+        self.anti_snipe_extension = anti_snipe_extension;
+        self.anti_snipe_max_total_extension = anti_snipe_max_total_extension;
         self.created_at = current_timestamp;
 
         if let Some(LockedVestingArgs {
@@ -356,6 +391,43 @@ impl Presale {
         self.recalculate_presale_timing(current_timestamp)
     }
 
+    /// Extend the presale end time when a deposit lands within the anti-snipe window, bounded by the maximum total extension.
+    pub fn extend_presale_end_time_if_within_anti_snipe_window(
+        &mut self,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        let remaining_extension = self
+            .anti_snipe_max_total_extension
+            .safe_sub(self.anti_snipe_total_extension)?;
+
+        if self.anti_snipe_window == 0 || remaining_extension == 0 {
+            return Ok(());
+        }
+
+        let anti_snipe_window_start_time = self
+            .presale_end_time
+            .saturating_sub(self.anti_snipe_window.into());
+
+        if current_timestamp < anti_snipe_window_start_time {
+            return Ok(());
+        }
+
+        let extension = self.anti_snipe_extension.min(remaining_extension);
+        self.anti_snipe_total_extension = self.anti_snipe_total_extension.safe_add(extension)?;
+
+        let new_presale_end_time = self.presale_end_time.safe_add(extension.into())?;
+
+        // Registry deposit window closing along with the presale is extended too
+        let presale_end_time = self.presale_end_time;
+        for presale_registry in self.presale_registries.iter_mut() {
+            if presale_registry.deposit_end_time == presale_end_time {
+                presale_registry.deposit_end_time = new_presale_end_time;
+            }
+        }
+
+        self.recalculate_presale_timing(new_presale_end_time)
+    }
+
     pub fn get_remaining_deposit_quota(&self) -> Result<u64> {
         let remaining_quota = self.presale_maximum_cap.safe_sub(self.total_deposit)?;
         Ok(remaining_quota)
@@ -388,6 +460,22 @@ impl Presale {
         Ok(())
     }
 
+    /// Withdraw penalty is retained by the presale apart from the escrow deposit fee. It's collected by the creator, even when the presale failed.
+    pub fn charge_withdraw_penalty(&mut self, registry_index: u8, penalty: u64) -> Result<()> {
+        let presale_registry = self.get_presale_registry_mut(registry_index.into())?;
+        presale_registry.charge_withdraw_penalty(penalty)?;
+        self.total_withdraw_penalty = self.total_withdraw_penalty.safe_add(penalty)?;
+        Ok(())
+    }
+
+    /// Track the part of the withdraw penalty retained by an alternative quote mint vault, in accounting unit
+    pub fn charge_alternative_quote_withdraw_penalty(&mut self, penalty: u64) -> Result<()> {
+        self.total_alternative_quote_withdraw_penalty = self
+            .total_alternative_quote_withdraw_penalty
+            .safe_add(penalty)?;
+        Ok(())
+    }
+
     pub fn update_total_refunded_quote_token(
         &mut self,
         amount: u64,
@@ -486,6 +574,200 @@ impl Presale {
         Ok(total_token_unsold)
     }
 
+    /// Total quote token raised. Prorata can have total_deposit > presale_maximum_cap
+    pub fn get_total_raised_quote(&self) -> u64 {
+        self.total_deposit.min(self.presale_maximum_cap)
+    }
+
+    /// Raised quote token and collected deposit fee of part of the registries deposit of a completed presale, in accounting unit.
+    /// Prorata refunds of the oversubscribed quote token are deducted proportionally with the given rounding.
+    pub fn get_quote_deposit_share(
+        &self,
+        registry_deposits: &[u64],
+        registry_deposit_fees: &[u64],
+        refund_rounding: Rounding,
+    ) -> Result<QuoteDepositShare> {
+        let presale_mode: PresaleMode = self.presale_mode.safe_cast()?;
+        let presale_remaining_quote = self.get_remaining_quote();
+
+        let mut raised_quote: u64 = 0;
+        let mut collected_fee: u64 = 0;
+
+        for ((registry, &deposit), &deposit_fee) in self
+            .presale_registries
+            .iter()
+            .zip(registry_deposits)
+            .zip(registry_deposit_fees)
+        {
+            let (refund_amount, refund_fee) = if presale_mode == PresaleMode::Prorata {
+                let RemainingQuote {
+                    refund_amount,
+                    refund_fee,
+                } = registry.get_finalized_presale_remaining_quote(
+                    presale_remaining_quote,
+                    self.total_deposit,
+                )?;
+
+                let share_refund_amount = if registry.total_deposit > 0 {
+                    mul_div(
+                        deposit,
+                        refund_amount,
+                        registry.total_deposit,
+                        refund_rounding,
+                    )?
+                } else {
+                    0
+                };
+
+                let share_refund_fee = if registry.total_deposit_fee > 0 {
+                    mul_div(
+                        deposit_fee,
+                        refund_fee,
+                        registry.total_deposit_fee,
+                        refund_rounding,
+                    )?
+                } else {
+                    0
+                };
+
+                (share_refund_amount, share_refund_fee)
+            } else {
+                (0, 0)
+            };
+
+            raised_quote = raised_quote.safe_add(deposit.safe_sub(refund_amount)?)?;
+            collected_fee = collected_fee.safe_add(deposit_fee.safe_sub(refund_fee)?)?;
+        }
+
+        Ok(QuoteDepositShare {
+            raised_quote,
+            collected_fee,
+        })
+    }
+
+    /// Upper bound of the raised quote token and collected deposit fee held by the alternative quote mint vaults
+    fn get_alternative_quote_deposit_share(&self) -> Result<QuoteDepositShare> {
+        let registry_deposits = self
+            .presale_registries
+            .map(|registry| registry.total_alternative_quote_deposit);
+        let registry_deposit_fees = self
+            .presale_registries
+            .map(|registry| registry.total_alternative_quote_deposit_fee);
+
+        self.get_quote_deposit_share(&registry_deposits, &registry_deposit_fees, Rounding::Down)
+    }
+
+    /// Raised quote token held by the quote token vault. Excludes deposits with alternative quote mints.
+    pub fn get_quote_token_vault_raised_quote(&self) -> Result<u64> {
+        let QuoteDepositShare { raised_quote, .. } = self.get_alternative_quote_deposit_share()?;
+        Ok(self.get_total_raised_quote().saturating_sub(raised_quote))
+    }
+
+    /// Deposit fee held by the quote token vault. Excludes deposits with alternative quote mints.
+    pub fn get_quote_token_vault_collected_fee(&self) -> Result<u64> {
+        let QuoteDepositShare { collected_fee, .. } = self.get_alternative_quote_deposit_share()?;
+        Ok(self
+            .get_total_collected_fee()?
+            .saturating_sub(collected_fee))
+    }
+
+    /// Referrer share of the referred deposit fee of each registry. Deposit fee refunded by prorata presale is excluded.
+    pub fn get_referral_fee(
+        &self,
+        registry_referred_deposit_fees: &[u64],
+        rounding: Rounding,
+    ) -> Result<u64> {
+        let presale_mode: PresaleMode = self.presale_mode.safe_cast()?;
+        let presale_remaining_quote = self.get_remaining_quote();
+
+        let mut referral_fee: u64 = 0;
+
+        for (registry, &referred_deposit_fee) in self
+            .presale_registries
+            .iter()
+            .zip(registry_referred_deposit_fees)
+        {
+            if referred_deposit_fee == 0 || registry.referral_fee_bps == 0 {
+                continue;
+            }
+
+            let collected_referred_deposit_fee = if presale_mode == PresaleMode::Prorata {
+                let RemainingQuote { refund_fee, .. } = registry
+                    .get_finalized_presale_remaining_quote(
+                        presale_remaining_quote,
+                        self.total_deposit,
+                    )?;
+                let registry_collected_fee = registry.total_deposit_fee.safe_sub(refund_fee)?;
+
+                mul_div(
+                    referred_deposit_fee,
+                    registry_collected_fee,
+                    registry.total_deposit_fee,
+                    rounding,
+                )?
+            } else {
+                referred_deposit_fee
+            };
+
+            let registry_referral_fee = mul_div(
+                collected_referred_deposit_fee,
+                registry.referral_fee_bps.into(),
+                MAX_FEE_BASIS_POINTS.into(),
+                rounding,
+            )?;
+
+            referral_fee = referral_fee.safe_add(registry_referral_fee)?;
+        }
+
+        Ok(referral_fee)
+    }
+
+    /// Withdraw penalty held by the quote token vault. Excludes penalty charged on withdrawals with alternative quote mints.
+    pub fn get_quote_token_vault_withdraw_penalty(&self) -> Result<u64> {
+        Ok(self
+            .total_withdraw_penalty
+            .safe_sub(self.total_alternative_quote_withdraw_penalty)?)
+    }
+
+    /// Deposit fee and withdraw penalty collectable by the creator from the quote token vault. Referral fee is rounded up, so the vault can always cover the referrer claims.
+    /// Deposit fee of a failed presale is refunded to the escrows, thus only the withdraw penalty is collectable.
+    pub fn get_creator_collectable_fee(&self, presale_progress: PresaleProgress) -> Result<u64> {
+        let withdraw_penalty = self.get_quote_token_vault_withdraw_penalty()?;
+        if presale_progress == PresaleProgress::Failed {
+            return Ok(withdraw_penalty);
+        }
+
+        let registry_referred_deposit_fees = self
+            .presale_registries
+            .map(|registry| registry.total_referred_deposit_fee);
+        let referral_fee = self.get_referral_fee(&registry_referred_deposit_fees, Rounding::Up)?;
+
+        Ok(self
+            .get_quote_token_vault_collected_fee()?
+            .saturating_sub(referral_fee)
+            .safe_add(withdraw_penalty)?)
+    }
+
+    pub fn has_finalization_callback(&self) -> bool {
+        self.finalization_callback_program != Pubkey::default()
+    }
+
+    pub fn is_denylist_enabled(&self) -> bool {
+        self.is_denylist_enabled != 0
+    }
+
+    pub fn enable_denylist(&mut self) {
+        self.is_denylist_enabled = 1;
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.is_finalized == 1
+    }
+
+    pub fn set_finalized(&mut self) {
+        self.is_finalized = 1;
+    }
+
     pub fn has_creator_withdrawn(&self) -> bool {
         self.has_creator_withdrawn != 0
     }
@@ -510,12 +792,39 @@ impl Presale {
             .ok_or(PresaleError::InvalidPresaleRegistryIndex.into())
     }
 
+    /// Registry whitelist mode if overridden, else fallback to presale whitelist mode
+    pub fn get_registry_whitelist_mode(&self, index: usize) -> Result<WhitelistMode> {
+        let presale_registry = self.get_presale_registry(index)?;
+        let whitelist_mode = if presale_registry.is_whitelist_mode_overridden() {
+            presale_registry.whitelist_mode
+        } else {
+            self.whitelist_mode
+        };
+        Ok(whitelist_mode.safe_cast()?)
+    }
+
+    pub fn has_registry_with_whitelist_mode<F>(&self, predicate: F) -> Result<bool>
+    where
+        F: Fn(WhitelistMode) -> bool,
+    {
+        for idx in 0..usize::from(self.total_presale_registry_count) {
+            if predicate(self.get_registry_whitelist_mode(idx)?) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     pub fn get_presale_registry_mut(&mut self, index: usize) -> Result<&mut PresaleRegistry> {
         self.presale_registries
             .get_mut(index)
             .ok_or(PresaleError::InvalidPresaleRegistryIndex.into())
     }
 
+    pub fn is_unsold_rollover_enabled(&self) -> bool {
+        self.is_unsold_rollover_enabled == 1
+    }
+
     pub fn is_deposit_fee_collected(&self) -> bool {
         self.deposit_fee_collected == 1
     }
@@ -556,6 +865,11 @@ impl Presale {
     }
 }
 
+pub struct QuoteDepositShare {
+    pub raised_quote: u64,
+    pub collected_fee: u64,
+}
+
 pub struct EscrowRemainingQuoteResult {
     pub refund_deposit_amount: u64,
     pub refund_fee_amount: u64,
@@ -21,25 +21,94 @@ pub struct PresaleRegistry {
     pub total_deposit_fee: u64,
     /// Deposit fee bps
     pub deposit_fee_bps: u16,
-    pub padding0: [u8; 14],
-    pub padding1: [u128; 5],
+    /// Whether the registry use its own whitelist mode instead of the presale whitelist mode
+    pub override_whitelist_mode: u8,
+    /// Registry whitelist mode. Only used when override_whitelist_mode is set
+    pub whitelist_mode: u8,
+    /// Share of the deposit fee claimable by the referrer of the deposit, in bps of the deposit fee
+    pub referral_fee_bps: u16,
+    pub padding0: [u8; 10],
+    /// When deposit to the registry opens. 0 means it follows the presale start time.
+    pub deposit_start_time: u64,
+    /// When deposit to the registry closes. 0 means it follows the presale end time.
+    pub deposit_end_time: u64,
+    /// Maximum amount of quote token the registry can raise. 0 means it's only bounded by the presale maximum cap.
+    pub registry_maximum_cap: u64,
+    /// Part of total_deposit_fee paid by referred deposits
+    pub total_referred_deposit_fee: u64,
+    /// Fixed price presale only. Token price of the registry, set upon presale initialization from the fixed price presale extra args.
+    pub q_price: u128,
+    /// Part of total_deposit deposited with alternative quote mints, in accounting unit
+    pub total_alternative_quote_deposit: u64,
+    /// Part of total_deposit_fee deposited with alternative quote mints, in accounting unit
+    pub total_alternative_quote_deposit_fee: u64,
+    /// Fixed price presale only. Extra quote token the discounted deposits are worth at the registry token price
+    pub total_price_discount_bonus: u64,
+    /// Withdraw penalty charged on withdrawals from this presale registry. Never refunded, collected by the creator
+    pub total_withdraw_penalty: u64,
 }
 
 static_assertions::const_assert_eq!(PresaleRegistry::INIT_SPACE, 160);
 static_assertions::assert_eq_align!(PresaleRegistry, u128);
 
 impl PresaleRegistry {
-    pub fn init(
-        &mut self,
-        presale_supply: u64,
-        buyer_minimum_deposit_cap: u64,
-        buyer_maximum_deposit_cap: u64,
-        deposit_fee_bps: u16,
-    ) {
+    pub fn init(&mut self, args: &PresaleRegistryArgs) {
+        let &PresaleRegistryArgs {
+            presale_supply,
+            buyer_minimum_deposit_cap,
+            buyer_maximum_deposit_cap,
+            deposit_fee_bps,
+            deposit_start_time,
+            deposit_end_time,
+            override_whitelist_mode,
+            whitelist_mode,
+            registry_maximum_cap,
+            referral_fee_bps,
+            ..
+        } = args;
+
         self.presale_supply = presale_supply;
         self.buyer_minimum_deposit_cap = buyer_minimum_deposit_cap;
         self.buyer_maximum_deposit_cap = buyer_maximum_deposit_cap;
         self.deposit_fee_bps = deposit_fee_bps;
+        self.deposit_start_time = deposit_start_time;
+        self.deposit_end_time = deposit_end_time;
+        self.override_whitelist_mode = override_whitelist_mode;
+        self.whitelist_mode = whitelist_mode;
+        self.registry_maximum_cap = registry_maximum_cap;
+        self.referral_fee_bps = referral_fee_bps;
+    }
+
+    pub fn is_whitelist_mode_overridden(&self) -> bool {
+        self.override_whitelist_mode == u8::from(BoolType::True)
+    }
+
+    /// Presale progress must be checked separately. Registry deposit window only narrows down the presale window.
+    pub fn is_within_deposit_window(&self, current_timestamp: u64) -> bool {
+        current_timestamp >= self.deposit_start_time
+            && (self.deposit_end_time == 0 || current_timestamp < self.deposit_end_time)
+    }
+
+    /// Registry no longer accept deposit or withdraw, thus the amount sold is final
+    pub fn is_deposit_closed(&self, presale_end_time: u64, current_timestamp: u64) -> bool {
+        current_timestamp >= presale_end_time
+            || (self.deposit_end_time > 0 && current_timestamp >= self.deposit_end_time)
+    }
+
+    pub fn ensure_within_deposit_window(&self, current_timestamp: u64) -> Result<()> {
+        require!(
+            self.is_within_deposit_window(current_timestamp),
+            PresaleError::PresaleRegistryNotOpen
+        );
+        Ok(())
+    }
+
+    /// Remaining quote token the registry can accept. Unlimited when registry maximum cap is not set.
+    pub fn get_remaining_deposit_quota(&self) -> u64 {
+        if self.registry_maximum_cap == 0 {
+            return u64::MAX;
+        }
+        self.registry_maximum_cap.saturating_sub(self.total_deposit)
     }
 
     pub fn calculate_deposit_fee_included_amount(
@@ -58,13 +127,61 @@ impl PresaleRegistry {
         self.total_deposit = self.total_deposit.safe_add(fee_excluded_deposit_amount)?;
         self.total_deposit_fee = self.total_deposit_fee.safe_add(fee)?;
 
+        let price_discount_bonus_before = escrow.get_price_discount_bonus()?;
         escrow.deposit(fee_excluded_deposit_amount, fee)?;
+        let price_discount_bonus_after = escrow.get_price_discount_bonus()?;
+
+        self.total_price_discount_bonus = self
+            .total_price_discount_bonus
+            .safe_add(price_discount_bonus_after.safe_sub(price_discount_bonus_before)?)?;
         Ok(())
     }
 
     pub fn withdraw(&mut self, escrow: &mut Escrow, amount: u64) -> Result<()> {
+        let price_discount_bonus_before = escrow.get_price_discount_bonus()?;
         escrow.withdraw(amount)?;
+        let price_discount_bonus_after = escrow.get_price_discount_bonus()?;
+
         self.total_deposit = self.total_deposit.safe_sub(amount)?;
+        self.total_price_discount_bonus = self
+            .total_price_discount_bonus
+            .safe_sub(price_discount_bonus_before.safe_sub(price_discount_bonus_after)?)?;
+        Ok(())
+    }
+
+    /// Total deposit valued at the registry token price
+    pub fn get_price_discount_included_total_deposit(&self) -> Result<u64> {
+        Ok(self
+            .total_deposit
+            .safe_add(self.total_price_discount_bonus)?)
+    }
+
+    pub fn deposit_alternative_quote(
+        &mut self,
+        fee_excluded_deposit_amount: u64,
+        fee: u64,
+    ) -> Result<()> {
+        self.total_alternative_quote_deposit = self
+            .total_alternative_quote_deposit
+            .safe_add(fee_excluded_deposit_amount)?;
+        self.total_alternative_quote_deposit_fee =
+            self.total_alternative_quote_deposit_fee.safe_add(fee)?;
+        Ok(())
+    }
+
+    pub fn withdraw_alternative_quote(&mut self, amount: u64) -> Result<()> {
+        self.total_alternative_quote_deposit =
+            self.total_alternative_quote_deposit.safe_sub(amount)?;
+        Ok(())
+    }
+
+    pub fn charge_withdraw_penalty(&mut self, penalty: u64) -> Result<()> {
+        self.total_withdraw_penalty = self.total_withdraw_penalty.safe_add(penalty)?;
+        Ok(())
+    }
+
+    pub fn deposit_referred_fee(&mut self, fee: u64) -> Result<()> {
+        self.total_referred_deposit_fee = self.total_referred_deposit_fee.safe_add(fee)?;
         Ok(())
     }
 
@@ -0,0 +1,112 @@
+use crate::*;
+
+/// Alternative quote mint accepted by the presale. Deposits are converted into the accounting unit, which is the presale quote mint.
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct QuoteMintConfig {
+    /// Presale pubkey that the quote mint config is belong
+    pub presale: Pubkey,
+    /// Alternative quote mint
+    pub quote_mint: Pubkey,
+    /// Vault holding the alternative quote mint deposits
+    pub quote_token_vault: Pubkey,
+    /// accounting amount = quote mint amount * conversion_ratio_numerator / conversion_ratio_denominator
+    pub conversion_ratio_numerator: u64,
+    pub conversion_ratio_denominator: u64,
+    /// Total deposit of each registry in accounting unit
+    pub registry_total_deposits: [u64; MAX_PRESALE_REGISTRY_COUNT],
+    /// Total deposit fee of each registry in accounting unit
+    pub registry_total_deposit_fees: [u64; MAX_PRESALE_REGISTRY_COUNT],
+    pub quote_token_program_flag: u8,
+    pub has_creator_withdrawn: u8,
+    pub deposit_fee_collected: u8,
+    pub padding0: [u8; 5],
+    /// Total withdraw penalty retained by the vault in accounting unit. Never refunded, collected by the creator
+    pub total_withdraw_penalty: u64,
+    /// Padding for further use
+    pub padding: [u64; 6],
+}
+
+static_assertions::const_assert_eq!(QuoteMintConfig::INIT_SPACE, 256);
+static_assertions::assert_eq_align!(QuoteMintConfig, u64);
+
+impl QuoteMintConfig {
+    pub fn initialize(
+        &mut self,
+        presale: Pubkey,
+        quote_mint: Pubkey,
+        quote_token_vault: Pubkey,
+        conversion_ratio_numerator: u64,
+        conversion_ratio_denominator: u64,
+        quote_token_program: Pubkey,
+    ) {
+        self.presale = presale;
+        self.quote_mint = quote_mint;
+        self.quote_token_vault = quote_token_vault;
+        self.conversion_ratio_numerator = conversion_ratio_numerator;
+        self.conversion_ratio_denominator = conversion_ratio_denominator;
+        self.quote_token_program_flag = token_program_to_flag(quote_token_program).into();
+    }
+
+    pub fn to_accounting_amount(&self, quote_mint_amount: u64, rounding: Rounding) -> Result<u64> {
+        mul_div(
+            quote_mint_amount,
+            self.conversion_ratio_numerator,
+            self.conversion_ratio_denominator,
+            rounding,
+        )
+    }
+
+    pub fn to_quote_mint_amount(&self, accounting_amount: u64, rounding: Rounding) -> Result<u64> {
+        mul_div(
+            accounting_amount,
+            self.conversion_ratio_denominator,
+            self.conversion_ratio_numerator,
+            rounding,
+        )
+    }
+
+    pub fn deposit(
+        &mut self,
+        registry_index: u8,
+        fee_excluded_deposit_amount: u64,
+        fee: u64,
+    ) -> Result<()> {
+        let index = usize::from(registry_index);
+
+        self.registry_total_deposits[index] =
+            self.registry_total_deposits[index].safe_add(fee_excluded_deposit_amount)?;
+        self.registry_total_deposit_fees[index] =
+            self.registry_total_deposit_fees[index].safe_add(fee)?;
+
+        Ok(())
+    }
+
+    pub fn charge_withdraw_penalty(&mut self, penalty: u64) -> Result<()> {
+        self.total_withdraw_penalty = self.total_withdraw_penalty.safe_add(penalty)?;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, registry_index: u8, amount: u64) -> Result<()> {
+        let index = usize::from(registry_index);
+        self.registry_total_deposits[index] =
+            self.registry_total_deposits[index].safe_sub(amount)?;
+        Ok(())
+    }
+
+    pub fn has_creator_withdrawn(&self) -> bool {
+        self.has_creator_withdrawn == 1
+    }
+
+    pub fn update_creator_withdrawn(&mut self) {
+        self.has_creator_withdrawn = 1;
+    }
+
+    pub fn is_deposit_fee_collected(&self) -> bool {
+        self.deposit_fee_collected == 1
+    }
+
+    pub fn set_deposit_fee_collected(&mut self) {
+        self.deposit_fee_collected = 1;
+    }
+}
@@ -0,0 +1,50 @@
+use crate::*;
+
+/// Deposits referred by a referrer in a presale
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct Referral {
+    /// Presale pubkey that the referral is belong
+    pub presale: Pubkey,
+    /// Referrer who receive the referral fee
+    pub referrer: Pubkey,
+    /// Cumulative referred deposit. Withdrawal doesn't reduce it.
+    pub total_referred_deposit: u64,
+    /// Cumulative deposit fee paid by the referred deposits
+    pub total_referred_deposit_fee: u64,
+    /// Deposit fee paid by the referred deposits of each registry
+    pub registry_referred_deposit_fees: [u64; MAX_PRESALE_REGISTRY_COUNT],
+    pub is_fee_claimed: u8,
+    pub padding0: [u8; 7],
+    /// Padding for further use
+    pub padding: [u64; 8],
+}
+
+static_assertions::const_assert_eq!(Referral::INIT_SPACE, 192);
+static_assertions::assert_eq_align!(Referral, u64);
+
+impl Referral {
+    pub fn initialize(&mut self, presale: Pubkey, referrer: Pubkey) {
+        self.presale = presale;
+        self.referrer = referrer;
+    }
+
+    pub fn deposit(&mut self, registry_index: u8, deposit_amount: u64, fee: u64) -> Result<()> {
+        let index = usize::from(registry_index);
+
+        self.total_referred_deposit = self.total_referred_deposit.safe_add(deposit_amount)?;
+        self.total_referred_deposit_fee = self.total_referred_deposit_fee.safe_add(fee)?;
+        self.registry_referred_deposit_fees[index] =
+            self.registry_referred_deposit_fees[index].safe_add(fee)?;
+
+        Ok(())
+    }
+
+    pub fn is_fee_claimed(&self) -> bool {
+        self.is_fee_claimed == 1
+    }
+
+    pub fn set_fee_claimed(&mut self) {
+        self.is_fee_claimed = 1;
+    }
+}
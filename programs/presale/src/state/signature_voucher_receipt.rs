@@ -0,0 +1,26 @@
+use crate::*;
+
+/// Record of a consumed signature voucher, so that the voucher can't be replayed once the escrow is closed
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct SignatureVoucherReceipt {
+    /// Presale pubkey that the voucher is belong
+    pub presale: Pubkey,
+    /// Owner of the escrow created with the voucher
+    pub owner: Pubkey,
+    /// Nonce of the voucher
+    pub nonce: u64,
+    /// Padding for further use
+    pub padding: [u64; 3],
+}
+
+static_assertions::const_assert_eq!(SignatureVoucherReceipt::INIT_SPACE, 96);
+static_assertions::assert_eq_align!(SignatureVoucherReceipt, u64);
+
+impl SignatureVoucherReceipt {
+    pub fn initialize(&mut self, presale: Pubkey, owner: Pubkey, nonce: u64) {
+        self.presale = presale;
+        self.owner = owner;
+        self.nonce = nonce;
+    }
+}
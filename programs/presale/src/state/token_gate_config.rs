@@ -0,0 +1,64 @@
+use crate::*;
+
+/// Holding requirement of a registry permissioned with token gate
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct TokenGateConfig {
+    /// Presale pubkey that the config is belong
+    pub presale: Pubkey,
+    /// Mint that escrow owner must hold
+    pub gating_mint: Pubkey,
+    /// Minimum amount of the gating mint that escrow owner must hold
+    pub minimum_holding_amount: u64,
+    /// Registry that the config is belong
+    pub registry_index: u8,
+    pub padding0: [u8; 7],
+    /// Deposit cap granted per held gating token, in Q64.64. 0 means the registry buyer maximum deposit cap is granted.
+    pub q_deposit_cap_per_holding: u128,
+    /// Padding for further use
+    pub padding: [u128; 4],
+}
+
+static_assertions::const_assert_eq!(TokenGateConfig::INIT_SPACE, 160);
+static_assertions::assert_eq_align!(TokenGateConfig, u128);
+
+impl TokenGateConfig {
+    pub fn initialize(
+        &mut self,
+        presale: Pubkey,
+        gating_mint: Pubkey,
+        registry_index: u8,
+        minimum_holding_amount: u64,
+        q_deposit_cap_per_holding: u128,
+    ) {
+        self.presale = presale;
+        self.gating_mint = gating_mint;
+        self.registry_index = registry_index;
+        self.minimum_holding_amount = minimum_holding_amount;
+        self.q_deposit_cap_per_holding = q_deposit_cap_per_holding;
+    }
+
+    /// Deposit cap granted to the holding amount, bounded by the registry buyer maximum deposit cap
+    pub fn get_deposit_cap(
+        &self,
+        holding_amount: u64,
+        presale_registry: &PresaleRegistry,
+    ) -> Result<u64> {
+        require!(
+            holding_amount >= self.minimum_holding_amount,
+            PresaleError::InsufficientGatingTokenHolding
+        );
+
+        if self.q_deposit_cap_per_holding == 0 {
+            return Ok(presale_registry.buyer_maximum_deposit_cap);
+        }
+
+        let deposit_cap = u128::from(holding_amount)
+            .safe_mul(self.q_deposit_cap_per_holding)?
+            .safe_shr(SCALE_OFFSET)?;
+
+        let deposit_cap = deposit_cap.min(presale_registry.buyer_maximum_deposit_cap.into());
+
+        Ok(deposit_cap.safe_cast()?)
+    }
+}
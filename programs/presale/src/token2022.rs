@@ -246,7 +246,7 @@ fn is_transfer_memo_required(user_token_ai: &AccountInfo<'_>) -> Result<bool> {
     }
 }
 
-fn get_transfer_hook_program_id<'info>(
+pub fn get_transfer_hook_program_id<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
 ) -> Result<Option<Pubkey>> {
     let token_mint_info = token_mint.to_account_info();
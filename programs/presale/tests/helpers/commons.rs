@@ -412,6 +412,22 @@ fn load_programs(svm: &mut LiteSVM) {
     let program_bytes = std::fs::read(program_path).expect("Failed to read program file");
     svm.add_program(presale::ID, &program_bytes);
 
+    let mock_finalization_callback_program_path = format!(
+        "{}/../../target/deploy/mock_finalization_callback.so",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    println!(
+        "Loading mock finalization callback program from: {}",
+        mock_finalization_callback_program_path
+    );
+    let mock_finalization_callback_program_bytes =
+        std::fs::read(mock_finalization_callback_program_path)
+            .expect("Failed to read mock finalization callback program file");
+    svm.add_program(
+        mock_finalization_callback::ID,
+        &mock_finalization_callback_program_bytes,
+    );
+
     let other_program_path = format!("{}/tests/fixtures", env!("CARGO_MANIFEST_DIR"));
     println!("Loading other programs from: {}", other_program_path);
 
@@ -68,3 +68,27 @@ pub use process_creator_collect_fee::*;
 
 mod process_close_merkle_root_config;
 pub use process_close_merkle_root_config::*;
+
+mod process_revoke_merkle_root_config;
+pub use process_revoke_merkle_root_config::*;
+
+mod process_finalize_presale;
+pub use process_finalize_presale::*;
+
+mod process_native_sol;
+pub use process_native_sol::*;
+
+mod process_quote_mint_config;
+pub use process_quote_mint_config::*;
+
+mod process_referral;
+pub use process_referral::*;
+
+mod process_token_gate;
+pub use process_token_gate::*;
+
+mod process_denylist;
+pub use process_denylist::*;
+
+mod process_update_escrow_deposit_cap;
+pub use process_update_escrow_deposit_cap::*;
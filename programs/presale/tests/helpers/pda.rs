@@ -71,12 +71,18 @@ pub fn derive_escrow(
     .0
 }
 
-pub fn derive_operator(creator: &Pubkey, operator: &Pubkey, program_id: &Pubkey) -> Pubkey {
+pub fn derive_operator(
+    creator: &Pubkey,
+    operator: &Pubkey,
+    presale: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
     Pubkey::find_program_address(
         &[
             presale::seeds::OPERATOR_PREFIX.as_ref(),
             creator.as_ref(),
             operator.as_ref(),
+            presale.as_ref(),
         ],
         program_id,
     )
@@ -105,3 +111,121 @@ pub fn derive_permissioned_server_metadata(presale: &Pubkey, program_id: &Pubkey
     )
     .0
 }
+
+pub fn derive_native_unwrap_token(owner: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::NATIVE_UNWRAP_PREFIX.as_ref(),
+            owner.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_quote_mint_config(
+    presale: &Pubkey,
+    quote_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::QUOTE_MINT_CONFIG_PREFIX.as_ref(),
+            presale.as_ref(),
+            quote_mint.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_quote_mint_config_vault(quote_mint_config: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::QUOTE_VAULT_PREFIX.as_ref(),
+            quote_mint_config.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_referral(presale: &Pubkey, referrer: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::REFERRAL_PREFIX.as_ref(),
+            presale.as_ref(),
+            referrer.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_token_gate_config(
+    presale: &Pubkey,
+    registry_index: u8,
+    program_id: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::TOKEN_GATE_CONFIG_PREFIX.as_ref(),
+            presale.as_ref(),
+            registry_index.to_le_bytes().as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_gating_token_vault(escrow: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::GATING_TOKEN_VAULT_PREFIX.as_ref(),
+            escrow.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_denylist_entry(presale: &Pubkey, wallet: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::DENYLIST_PREFIX.as_ref(),
+            presale.as_ref(),
+            wallet.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_finalization_callback_authority(presale: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::FINALIZATION_CALLBACK_AUTHORITY_PREFIX.as_ref(),
+            presale.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+pub fn derive_signature_voucher_receipt(
+    presale: &Pubkey,
+    owner: &Pubkey,
+    nonce: u64,
+    program_id: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            presale::seeds::SIGNATURE_VOUCHER_RECEIPT_PREFIX.as_ref(),
+            presale.as_ref(),
+            owner.as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
@@ -23,11 +23,12 @@ pub fn build_merkle_tree(
 ) -> ConfigMerkleTree {
     let tree_nodes = whitelist_wallets
         .into_iter()
-        .map(|wallet| TreeNode {
-            escrow_owner: wallet.address,
-            registry_index: wallet.registry_index,
-            proof: None,
-            deposit_cap: wallet.max_deposit_cap,
+        .map(|wallet| {
+            TreeNode::new(
+                wallet.address,
+                wallet.registry_index,
+                wallet.max_deposit_cap,
+            )
         })
         .collect::<Vec<_>>();
 
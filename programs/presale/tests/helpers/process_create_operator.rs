@@ -4,7 +4,8 @@ use anchor_client::solana_sdk::instruction::Instruction;
 use anchor_client::solana_sdk::signer::Signer;
 use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Keypair};
 use anchor_lang::{InstructionData, ToAccountMetas};
-use litesvm::LiteSVM;
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::{CreateOperatorParams, UpdateOperatorParams};
 
 use crate::helpers::{derive_event_authority, derive_operator, process_transaction};
 
@@ -12,16 +13,21 @@ use crate::helpers::{derive_event_authority, derive_operator, process_transactio
 pub struct HandleCreateOperatorArgs {
     pub owner: Rc<Keypair>,
     pub operator: Pubkey,
+    pub params: CreateOperatorParams,
 }
 
 pub fn create_operator_ix(args: HandleCreateOperatorArgs) -> Instruction {
-    let HandleCreateOperatorArgs { owner, operator } = args;
+    let HandleCreateOperatorArgs {
+        owner,
+        operator,
+        params,
+    } = args;
 
     let owner_pubkey = owner.pubkey();
 
-    let ix_data = presale::instruction::CreateOperator {}.data();
+    let ix_data = presale::instruction::CreateOperator { params }.data();
 
-    let operator_pda = derive_operator(&owner_pubkey, &operator, &presale::ID);
+    let operator_pda = derive_operator(&owner_pubkey, &operator, &params.presale, &presale::ID);
 
     let accounts = presale::accounts::CreateOperatorCtx {
         operator: operator_pda,
@@ -40,9 +46,83 @@ pub fn create_operator_ix(args: HandleCreateOperatorArgs) -> Instruction {
     }
 }
 
+/// Operator scoped to the presale when it exists, otherwise the unscoped operator
+pub fn find_operator(
+    lite_svm: &LiteSVM,
+    creator: &Pubkey,
+    operator: &Pubkey,
+    presale: &Pubkey,
+) -> Pubkey {
+    let scoped_operator = derive_operator(creator, operator, presale, &presale::ID);
+
+    if lite_svm.get_account(&scoped_operator).is_some() {
+        scoped_operator
+    } else {
+        derive_operator(creator, operator, &Pubkey::default(), &presale::ID)
+    }
+}
+
 pub fn handle_create_operator(lite_svm: &mut LiteSVM, args: HandleCreateOperatorArgs) {
     let instruction = create_operator_ix(args.clone());
 
     let HandleCreateOperatorArgs { owner, .. } = args;
     process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
 }
+
+pub fn handle_create_operator_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreateOperatorArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_operator_ix(args.clone());
+
+    let HandleCreateOperatorArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleUpdateOperatorArgs {
+    pub owner: Rc<Keypair>,
+    pub operator_pda: Pubkey,
+    pub params: UpdateOperatorParams,
+}
+
+pub fn create_update_operator_ix(args: HandleUpdateOperatorArgs) -> Instruction {
+    let HandleUpdateOperatorArgs {
+        owner,
+        operator_pda,
+        params,
+    } = args;
+
+    let ix_data = presale::instruction::UpdateOperator { params }.data();
+
+    let accounts = presale::accounts::UpdateOperatorCtx {
+        operator: operator_pda,
+        creator: owner.pubkey(),
+        program: presale::ID,
+        event_authority: derive_event_authority(&presale::ID),
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }
+}
+
+pub fn handle_update_operator(lite_svm: &mut LiteSVM, args: HandleUpdateOperatorArgs) {
+    let instruction = create_update_operator_ix(args.clone());
+
+    let HandleUpdateOperatorArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_update_operator_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleUpdateOperatorArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_update_operator_ix(args.clone());
+
+    let HandleUpdateOperatorArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
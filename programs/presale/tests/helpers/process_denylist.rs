@@ -0,0 +1,104 @@
+use std::rc::Rc;
+
+use crate::helpers::{derive_denylist_entry, derive_event_authority, process_transaction};
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+
+#[derive(Clone)]
+pub struct HandleDenylistArgs {
+    pub presale: Pubkey,
+    pub wallet: Pubkey,
+    pub owner: Rc<Keypair>,
+    /// Escrow PDAs of the wallet in every permissionless registry
+    pub escrows: Vec<Pubkey>,
+}
+
+pub fn create_add_to_denylist_ix(args: HandleDenylistArgs) -> Instruction {
+    let HandleDenylistArgs {
+        presale,
+        wallet,
+        owner,
+        escrows,
+    } = args;
+
+    let mut accounts = presale::accounts::AddToDenylistCtx {
+        presale,
+        denylist_entry: derive_denylist_entry(&presale, &wallet, &presale::ID),
+        wallet,
+        owner: owner.pubkey(),
+        system_program: anchor_lang::solana_program::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(
+        escrows
+            .into_iter()
+            .map(|escrow| AccountMeta::new(escrow, false)),
+    );
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: presale::instruction::AddToDenylist {}.data(),
+    }
+}
+
+pub fn handle_add_to_denylist(lite_svm: &mut LiteSVM, args: HandleDenylistArgs) {
+    let instruction = create_add_to_denylist_ix(args.clone());
+    let HandleDenylistArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_add_to_denylist_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleDenylistArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_add_to_denylist_ix(args.clone());
+    let HandleDenylistArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+pub fn create_remove_from_denylist_ix(args: HandleDenylistArgs) -> Instruction {
+    let HandleDenylistArgs {
+        presale,
+        wallet,
+        owner,
+        escrows,
+    } = args;
+
+    let mut accounts = presale::accounts::RemoveFromDenylistCtx {
+        presale,
+        denylist_entry: derive_denylist_entry(&presale, &wallet, &presale::ID),
+        wallet,
+        owner: owner.pubkey(),
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(
+        escrows
+            .into_iter()
+            .map(|escrow| AccountMeta::new(escrow, false)),
+    );
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: presale::instruction::RemoveFromDenylist {}.data(),
+    }
+}
+
+pub fn handle_remove_from_denylist(lite_svm: &mut LiteSVM, args: HandleDenylistArgs) {
+    let instruction = create_remove_from_denylist_ix(args.clone());
+    let HandleDenylistArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
@@ -31,6 +31,14 @@ pub struct HandleEscrowDepositArgs {
 pub fn create_deposit_ix(
     lite_svm: &mut LiteSVM,
     args: HandleEscrowDepositArgs,
+) -> Vec<Instruction> {
+    create_deposit_ix_with_referral(lite_svm, args, None)
+}
+
+pub fn create_deposit_ix_with_referral(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositArgs,
+    referral: Option<Pubkey>,
 ) -> Vec<Instruction> {
     let HandleEscrowDepositArgs {
         owner,
@@ -120,6 +128,7 @@ pub fn create_deposit_ix(
         quote_token_vault: presale_state.quote_token_vault,
         payer_quote_token,
         escrow,
+        referral,
         token_program: quote_token_program,
         program: presale::ID,
         presale,
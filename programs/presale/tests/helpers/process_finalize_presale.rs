@@ -0,0 +1,106 @@
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_lang::{prelude::AccountMeta, *};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::Presale;
+use std::rc::Rc;
+
+use crate::helpers::{
+    derive_event_authority, derive_finalization_callback_authority, process_transaction, LiteSVMExt,
+};
+
+pub fn derive_mock_finalization_callback_record(presale: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[mock_finalization_callback::RECORD_PREFIX, presale.as_ref()],
+        &mock_finalization_callback::ID,
+    )
+    .0
+}
+
+pub fn handle_initialize_mock_finalization_callback_record(
+    lite_svm: &mut LiteSVM,
+    presale: Pubkey,
+    payer: Rc<Keypair>,
+) -> Pubkey {
+    let record = derive_mock_finalization_callback_record(&presale);
+
+    let accounts = mock_finalization_callback::accounts::InitializeRecordCtx {
+        record,
+        presale,
+        payer: payer.pubkey(),
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(None);
+
+    let ix = Instruction {
+        program_id: mock_finalization_callback::ID,
+        accounts,
+        data: mock_finalization_callback::instruction::InitializeRecord {}.data(),
+    };
+
+    process_transaction(lite_svm, &[ix], Some(&payer.pubkey()), &[&payer]).unwrap();
+
+    record
+}
+
+#[derive(Clone)]
+pub struct HandleFinalizePresaleArgs {
+    pub presale: Pubkey,
+    pub payer: Rc<Keypair>,
+}
+
+pub fn create_finalize_presale_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleFinalizePresaleArgs,
+) -> Vec<Instruction> {
+    let HandleFinalizePresaleArgs { presale, .. } = args;
+
+    let presale_state = lite_svm
+        .get_deserialized_zc_account::<Presale>(&presale)
+        .unwrap();
+
+    let ix_data = presale::instruction::FinalizePresale {}.data();
+
+    let mut accounts = presale::accounts::FinalizePresaleCtx {
+        presale,
+        finalization_callback_authority: derive_finalization_callback_authority(
+            &presale,
+            &presale::ID,
+        ),
+        base_token_vault: presale_state.base_token_vault,
+        quote_token_vault: presale_state.quote_token_vault,
+        finalization_callback_program: presale_state.finalization_callback_program,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.push(AccountMeta::new(
+        derive_mock_finalization_callback_record(&presale),
+        false,
+    ));
+
+    let ix = Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    };
+
+    vec![ix]
+}
+
+pub fn handle_finalize_presale(lite_svm: &mut LiteSVM, args: HandleFinalizePresaleArgs) {
+    let instructions = create_finalize_presale_ix(lite_svm, args.clone());
+    let HandleFinalizePresaleArgs { payer, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&payer.pubkey()), &[&payer]).unwrap();
+}
+
+pub fn handle_finalize_presale_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleFinalizePresaleArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_finalize_presale_ix(lite_svm, args.clone());
+    let HandleFinalizePresaleArgs { payer, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&payer.pubkey()), &[&payer]).unwrap_err()
+}
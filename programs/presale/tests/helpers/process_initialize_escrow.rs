@@ -1,14 +1,23 @@
+use anchor_client::solana_sdk::ed25519_instruction::new_ed25519_instruction_with_signature;
 use anchor_client::solana_sdk::{
-    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
 };
 use anchor_lang::*;
 use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use presale::{
-    CreatePermissionedEscrowWithCreatorParams, CreatePermissionedEscrowWithMerkleProofParams,
+    get_signature_voucher_message, CreatePermissionedEscrowWithCreatorParams,
+    CreatePermissionedEscrowWithMerkleProofParams, CreatePermissionedEscrowWithSignatureParams,
+    CreatePermissionlessEscrowParams, EscrowTermsOverride,
 };
 use std::rc::Rc;
 
-use crate::helpers::{derive_escrow, derive_event_authority, derive_operator, process_transaction};
+use crate::helpers::{
+    derive_denylist_entry, derive_escrow, derive_event_authority, derive_operator,
+    derive_signature_voucher_receipt, find_operator, process_transaction,
+};
 
 #[derive(Clone)]
 pub struct HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -23,6 +32,14 @@ pub struct HandleCreatePermissionedEscrowWithMerkleProofArgs {
 pub fn create_permissioned_escrow_with_merkle_proof_ix(
     lite_svm: &mut LiteSVM,
     args: HandleCreatePermissionedEscrowWithMerkleProofArgs,
+) -> Option<Instruction> {
+    create_permissioned_escrow_with_merkle_proof_and_terms_override_ix(lite_svm, args, None)
+}
+
+pub fn create_permissioned_escrow_with_merkle_proof_and_terms_override_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatePermissionedEscrowWithMerkleProofArgs,
+    terms_override: Option<EscrowTermsOverride>,
 ) -> Option<Instruction> {
     let HandleCreatePermissionedEscrowWithMerkleProofArgs {
         presale,
@@ -47,6 +64,7 @@ pub fn create_permissioned_escrow_with_merkle_proof_ix(
             registry_index,
             proof,
             deposit_cap: max_deposit_cap,
+            terms_override,
             ..Default::default()
         },
     }
@@ -83,6 +101,21 @@ pub fn handle_create_permissioned_escrow_with_merkle_proof(
     process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
 }
 
+pub fn handle_create_permissioned_escrow_with_merkle_proof_and_terms_override(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatePermissionedEscrowWithMerkleProofArgs,
+    terms_override: Option<EscrowTermsOverride>,
+) {
+    let instruction = create_permissioned_escrow_with_merkle_proof_and_terms_override_ix(
+        lite_svm,
+        args.clone(),
+        terms_override,
+    )
+    .unwrap();
+    let HandleCreatePermissionedEscrowWithMerkleProofArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
 pub fn handle_create_permissioned_escrow_with_merkle_proof_err(
     lite_svm: &mut LiteSVM,
     args: HandleCreatePermissionedEscrowWithMerkleProofArgs,
@@ -119,9 +152,15 @@ pub fn create_permissionless_escrow_ix(
         return None; // Escrow account already exists
     }
 
-    let ix_data = presale::instruction::CreatePermissionlessEscrow {}.data();
+    let ix_data = presale::instruction::CreatePermissionlessEscrow {
+        params: CreatePermissionlessEscrowParams {
+            registry_index,
+            ..Default::default()
+        },
+    }
+    .data();
 
-    let accounts = presale::accounts::CreatePermissionlessEscrowCtx {
+    let mut accounts = presale::accounts::CreatePermissionlessEscrowCtx {
         escrow,
         owner: owner_pubkey,
         system_program: anchor_lang::solana_program::system_program::ID,
@@ -132,6 +171,12 @@ pub fn create_permissionless_escrow_ix(
     }
     .to_account_metas(None);
 
+    // Required once the presale has denylisted wallets
+    accounts.push(AccountMeta::new_readonly(
+        derive_denylist_entry(&presale, &owner_pubkey, &presale::ID),
+        false,
+    ));
+
     let instruction = Instruction {
         program_id: presale::ID,
         accounts,
@@ -173,7 +218,7 @@ pub fn create_permissioned_escrow_with_operator_ix(
         return None; // Escrow account already exists
     }
 
-    let operator_pda = derive_operator(&vault_owner, &operator.pubkey(), &presale::ID);
+    let operator_pda = find_operator(lite_svm, &vault_owner, &operator.pubkey(), &presale);
     let ix_data = presale::instruction::CreatePermissionedEscrowWithCreator {
         params: CreatePermissionedEscrowWithCreatorParams {
             registry_index,
@@ -263,3 +308,109 @@ pub fn handle_create_permissionless_escrow_err(
     let owner_pubkey = owner.pubkey();
     process_transaction(lite_svm, &[instruction], Some(&owner_pubkey), &[&owner]).unwrap_err()
 }
+
+#[derive(Clone)]
+pub struct HandleCreatePermissionedEscrowWithSignatureArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub vault_owner: Pubkey,
+    pub operator: Rc<Keypair>,
+    pub registry_index: u8,
+    pub max_deposit_cap: u64,
+    pub expiry: u64,
+    pub nonce: u64,
+}
+
+/// Ed25519 signature verification instruction followed by the escrow creation instruction
+pub fn create_permissioned_escrow_with_signature_ix(
+    args: HandleCreatePermissionedEscrowWithSignatureArgs,
+) -> Vec<Instruction> {
+    let HandleCreatePermissionedEscrowWithSignatureArgs {
+        presale,
+        owner,
+        vault_owner,
+        operator,
+        registry_index,
+        max_deposit_cap,
+        expiry,
+        nonce,
+    } = args;
+
+    let owner_pubkey = owner.pubkey();
+    let escrow = derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID);
+
+    let message = get_signature_voucher_message(
+        &presale,
+        &owner_pubkey,
+        registry_index,
+        max_deposit_cap,
+        expiry,
+        nonce,
+    );
+    let signature: [u8; 64] = operator.sign_message(&message).into();
+    let ed25519_ix =
+        new_ed25519_instruction_with_signature(&message, &signature, &operator.pubkey().to_bytes());
+
+    let operator_pda = derive_operator(
+        &vault_owner,
+        &operator.pubkey(),
+        &Pubkey::default(),
+        &presale::ID,
+    );
+    let ix_data = presale::instruction::CreatePermissionedEscrowWithSignature {
+        params: CreatePermissionedEscrowWithSignatureParams {
+            registry_index,
+            deposit_cap: max_deposit_cap,
+            expiry,
+            nonce,
+            ..Default::default()
+        },
+    }
+    .data();
+
+    let accounts = presale::accounts::CreatePermissionedEscrowWithSignatureCtx {
+        escrow,
+        owner: owner_pubkey,
+        system_program: anchor_lang::solana_program::system_program::ID,
+        program: presale::ID,
+        presale,
+        payer: owner_pubkey,
+        signature_voucher_receipt: derive_signature_voucher_receipt(
+            &presale,
+            &owner_pubkey,
+            nonce,
+            &presale::ID,
+        ),
+        operator: operator_pda,
+        instructions_sysvar: anchor_lang::solana_program::sysvar::instructions::ID,
+        event_authority: derive_event_authority(&presale::ID),
+    }
+    .to_account_metas(None);
+
+    vec![
+        ed25519_ix,
+        Instruction {
+            program_id: presale::ID,
+            accounts,
+            data: ix_data,
+        },
+    ]
+}
+
+pub fn handle_create_permissioned_escrow_with_signature(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatePermissionedEscrowWithSignatureArgs,
+) {
+    let instructions = create_permissioned_escrow_with_signature_ix(args.clone());
+    let HandleCreatePermissionedEscrowWithSignatureArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_create_permissioned_escrow_with_signature_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatePermissionedEscrowWithSignatureArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_permissioned_escrow_with_signature_ix(args.clone());
+    let HandleCreatePermissionedEscrowWithSignatureArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
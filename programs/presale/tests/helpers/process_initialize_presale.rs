@@ -28,6 +28,8 @@ pub const PRESALE_MULTIPLE_REGISTRIES_DEFAULT_BASIS_POINTS: [u16; MAX_PRESALE_RE
 
 pub const DEFAULT_DEPOSIT_BPS: u16 = 500;
 
+pub const DEFAULT_REFERRAL_FEE_BPS: u16 = 2_000;
+
 pub const DEFAULT_PRICE: f64 = 0.01;
 
 fn calculate_amount_by_bps(total_amount: u128, bps: u16) -> u128 {
@@ -113,6 +115,7 @@ pub fn update_presale_registries_with_default_deposit_fee(
             continue;
         }
         presale_registry.deposit_fee_bps = DEFAULT_DEPOSIT_BPS;
+        presale_registry.referral_fee_bps = DEFAULT_REFERRAL_FEE_BPS;
     }
 }
 
@@ -875,6 +878,41 @@ pub fn handle_create_predefined_permissionless_fixed_price_presale(
     }
 }
 
+pub fn handle_create_predefined_permissionless_fixed_price_presale_with_finalization_callback(
+    lite_svm: &mut LiteSVM,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    user: Rc<Keypair>,
+    finalization_callback_program: Pubkey,
+) -> HandleCreatePredefinedPresaleResponse {
+    let mut wrapper = create_default_fixed_price_presale_args_wrapper(
+        base_mint,
+        quote_mint,
+        lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user.pubkey(),
+    );
+
+    wrapper
+        .presale_params_wrapper
+        .args
+        .params
+        .finalization_callback_program = finalization_callback_program;
+
+    let instructions = wrapper.to_instructions();
+
+    process_transaction(lite_svm, &instructions, Some(&user.pubkey()), &[&user]).unwrap();
+
+    let user_pubkey = user.pubkey();
+
+    HandleCreatePredefinedPresaleResponse {
+        base_mint,
+        quote_mint,
+        presale_pubkey: derive_presale(&base_mint, &quote_mint, &user_pubkey, &presale::ID),
+    }
+}
+
 pub fn handle_create_predefined_permissionless_fixed_price_presale_with_immediate_release(
     lite_svm: &mut LiteSVM,
     base_mint: Pubkey,
@@ -1002,6 +1040,56 @@ pub fn handle_create_predefined_permissioned_with_authority_fixed_price_presale(
     }
 }
 
+pub fn handle_create_predefined_permissioned_with_signature_fixed_price_presale(
+    lite_svm: &mut LiteSVM,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    user: Rc<Keypair>,
+) -> HandleCreatePredefinedPresaleResponse {
+    let instructions = create_predefined_fixed_price_presale_ix(
+        lite_svm,
+        base_mint,
+        quote_mint,
+        Rc::clone(&user),
+        WhitelistMode::PermissionWithSignature,
+    );
+
+    process_transaction(lite_svm, &instructions, Some(&user.pubkey()), &[&user]).unwrap();
+
+    let user_pubkey = user.pubkey();
+
+    HandleCreatePredefinedPresaleResponse {
+        base_mint,
+        quote_mint,
+        presale_pubkey: derive_presale(&base_mint, &quote_mint, &user_pubkey, &presale::ID),
+    }
+}
+
+pub fn handle_create_predefined_permissioned_with_token_gate_fixed_price_presale(
+    lite_svm: &mut LiteSVM,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    user: Rc<Keypair>,
+) -> HandleCreatePredefinedPresaleResponse {
+    let instructions = create_predefined_fixed_price_presale_ix(
+        lite_svm,
+        base_mint,
+        quote_mint,
+        Rc::clone(&user),
+        WhitelistMode::PermissionWithTokenGate,
+    );
+
+    process_transaction(lite_svm, &instructions, Some(&user.pubkey()), &[&user]).unwrap();
+
+    let user_pubkey = user.pubkey();
+
+    HandleCreatePredefinedPresaleResponse {
+        base_mint,
+        quote_mint,
+        presale_pubkey: derive_presale(&base_mint, &quote_mint, &user_pubkey, &presale::ID),
+    }
+}
+
 pub fn handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
     lite_svm: &mut LiteSVM,
     base_mint: Pubkey,
@@ -0,0 +1,288 @@
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_lang::*;
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::Presale;
+use std::rc::Rc;
+
+use crate::helpers::{
+    create_permissionless_escrow_ix, derive_escrow, derive_event_authority,
+    derive_native_unwrap_token, process_transaction, HandleCreatePermissionlessEscrowArgs,
+    LiteSVMExt,
+};
+
+#[derive(Clone)]
+pub struct HandleEscrowDepositNativeArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub max_amount: u64,
+    pub registry_index: u8,
+}
+
+pub fn create_deposit_native_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositNativeArgs,
+) -> Vec<Instruction> {
+    create_deposit_native_ix_with_referral(lite_svm, args, None)
+}
+
+pub fn create_deposit_native_ix_with_referral(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositNativeArgs,
+    referral: Option<Pubkey>,
+) -> Vec<Instruction> {
+    let HandleEscrowDepositNativeArgs {
+        presale,
+        owner,
+        max_amount,
+        registry_index,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let mut instructions = vec![];
+
+    let create_permissionless_escrow_ix = create_permissionless_escrow_ix(
+        lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale,
+            owner: Rc::clone(&owner),
+            registry_index,
+        },
+    );
+
+    if let Some(ix) = create_permissionless_escrow_ix {
+        instructions.push(ix);
+    }
+
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+
+    let quote_token_program = lite_svm
+        .get_account(&presale_state.quote_mint)
+        .unwrap()
+        .owner;
+
+    let ix_data = presale::instruction::DepositNative { max_amount }.data();
+
+    let accounts = presale::accounts::DepositNativeCtx {
+        presale,
+        quote_token_vault: presale_state.quote_token_vault,
+        quote_mint: presale_state.quote_mint,
+        escrow: derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID),
+        referral,
+        payer: owner_pubkey,
+        token_program: quote_token_program,
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    instructions.push(Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    });
+
+    instructions
+}
+
+pub fn handle_escrow_deposit_native(lite_svm: &mut LiteSVM, args: HandleEscrowDepositNativeArgs) {
+    let instructions = create_deposit_native_ix(lite_svm, args.clone());
+    let HandleEscrowDepositNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_escrow_deposit_native_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositNativeArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_deposit_native_ix(lite_svm, args.clone());
+    let HandleEscrowDepositNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleEscrowWithdrawNativeArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub amount: u64,
+    pub registry_index: u8,
+}
+
+pub fn create_withdraw_native_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowWithdrawNativeArgs,
+) -> Vec<Instruction> {
+    let HandleEscrowWithdrawNativeArgs {
+        presale,
+        owner,
+        amount,
+        registry_index,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+
+    let quote_token_program = lite_svm
+        .get_account(&presale_state.quote_mint)
+        .unwrap()
+        .owner;
+
+    let ix_data = presale::instruction::WithdrawNative { amount }.data();
+
+    let accounts = presale::accounts::WithdrawNativeCtx {
+        presale,
+        quote_token_vault: presale_state.quote_token_vault,
+        quote_mint: presale_state.quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        escrow: derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID),
+        native_unwrap_token: derive_native_unwrap_token(&owner_pubkey, &presale::ID),
+        owner: owner_pubkey,
+        token_program: quote_token_program,
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    vec![Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }]
+}
+
+pub fn handle_escrow_withdraw_native(lite_svm: &mut LiteSVM, args: HandleEscrowWithdrawNativeArgs) {
+    let instructions = create_withdraw_native_ix(lite_svm, args.clone());
+    let HandleEscrowWithdrawNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_escrow_withdraw_native_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowWithdrawNativeArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_withdraw_native_ix(lite_svm, args.clone());
+    let HandleEscrowWithdrawNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleWithdrawRemainingQuoteNativeArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub registry_index: u8,
+}
+
+pub fn create_withdraw_remaining_quote_native_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleWithdrawRemainingQuoteNativeArgs,
+) -> Vec<Instruction> {
+    let HandleWithdrawRemainingQuoteNativeArgs {
+        presale,
+        owner,
+        registry_index,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+
+    let quote_token_program = lite_svm
+        .get_account(&presale_state.quote_mint)
+        .unwrap()
+        .owner;
+
+    let ix_data = presale::instruction::WithdrawRemainingQuoteNative {}.data();
+
+    let accounts = presale::accounts::WithdrawRemainingQuoteNativeCtx {
+        presale,
+        quote_token_vault: presale_state.quote_token_vault,
+        quote_mint: presale_state.quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        escrow: derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID),
+        native_unwrap_token: derive_native_unwrap_token(&owner_pubkey, &presale::ID),
+        owner: owner_pubkey,
+        token_program: quote_token_program,
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    vec![Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }]
+}
+
+pub fn handle_withdraw_remaining_quote_native(
+    lite_svm: &mut LiteSVM,
+    args: HandleWithdrawRemainingQuoteNativeArgs,
+) {
+    let instructions = create_withdraw_remaining_quote_native_ix(lite_svm, args.clone());
+    let HandleWithdrawRemainingQuoteNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+#[derive(Clone)]
+pub struct HandleCreatorWithdrawNativeArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+}
+
+pub fn create_creator_withdraw_native_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatorWithdrawNativeArgs,
+) -> Vec<Instruction> {
+    let HandleCreatorWithdrawNativeArgs { presale, owner } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+
+    let quote_token_program = lite_svm
+        .get_account(&presale_state.quote_mint)
+        .unwrap()
+        .owner;
+
+    let ix_data = presale::instruction::CreatorWithdrawNative {}.data();
+
+    let accounts = presale::accounts::CreatorWithdrawNativeCtx {
+        presale,
+        quote_token_vault: presale_state.quote_token_vault,
+        quote_mint: presale_state.quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        native_unwrap_token: derive_native_unwrap_token(&owner_pubkey, &presale::ID),
+        owner: owner_pubkey,
+        token_program: quote_token_program,
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    vec![Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }]
+}
+
+pub fn handle_creator_withdraw_native(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatorWithdrawNativeArgs,
+) {
+    let instructions = create_creator_withdraw_native_ix(lite_svm, args.clone());
+    let HandleCreatorWithdrawNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_creator_withdraw_native_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatorWithdrawNativeArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_creator_withdraw_native_ix(lite_svm, args.clone());
+    let HandleCreatorWithdrawNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
@@ -0,0 +1,502 @@
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use anchor_lang::*;
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::{
+    AccountsType, CreateQuoteMintConfigParams, RemainingAccountsInfo, RemainingAccountsSlice,
+};
+use std::rc::Rc;
+
+use crate::helpers::{
+    create_permissionless_escrow_ix, derive_escrow, derive_event_authority,
+    derive_quote_mint_config, derive_quote_mint_config_vault,
+    get_extra_account_metas_for_transfer_hook, process_transaction,
+    HandleCreatePermissionlessEscrowArgs,
+};
+
+fn get_transfer_hook_remaining_accounts(
+    lite_svm: &LiteSVM,
+    quote_token_program: &Pubkey,
+    source: &Pubkey,
+    quote_mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+) -> (Vec<AccountMeta>, RemainingAccountsInfo) {
+    let transfer_hook_accounts = get_extra_account_metas_for_transfer_hook(
+        quote_token_program,
+        source,
+        quote_mint,
+        destination,
+        authority,
+        lite_svm,
+    );
+
+    let remaining_accounts_info = RemainingAccountsInfo {
+        slices: vec![RemainingAccountsSlice {
+            accounts_type: AccountsType::TransferHookQuote,
+            length: transfer_hook_accounts.len() as u8,
+        }],
+    };
+
+    (transfer_hook_accounts, remaining_accounts_info)
+}
+
+#[derive(Clone)]
+pub struct HandleCreateQuoteMintConfigArgs {
+    pub presale: Pubkey,
+    pub quote_mint: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub conversion_ratio_numerator: u64,
+    pub conversion_ratio_denominator: u64,
+}
+
+pub fn create_quote_mint_config_ix(
+    lite_svm: &LiteSVM,
+    args: HandleCreateQuoteMintConfigArgs,
+) -> Vec<Instruction> {
+    let HandleCreateQuoteMintConfigArgs {
+        presale,
+        quote_mint,
+        owner,
+        conversion_ratio_numerator,
+        conversion_ratio_denominator,
+    } = args;
+
+    let quote_token_program = lite_svm.get_account(&quote_mint).unwrap().owner;
+    let quote_mint_config = derive_quote_mint_config(&presale, &quote_mint, &presale::ID);
+
+    let ix_data = presale::instruction::CreateQuoteMintConfig {
+        params: CreateQuoteMintConfigParams {
+            conversion_ratio_numerator,
+            conversion_ratio_denominator,
+        },
+    }
+    .data();
+
+    let accounts = presale::accounts::CreateQuoteMintConfigCtx {
+        presale,
+        quote_mint_config,
+        quote_token_vault: derive_quote_mint_config_vault(&quote_mint_config, &presale::ID),
+        quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        owner: owner.pubkey(),
+        quote_token_program,
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    vec![Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }]
+}
+
+pub fn handle_create_quote_mint_config(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreateQuoteMintConfigArgs,
+) {
+    let instructions = create_quote_mint_config_ix(lite_svm, args.clone());
+    let HandleCreateQuoteMintConfigArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_create_quote_mint_config_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreateQuoteMintConfigArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_quote_mint_config_ix(lite_svm, args.clone());
+    let HandleCreateQuoteMintConfigArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleDepositWithQuoteMintArgs {
+    pub presale: Pubkey,
+    pub quote_mint: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub max_amount: u64,
+    pub registry_index: u8,
+}
+
+pub fn create_deposit_with_quote_mint_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleDepositWithQuoteMintArgs,
+) -> Vec<Instruction> {
+    create_deposit_with_quote_mint_ix_with_referral(lite_svm, args, None)
+}
+
+pub fn create_deposit_with_quote_mint_ix_with_referral(
+    lite_svm: &mut LiteSVM,
+    args: HandleDepositWithQuoteMintArgs,
+    referral: Option<Pubkey>,
+) -> Vec<Instruction> {
+    let HandleDepositWithQuoteMintArgs {
+        presale,
+        quote_mint,
+        owner,
+        max_amount,
+        registry_index,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let mut instructions = vec![];
+
+    let create_permissionless_escrow_ix = create_permissionless_escrow_ix(
+        lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale,
+            owner: Rc::clone(&owner),
+            registry_index,
+        },
+    );
+
+    if let Some(ix) = create_permissionless_escrow_ix {
+        instructions.push(ix);
+    }
+
+    let quote_token_program = lite_svm.get_account(&quote_mint).unwrap().owner;
+    let quote_mint_config = derive_quote_mint_config(&presale, &quote_mint, &presale::ID);
+    let quote_token_vault = derive_quote_mint_config_vault(&quote_mint_config, &presale::ID);
+
+    let payer_quote_token = get_associated_token_address_with_program_id(
+        &owner_pubkey,
+        &quote_mint,
+        &quote_token_program,
+    );
+
+    instructions.push(create_associated_token_account_idempotent(
+        &owner_pubkey,
+        &owner_pubkey,
+        &quote_mint,
+        &quote_token_program,
+    ));
+
+    let (transfer_hook_accounts, remaining_account_info) = get_transfer_hook_remaining_accounts(
+        lite_svm,
+        &quote_token_program,
+        &payer_quote_token,
+        &quote_mint,
+        &quote_token_vault,
+        &owner_pubkey,
+    );
+
+    let ix_data = presale::instruction::DepositWithQuoteMint {
+        max_amount,
+        remaining_account_info,
+    }
+    .data();
+
+    let mut accounts = presale::accounts::DepositWithQuoteMintCtx {
+        presale,
+        quote_mint_config,
+        quote_token_vault,
+        quote_mint,
+        escrow: derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID),
+        referral,
+        payer_quote_token,
+        payer: owner_pubkey,
+        token_program: quote_token_program,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(transfer_hook_accounts);
+
+    instructions.push(Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    });
+
+    instructions
+}
+
+pub fn handle_deposit_with_quote_mint(
+    lite_svm: &mut LiteSVM,
+    args: HandleDepositWithQuoteMintArgs,
+) {
+    let instructions = create_deposit_with_quote_mint_ix(lite_svm, args.clone());
+    let HandleDepositWithQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_deposit_with_quote_mint_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleDepositWithQuoteMintArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_deposit_with_quote_mint_ix(lite_svm, args.clone());
+    let HandleDepositWithQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleWithdrawWithQuoteMintArgs {
+    pub presale: Pubkey,
+    pub quote_mint: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub amount: u64,
+    pub registry_index: u8,
+}
+
+pub fn create_withdraw_with_quote_mint_ix(
+    lite_svm: &LiteSVM,
+    args: HandleWithdrawWithQuoteMintArgs,
+) -> Vec<Instruction> {
+    let HandleWithdrawWithQuoteMintArgs {
+        presale,
+        quote_mint,
+        owner,
+        amount,
+        registry_index,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let quote_token_program = lite_svm.get_account(&quote_mint).unwrap().owner;
+    let quote_mint_config = derive_quote_mint_config(&presale, &quote_mint, &presale::ID);
+    let quote_token_vault = derive_quote_mint_config_vault(&quote_mint_config, &presale::ID);
+
+    let owner_quote_token = get_associated_token_address_with_program_id(
+        &owner_pubkey,
+        &quote_mint,
+        &quote_token_program,
+    );
+
+    let (transfer_hook_accounts, remaining_account_info) = get_transfer_hook_remaining_accounts(
+        lite_svm,
+        &quote_token_program,
+        &quote_token_vault,
+        &quote_mint,
+        &owner_quote_token,
+        &owner_pubkey,
+    );
+
+    let ix_data = presale::instruction::WithdrawWithQuoteMint {
+        amount,
+        remaining_account_info,
+    }
+    .data();
+
+    let mut accounts = presale::accounts::WithdrawWithQuoteMintCtx {
+        presale,
+        quote_mint_config,
+        quote_token_vault,
+        quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        escrow: derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID),
+        owner_quote_token,
+        owner: owner_pubkey,
+        token_program: quote_token_program,
+        memo_program: anchor_spl::memo::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(transfer_hook_accounts);
+
+    vec![Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }]
+}
+
+pub fn handle_withdraw_with_quote_mint(
+    lite_svm: &mut LiteSVM,
+    args: HandleWithdrawWithQuoteMintArgs,
+) {
+    let instructions = create_withdraw_with_quote_mint_ix(lite_svm, args.clone());
+    let HandleWithdrawWithQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_withdraw_with_quote_mint_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleWithdrawWithQuoteMintArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_withdraw_with_quote_mint_ix(lite_svm, args.clone());
+    let HandleWithdrawWithQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleWithdrawRemainingQuoteWithQuoteMintArgs {
+    pub presale: Pubkey,
+    pub quote_mint: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub registry_index: u8,
+}
+
+pub fn create_withdraw_remaining_quote_with_quote_mint_ix(
+    lite_svm: &LiteSVM,
+    args: HandleWithdrawRemainingQuoteWithQuoteMintArgs,
+) -> Vec<Instruction> {
+    let HandleWithdrawRemainingQuoteWithQuoteMintArgs {
+        presale,
+        quote_mint,
+        owner,
+        registry_index,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let quote_token_program = lite_svm.get_account(&quote_mint).unwrap().owner;
+    let quote_mint_config = derive_quote_mint_config(&presale, &quote_mint, &presale::ID);
+    let quote_token_vault = derive_quote_mint_config_vault(&quote_mint_config, &presale::ID);
+
+    let owner_quote_token = get_associated_token_address_with_program_id(
+        &owner_pubkey,
+        &quote_mint,
+        &quote_token_program,
+    );
+
+    let (transfer_hook_accounts, remaining_accounts_info) = get_transfer_hook_remaining_accounts(
+        lite_svm,
+        &quote_token_program,
+        &quote_token_vault,
+        &quote_mint,
+        &owner_quote_token,
+        &owner_pubkey,
+    );
+
+    let ix_data = presale::instruction::WithdrawRemainingQuoteWithQuoteMint {
+        remaining_accounts_info,
+    }
+    .data();
+
+    let mut accounts = presale::accounts::WithdrawRemainingQuoteWithQuoteMintCtx {
+        presale,
+        quote_mint_config,
+        quote_token_vault,
+        quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        escrow: derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID),
+        owner_quote_token,
+        owner: owner_pubkey,
+        token_program: quote_token_program,
+        memo_program: anchor_spl::memo::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(transfer_hook_accounts);
+
+    vec![Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }]
+}
+
+pub fn handle_withdraw_remaining_quote_with_quote_mint(
+    lite_svm: &mut LiteSVM,
+    args: HandleWithdrawRemainingQuoteWithQuoteMintArgs,
+) {
+    let instructions = create_withdraw_remaining_quote_with_quote_mint_ix(lite_svm, args.clone());
+    let HandleWithdrawRemainingQuoteWithQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+#[derive(Clone)]
+pub struct HandleCreatorWithdrawQuoteMintArgs {
+    pub presale: Pubkey,
+    pub quote_mint: Pubkey,
+    pub owner: Rc<Keypair>,
+}
+
+pub fn create_creator_withdraw_quote_mint_ix(
+    lite_svm: &LiteSVM,
+    args: HandleCreatorWithdrawQuoteMintArgs,
+) -> Vec<Instruction> {
+    let HandleCreatorWithdrawQuoteMintArgs {
+        presale,
+        quote_mint,
+        owner,
+    } = args;
+    let owner_pubkey = owner.pubkey();
+
+    let quote_token_program = lite_svm.get_account(&quote_mint).unwrap().owner;
+    let quote_mint_config = derive_quote_mint_config(&presale, &quote_mint, &presale::ID);
+    let quote_token_vault = derive_quote_mint_config_vault(&quote_mint_config, &presale::ID);
+
+    let owner_quote_token = get_associated_token_address_with_program_id(
+        &owner_pubkey,
+        &quote_mint,
+        &quote_token_program,
+    );
+
+    let (transfer_hook_accounts, remaining_accounts_info) = get_transfer_hook_remaining_accounts(
+        lite_svm,
+        &quote_token_program,
+        &quote_token_vault,
+        &quote_mint,
+        &owner_quote_token,
+        &owner_pubkey,
+    );
+
+    let ix_data = presale::instruction::CreatorWithdrawQuoteMint {
+        remaining_accounts_info,
+    }
+    .data();
+
+    let mut accounts = presale::accounts::CreatorWithdrawQuoteMintCtx {
+        presale,
+        quote_mint_config,
+        quote_token_vault,
+        quote_mint,
+        presale_authority: presale::presale_authority::ID,
+        owner_quote_token,
+        owner: owner_pubkey,
+        token_program: quote_token_program,
+        memo_program: anchor_spl::memo::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(transfer_hook_accounts);
+
+    vec![
+        create_associated_token_account_idempotent(
+            &owner_pubkey,
+            &owner_pubkey,
+            &quote_mint,
+            &quote_token_program,
+        ),
+        Instruction {
+            program_id: presale::ID,
+            accounts,
+            data: ix_data,
+        },
+    ]
+}
+
+pub fn handle_creator_withdraw_quote_mint(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatorWithdrawQuoteMintArgs,
+) {
+    let instructions = create_creator_withdraw_quote_mint_ix(lite_svm, args.clone());
+    let HandleCreatorWithdrawQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_creator_withdraw_quote_mint_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatorWithdrawQuoteMintArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_creator_withdraw_quote_mint_ix(lite_svm, args.clone());
+    let HandleCreatorWithdrawQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
@@ -0,0 +1,212 @@
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_lang::*;
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::{AccountsType, Presale, RemainingAccountsInfo, RemainingAccountsSlice};
+use std::rc::Rc;
+
+use crate::helpers::{
+    create_deposit_ix_with_referral, create_deposit_native_ix_with_referral,
+    create_deposit_with_quote_mint_ix_with_referral, derive_event_authority, derive_referral,
+    get_extra_account_metas_for_transfer_hook, process_transaction, HandleDepositWithQuoteMintArgs,
+    HandleEscrowDepositArgs, HandleEscrowDepositNativeArgs, LiteSVMExt,
+};
+
+#[derive(Clone)]
+pub struct HandleCreateReferralArgs {
+    pub presale: Pubkey,
+    pub referrer: Pubkey,
+    pub payer: Rc<Keypair>,
+}
+
+pub fn create_referral_ix(args: HandleCreateReferralArgs) -> Instruction {
+    let HandleCreateReferralArgs {
+        presale,
+        referrer,
+        payer,
+    } = args;
+
+    let accounts = presale::accounts::CreateReferralCtx {
+        presale,
+        referral: derive_referral(&presale, &referrer, &presale::ID),
+        referrer,
+        payer: payer.pubkey(),
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: presale::instruction::CreateReferral {}.data(),
+    }
+}
+
+pub fn handle_create_referral(lite_svm: &mut LiteSVM, args: HandleCreateReferralArgs) {
+    let instruction = create_referral_ix(args.clone());
+    let HandleCreateReferralArgs { payer, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&payer.pubkey()), &[&payer]).unwrap();
+}
+
+pub fn create_deposit_with_referral_ix(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositArgs,
+    referrer: Pubkey,
+) -> Vec<Instruction> {
+    let referral = derive_referral(&args.presale, &referrer, &presale::ID);
+    create_deposit_ix_with_referral(lite_svm, args, Some(referral))
+}
+
+pub fn handle_escrow_deposit_with_referral(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositArgs,
+    referrer: Pubkey,
+) {
+    let instructions = create_deposit_with_referral_ix(lite_svm, args.clone(), referrer);
+    let HandleEscrowDepositArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_escrow_deposit_with_referral_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositArgs,
+    referrer: Pubkey,
+) -> FailedTransactionMetadata {
+    let instructions = create_deposit_with_referral_ix(lite_svm, args.clone(), referrer);
+    let HandleEscrowDepositArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+pub fn handle_deposit_native_with_referral(
+    lite_svm: &mut LiteSVM,
+    args: HandleEscrowDepositNativeArgs,
+    referrer: Pubkey,
+) {
+    let referral = derive_referral(&args.presale, &referrer, &presale::ID);
+    let instructions =
+        create_deposit_native_ix_with_referral(lite_svm, args.clone(), Some(referral));
+    let HandleEscrowDepositNativeArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_deposit_with_quote_mint_with_referral_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleDepositWithQuoteMintArgs,
+    referrer: Pubkey,
+) -> FailedTransactionMetadata {
+    let referral = derive_referral(&args.presale, &referrer, &presale::ID);
+    let instructions =
+        create_deposit_with_quote_mint_ix_with_referral(lite_svm, args.clone(), Some(referral));
+    let HandleDepositWithQuoteMintArgs { owner, .. } = args;
+    process_transaction(lite_svm, &instructions, Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleClaimReferralFeeArgs {
+    pub presale: Pubkey,
+    pub referrer: Rc<Keypair>,
+}
+
+pub fn create_claim_referral_fee_ix(
+    lite_svm: &LiteSVM,
+    args: HandleClaimReferralFeeArgs,
+) -> Vec<Instruction> {
+    let HandleClaimReferralFeeArgs { presale, referrer } = args;
+    let referrer_pubkey = referrer.pubkey();
+
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+    let quote_token_program = lite_svm
+        .get_account(&presale_state.quote_mint)
+        .unwrap()
+        .owner;
+
+    let referrer_quote_token = get_associated_token_address_with_program_id(
+        &referrer_pubkey,
+        &presale_state.quote_mint,
+        &quote_token_program,
+    );
+
+    let transfer_hook_accounts = get_extra_account_metas_for_transfer_hook(
+        &quote_token_program,
+        &presale_state.quote_token_vault,
+        &presale_state.quote_mint,
+        &referrer_quote_token,
+        &referrer_pubkey,
+        lite_svm,
+    );
+
+    let ix_data = presale::instruction::ClaimReferralFee {
+        remaining_accounts_info: RemainingAccountsInfo {
+            slices: vec![RemainingAccountsSlice {
+                accounts_type: AccountsType::TransferHookQuote,
+                length: transfer_hook_accounts.len() as u8,
+            }],
+        },
+    }
+    .data();
+
+    let mut accounts = presale::accounts::ClaimReferralFeeCtx {
+        presale,
+        referral: derive_referral(&presale, &referrer_pubkey, &presale::ID),
+        presale_authority: presale::presale_authority::ID,
+        quote_token_vault: presale_state.quote_token_vault,
+        quote_mint: presale_state.quote_mint,
+        referrer_quote_token,
+        referrer: referrer_pubkey,
+        token_program: quote_token_program,
+        memo_program: anchor_spl::memo::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(transfer_hook_accounts);
+
+    vec![
+        create_associated_token_account_idempotent(
+            &referrer_pubkey,
+            &referrer_pubkey,
+            &presale_state.quote_mint,
+            &quote_token_program,
+        ),
+        Instruction {
+            program_id: presale::ID,
+            accounts,
+            data: ix_data,
+        },
+    ]
+}
+
+pub fn handle_claim_referral_fee(lite_svm: &mut LiteSVM, args: HandleClaimReferralFeeArgs) {
+    let instructions = create_claim_referral_fee_ix(lite_svm, args.clone());
+    let HandleClaimReferralFeeArgs { referrer, .. } = args;
+    process_transaction(
+        lite_svm,
+        &instructions,
+        Some(&referrer.pubkey()),
+        &[&referrer],
+    )
+    .unwrap();
+}
+
+pub fn handle_claim_referral_fee_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleClaimReferralFeeArgs,
+) -> FailedTransactionMetadata {
+    let instructions = create_claim_referral_fee_ix(lite_svm, args.clone());
+    let HandleClaimReferralFeeArgs { referrer, .. } = args;
+    process_transaction(
+        lite_svm,
+        &instructions,
+        Some(&referrer.pubkey()),
+        &[&referrer],
+    )
+    .unwrap_err()
+}
@@ -0,0 +1,68 @@
+use std::rc::Rc;
+
+use crate::helpers::{derive_event_authority, process_transaction};
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+
+#[derive(Clone)]
+pub struct HandleRevokeMerkleRootConfigArgs {
+    pub presale: Pubkey,
+    pub merkle_root_config: Pubkey,
+    pub creator: Rc<Keypair>,
+}
+
+pub fn create_revoke_merkle_root_config_ix(args: HandleRevokeMerkleRootConfigArgs) -> Instruction {
+    let HandleRevokeMerkleRootConfigArgs {
+        presale,
+        merkle_root_config,
+        creator,
+    } = args;
+
+    let accounts = presale::accounts::RevokeMerkleRootConfigCtx {
+        presale,
+        merkle_root_config,
+        creator: creator.pubkey(),
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: presale::instruction::RevokeMerkleRootConfig {}.data(),
+    }
+}
+
+pub fn handle_revoke_merkle_root_config(
+    lite_svm: &mut LiteSVM,
+    args: HandleRevokeMerkleRootConfigArgs,
+) {
+    let instruction = create_revoke_merkle_root_config_ix(args.clone());
+    let HandleRevokeMerkleRootConfigArgs { creator, .. } = args;
+    process_transaction(
+        lite_svm,
+        &[instruction],
+        Some(&creator.pubkey()),
+        &[&creator],
+    )
+    .unwrap();
+}
+
+pub fn handle_revoke_merkle_root_config_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleRevokeMerkleRootConfigArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_revoke_merkle_root_config_ix(args.clone());
+    let HandleRevokeMerkleRootConfigArgs { creator, .. } = args;
+    process_transaction(
+        lite_svm,
+        &[instruction],
+        Some(&creator.pubkey()),
+        &[&creator],
+    )
+    .unwrap_err()
+}
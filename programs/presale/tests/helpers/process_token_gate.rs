@@ -0,0 +1,215 @@
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_lang::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::{CreatePermissionedEscrowWithTokenGateParams, CreateTokenGateConfigParams};
+use std::rc::Rc;
+
+use crate::helpers::{
+    derive_escrow, derive_event_authority, derive_gating_token_vault, derive_token_gate_config,
+    process_transaction,
+};
+
+#[derive(Clone)]
+pub struct HandleCreateTokenGateConfigArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub gating_mint: Pubkey,
+    pub registry_index: u8,
+    pub minimum_holding_amount: u64,
+    pub q_deposit_cap_per_holding: u128,
+}
+
+pub fn create_token_gate_config_ix(args: HandleCreateTokenGateConfigArgs) -> Instruction {
+    let HandleCreateTokenGateConfigArgs {
+        presale,
+        owner,
+        gating_mint,
+        registry_index,
+        minimum_holding_amount,
+        q_deposit_cap_per_holding,
+    } = args;
+
+    let accounts = presale::accounts::CreateTokenGateConfigCtx {
+        presale,
+        token_gate_config: derive_token_gate_config(&presale, registry_index, &presale::ID),
+        gating_mint,
+        owner: owner.pubkey(),
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    let data = presale::instruction::CreateTokenGateConfig {
+        params: CreateTokenGateConfigParams {
+            registry_index,
+            minimum_holding_amount,
+            q_deposit_cap_per_holding,
+        },
+    }
+    .data();
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn handle_create_token_gate_config(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreateTokenGateConfigArgs,
+) {
+    let instruction = create_token_gate_config_ix(args.clone());
+    let HandleCreateTokenGateConfigArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+#[derive(Clone)]
+pub struct HandleCreatePermissionedEscrowWithTokenGateArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub gating_mint: Pubkey,
+    pub registry_index: u8,
+    pub holding_amount: u64,
+}
+
+pub fn create_permissioned_escrow_with_token_gate_ix(
+    lite_svm: &LiteSVM,
+    args: HandleCreatePermissionedEscrowWithTokenGateArgs,
+) -> Instruction {
+    let HandleCreatePermissionedEscrowWithTokenGateArgs {
+        presale,
+        owner,
+        gating_mint,
+        registry_index,
+        holding_amount,
+    } = args;
+
+    let owner_pubkey = owner.pubkey();
+    let gating_mint_program = lite_svm.get_account(&gating_mint).unwrap().owner;
+    let escrow = derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID);
+
+    let accounts = presale::accounts::CreatePermissionedEscrowWithTokenGateCtx {
+        presale,
+        escrow,
+        owner: owner_pubkey,
+        token_gate_config: derive_token_gate_config(&presale, registry_index, &presale::ID),
+        gating_mint,
+        holding_token_account: get_associated_token_address_with_program_id(
+            &owner_pubkey,
+            &gating_mint,
+            &gating_mint_program,
+        ),
+        gating_token_vault: derive_gating_token_vault(&escrow, &presale::ID),
+        presale_authority: presale::presale_authority::ID,
+        payer: owner_pubkey,
+        token_program: gating_mint_program,
+        system_program: anchor_lang::system_program::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    let data = presale::instruction::CreatePermissionedEscrowWithTokenGate {
+        params: CreatePermissionedEscrowWithTokenGateParams {
+            registry_index,
+            holding_amount,
+            ..Default::default()
+        },
+    }
+    .data();
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn handle_create_permissioned_escrow_with_token_gate(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatePermissionedEscrowWithTokenGateArgs,
+) {
+    let instruction = create_permissioned_escrow_with_token_gate_ix(lite_svm, args.clone());
+    let HandleCreatePermissionedEscrowWithTokenGateArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_create_permissioned_escrow_with_token_gate_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleCreatePermissionedEscrowWithTokenGateArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_permissioned_escrow_with_token_gate_ix(lite_svm, args.clone());
+    let HandleCreatePermissionedEscrowWithTokenGateArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleWithdrawGatingTokenArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub gating_mint: Pubkey,
+    pub registry_index: u8,
+}
+
+pub fn create_withdraw_gating_token_ix(
+    lite_svm: &LiteSVM,
+    args: HandleWithdrawGatingTokenArgs,
+) -> Instruction {
+    let HandleWithdrawGatingTokenArgs {
+        presale,
+        owner,
+        gating_mint,
+        registry_index,
+    } = args;
+
+    let owner_pubkey = owner.pubkey();
+    let gating_mint_program = lite_svm.get_account(&gating_mint).unwrap().owner;
+    let escrow = derive_escrow(&presale, &owner_pubkey, registry_index, &presale::ID);
+
+    let accounts = presale::accounts::WithdrawGatingTokenCtx {
+        presale,
+        escrow,
+        gating_token_vault: derive_gating_token_vault(&escrow, &presale::ID),
+        gating_mint,
+        owner_gating_token: get_associated_token_address_with_program_id(
+            &owner_pubkey,
+            &gating_mint,
+            &gating_mint_program,
+        ),
+        presale_authority: presale::presale_authority::ID,
+        owner: owner_pubkey,
+        token_program: gating_mint_program,
+        memo_program: anchor_spl::memo::ID,
+        event_authority: derive_event_authority(&presale::ID),
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    let data = presale::instruction::WithdrawGatingToken {}.data();
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn handle_withdraw_gating_token(lite_svm: &mut LiteSVM, args: HandleWithdrawGatingTokenArgs) {
+    let instruction = create_withdraw_gating_token_ix(lite_svm, args.clone());
+    let HandleWithdrawGatingTokenArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_withdraw_gating_token_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleWithdrawGatingTokenArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_withdraw_gating_token_ix(lite_svm, args.clone());
+    let HandleWithdrawGatingTokenArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
@@ -0,0 +1,163 @@
+use std::rc::Rc;
+
+use crate::helpers::{derive_escrow, derive_event_authority, derive_operator, process_transaction};
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use presale::{
+    UpdateEscrowDepositCapWithCreatorParams, UpdateEscrowDepositCapWithMerkleProofParams,
+};
+
+#[derive(Clone)]
+pub struct HandleUpdateEscrowDepositCapWithOperatorArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub vault_owner: Pubkey,
+    pub operator: Rc<Keypair>,
+    pub registry_index: u8,
+    pub deposit_cap: u64,
+}
+
+pub fn create_update_escrow_deposit_cap_with_operator_ix(
+    args: HandleUpdateEscrowDepositCapWithOperatorArgs,
+) -> Instruction {
+    let HandleUpdateEscrowDepositCapWithOperatorArgs {
+        presale,
+        owner,
+        vault_owner,
+        operator,
+        registry_index,
+        deposit_cap,
+    } = args;
+
+    let ix_data = presale::instruction::UpdateEscrowDepositCapWithCreator {
+        params: UpdateEscrowDepositCapWithCreatorParams {
+            deposit_cap,
+            ..Default::default()
+        },
+    }
+    .data();
+
+    let accounts = presale::accounts::UpdateEscrowDepositCapWithCreatorCtx {
+        presale,
+        escrow: derive_escrow(&presale, &owner.pubkey(), registry_index, &presale::ID),
+        operator: derive_operator(
+            &vault_owner,
+            &operator.pubkey(),
+            &Pubkey::default(),
+            &presale::ID,
+        ),
+        operator_owner: operator.pubkey(),
+        program: presale::ID,
+        event_authority: derive_event_authority(&presale::ID),
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }
+}
+
+pub fn handle_update_escrow_deposit_cap_with_operator(
+    lite_svm: &mut LiteSVM,
+    args: HandleUpdateEscrowDepositCapWithOperatorArgs,
+) {
+    let instruction = create_update_escrow_deposit_cap_with_operator_ix(args.clone());
+    let HandleUpdateEscrowDepositCapWithOperatorArgs {
+        owner, operator, ..
+    } = args;
+    process_transaction(
+        lite_svm,
+        &[instruction],
+        Some(&owner.pubkey()),
+        &[&owner, &operator],
+    )
+    .unwrap();
+}
+
+pub fn handle_update_escrow_deposit_cap_with_operator_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleUpdateEscrowDepositCapWithOperatorArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_update_escrow_deposit_cap_with_operator_ix(args.clone());
+    let HandleUpdateEscrowDepositCapWithOperatorArgs {
+        owner, operator, ..
+    } = args;
+    process_transaction(
+        lite_svm,
+        &[instruction],
+        Some(&owner.pubkey()),
+        &[&owner, &operator],
+    )
+    .unwrap_err()
+}
+
+#[derive(Clone)]
+pub struct HandleUpdateEscrowDepositCapWithMerkleProofArgs {
+    pub presale: Pubkey,
+    pub owner: Rc<Keypair>,
+    pub merkle_root_config: Pubkey,
+    pub registry_index: u8,
+    pub deposit_cap: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+pub fn create_update_escrow_deposit_cap_with_merkle_proof_ix(
+    args: HandleUpdateEscrowDepositCapWithMerkleProofArgs,
+) -> Instruction {
+    let HandleUpdateEscrowDepositCapWithMerkleProofArgs {
+        presale,
+        owner,
+        merkle_root_config,
+        registry_index,
+        deposit_cap,
+        proof,
+    } = args;
+
+    let ix_data = presale::instruction::UpdateEscrowDepositCapWithMerkleProof {
+        params: UpdateEscrowDepositCapWithMerkleProofParams {
+            proof,
+            deposit_cap,
+            ..Default::default()
+        },
+    }
+    .data();
+
+    let accounts = presale::accounts::UpdateEscrowDepositCapWithMerkleProofCtx {
+        presale,
+        escrow: derive_escrow(&presale, &owner.pubkey(), registry_index, &presale::ID),
+        owner: owner.pubkey(),
+        merkle_root_config,
+        program: presale::ID,
+        event_authority: derive_event_authority(&presale::ID),
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data: ix_data,
+    }
+}
+
+pub fn handle_update_escrow_deposit_cap_with_merkle_proof(
+    lite_svm: &mut LiteSVM,
+    args: HandleUpdateEscrowDepositCapWithMerkleProofArgs,
+) {
+    let instruction = create_update_escrow_deposit_cap_with_merkle_proof_ix(args.clone());
+    let HandleUpdateEscrowDepositCapWithMerkleProofArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap();
+}
+
+pub fn handle_update_escrow_deposit_cap_with_merkle_proof_err(
+    lite_svm: &mut LiteSVM,
+    args: HandleUpdateEscrowDepositCapWithMerkleProofArgs,
+) -> FailedTransactionMetadata {
+    let instruction = create_update_escrow_deposit_cap_with_merkle_proof_ix(args.clone());
+    let HandleUpdateEscrowDepositCapWithMerkleProofArgs { owner, .. } = args;
+    process_transaction(lite_svm, &[instruction], Some(&owner.pubkey()), &[&owner]).unwrap_err()
+}
@@ -135,7 +135,7 @@ fn test_fixed_price_presale_registry_consume_over_supply() {
     }];
 
     let merkle_tree = build_merkle_tree(whitelist_wallets, 0);
-    let tree_node = merkle_tree.get_node(&user_pubkey);
+    let tree_node = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_merkle_root_config(
         &mut lite_svm,
@@ -10,9 +10,12 @@ use anchor_spl::associated_token::get_associated_token_address_with_program_id;
 use anchor_spl::token_interface::TokenAccount;
 use helpers::*;
 use litesvm::LiteSVM;
+use merkle_tree::config_merkle_tree::ConfigMerkleTree;
+use merkle_tree::tree_node::TreeNode;
 use presale::{
-    calculate_dripped_amount_for_user, Escrow, FixedPricePresaleHandler, Presale,
-    DEFAULT_PERMISSIONLESS_REGISTRY_INDEX, SCALE_OFFSET,
+    calculate_dripped_amount_for_user, BoolType, CreateOperatorParams, Escrow,
+    FixedPricePresaleHandler, Presale, WhitelistMode, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+    SCALE_OFFSET,
 };
 use std::ops::Shl;
 use std::rc::Rc;
@@ -152,7 +155,7 @@ fn test_claim_empty_prorata_presale_registries() {
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -176,7 +179,7 @@ fn test_claim_empty_prorata_presale_registries() {
         },
     );
 
-    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey);
+    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -651,7 +654,7 @@ fn test_claim_permissioned_fcfs_presale_with_multiple_presale_registries() {
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -664,7 +667,7 @@ fn test_claim_permissioned_fcfs_presale_with_multiple_presale_registries() {
         },
     );
 
-    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey);
+    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -677,7 +680,7 @@ fn test_claim_permissioned_fcfs_presale_with_multiple_presale_registries() {
         },
     );
 
-    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey);
+    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -861,7 +864,7 @@ fn test_claim_permissioned_prorata_presale_with_multiple_presale_registries() {
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -874,7 +877,7 @@ fn test_claim_permissioned_prorata_presale_with_multiple_presale_registries() {
         },
     );
 
-    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey);
+    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -887,7 +890,7 @@ fn test_claim_permissioned_prorata_presale_with_multiple_presale_registries() {
         },
     );
 
-    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey);
+    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -1071,7 +1074,7 @@ fn test_claim_permissioned_fixed_price_presale_with_multiple_presale_registries(
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -1084,7 +1087,7 @@ fn test_claim_permissioned_fixed_price_presale_with_multiple_presale_registries(
         },
     );
 
-    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey);
+    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -1097,7 +1100,7 @@ fn test_claim_permissioned_fixed_price_presale_with_multiple_presale_registries(
         },
     );
 
-    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey);
+    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey).unwrap();
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
         HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -1327,3 +1330,350 @@ fn test_claim_permissionless_presale() {
         None,
     );
 }
+
+#[test]
+fn test_claim_fcfs_presale_with_unsold_rollover() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_fcfs_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::PermissionWithAuthority,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    // Public registry 0 receives the unsold supply of the whitelisted registry 1
+    let params = &mut wrapper.args.params;
+    params.presale_params.enable_unsold_rollover = BoolType::True.into();
+    params.presale_params.unsold_rollover_registry_index = DEFAULT_PERMISSIONLESS_REGISTRY_INDEX;
+
+    let presale_registries = &mut params.presale_registries;
+    presale_registries[0].presale_supply /= 2;
+    let mut permissioned_registry = presale_registries[0];
+    presale_registries[0].override_whitelist_mode = BoolType::True.into();
+    presale_registries[0].whitelist_mode = WhitelistMode::Permissionless.into();
+    presale_registries[0].buyer_minimum_deposit_cap = 1_000;
+    permissioned_registry.buyer_minimum_deposit_cap = 1;
+    presale_registries.push(permissioned_registry);
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_maximum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    warp_time(&mut lite_svm, presale_state.vesting_end_time + 1);
+
+    claim_and_assert(
+        &mut lite_svm,
+        Rc::clone(&user),
+        presale_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        Cmp::GreaterThan,
+        Some(presale_state.presale_supply),
+    );
+}
+
+#[test]
+fn test_claim_fixed_price_presale_with_registry_price() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_fixed_price_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::PermissionWithAuthority,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    // Registry 0 is public and sold at presale price, registry 1 is whitelisted and sold at double the presale price
+    let fixed_price_params = &mut wrapper.fixed_point_params_wrapper.args.params;
+    let q_price = fixed_price_params.q_price;
+    let registry_q_price = q_price * 2;
+    fixed_price_params.registry_q_prices[0] = registry_q_price;
+
+    let presale_registries = &mut wrapper
+        .presale_params_wrapper
+        .args
+        .params
+        .presale_registries;
+    presale_registries[0].presale_supply /= 2;
+    let mut permissioned_registry = presale_registries[0];
+    presale_registries[0].override_whitelist_mode = BoolType::True.into();
+    presale_registries[0].whitelist_mode = WhitelistMode::Permissionless.into();
+    permissioned_registry.buyer_minimum_deposit_cap = registry_q_price
+        .div_ceil(presale::SCALE_MULTIPLIER)
+        .try_into()
+        .unwrap();
+    presale_registries.push(permissioned_registry);
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert_eq!(presale_state.presale_registries[0].q_price, q_price);
+    assert_eq!(
+        presale_state.presale_registries[1].q_price,
+        registry_q_price
+    );
+
+    let operator = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
+        },
+    );
+
+    handle_create_permissioned_escrow_with_operator(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user_pubkey,
+            operator: Rc::clone(&operator),
+            registry_index: 1,
+            max_deposit_cap: presale_state.presale_maximum_cap,
+        },
+    );
+
+    let deposit_amount = presale_state.presale_minimum_cap;
+
+    for registry_index in [DEFAULT_PERMISSIONLESS_REGISTRY_INDEX, 1] {
+        handle_escrow_deposit(
+            &mut lite_svm,
+            HandleEscrowDepositArgs {
+                presale: presale_pubkey,
+                owner: Rc::clone(&user),
+                max_amount: deposit_amount,
+                registry_index,
+            },
+        );
+    }
+
+    warp_time(&mut lite_svm, presale_state.vesting_end_time + 1);
+
+    for (registry_index, q_price) in [
+        (DEFAULT_PERMISSIONLESS_REGISTRY_INDEX, q_price),
+        (1, registry_q_price),
+    ] {
+        let escrow = derive_escrow(&presale_pubkey, &user_pubkey, registry_index, &presale::ID);
+        let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+        let token_bought = u128::from(escrow_state.total_deposit).shl(SCALE_OFFSET) / q_price;
+
+        claim_and_assert(
+            &mut lite_svm,
+            Rc::clone(&user),
+            presale_pubkey,
+            registry_index,
+            Cmp::GreaterThan,
+            Some(token_bought.try_into().unwrap()),
+        );
+    }
+}
+
+#[test]
+fn test_claim_fixed_price_presale_with_escrow_terms_override() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let user_1 = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+    let user_1_pubkey = user_1.pubkey();
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let deposit_cap = presale_state.presale_registries[0].buyer_maximum_deposit_cap;
+
+    // User 1 buys at 20% discount, and receives everything at the immediate release
+    let price_discount_bps = 2_000;
+    let tree_nodes = vec![
+        TreeNode::new(user_pubkey, 0, deposit_cap),
+        TreeNode {
+            price_discount_bps: Some(price_discount_bps),
+            immediate_release_bps: Some(10_000),
+            vest_duration: Some(0),
+            ..TreeNode::new(user_1_pubkey, 0, deposit_cap)
+        },
+    ];
+    let merkle_tree = ConfigMerkleTree::new(tree_nodes, 0).unwrap();
+    let merkle_root_config =
+        merkle_tree.get_merkle_root_config_pubkey(presale_pubkey, &presale::ID);
+
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree,
+        },
+    );
+
+    for keypair in [Rc::clone(&user), Rc::clone(&user_1)] {
+        let tree_node = merkle_tree.get_node(&keypair.pubkey()).unwrap();
+        handle_create_permissioned_escrow_with_merkle_proof_and_terms_override(
+            &mut lite_svm,
+            HandleCreatePermissionedEscrowWithMerkleProofArgs {
+                presale: presale_pubkey,
+                owner: Rc::clone(&keypair),
+                merkle_root_config,
+                registry_index: tree_node.registry_index,
+                max_deposit_cap: tree_node.deposit_cap,
+                proof: tree_node.proof.clone().unwrap(),
+            },
+            tree_node.get_terms_override(),
+        );
+    }
+
+    let escrow = derive_escrow(&presale_pubkey, &user_pubkey, 0, &presale::ID);
+    let escrow_1 = derive_escrow(&presale_pubkey, &user_1_pubkey, 0, &presale::ID);
+
+    let escrow_state_1: Escrow = lite_svm.get_deserialized_zc_account(&escrow_1).unwrap();
+    assert_eq!(escrow_state_1.price_discount_bps, price_discount_bps);
+    assert!(escrow_state_1.is_vesting_overridden());
+    assert_eq!(escrow_state_1.immediate_release_bps, 10_000);
+    assert_eq!(escrow_state_1.vest_duration, 0);
+
+    let deposit_amount = presale_state.presale_minimum_cap;
+    for keypair in [Rc::clone(&user), Rc::clone(&user_1)] {
+        handle_escrow_deposit(
+            &mut lite_svm,
+            HandleEscrowDepositArgs {
+                presale: presale_pubkey,
+                owner: keypair,
+                max_amount: deposit_amount,
+                registry_index: 0,
+            },
+        );
+    }
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let presale_registry = presale_state.presale_registries[0];
+    let escrow_state_1: Escrow = lite_svm.get_deserialized_zc_account(&escrow_1).unwrap();
+
+    // Discounted deposit is worth more at the registry price
+    let price_discount_bonus = escrow_state_1.total_deposit * u64::from(price_discount_bps)
+        / (10_000 - u64::from(price_discount_bps));
+    assert_eq!(
+        presale_registry.total_price_discount_bonus,
+        price_discount_bonus
+    );
+
+    let fixed_price_handler = decode_presale_mode_raw_data::<FixedPricePresaleHandler>(
+        &presale_state.presale_mode_raw_data,
+    );
+    let weighted_total_deposit = presale_registry.total_deposit + price_discount_bonus;
+    let total_token_sold =
+        (u128::from(weighted_total_deposit).shl(SCALE_OFFSET) / fixed_price_handler.q_price) as u64;
+
+    let expected_claim_amount = |weighted_deposit: u64| -> u64 {
+        (u128::from(total_token_sold) * u128::from(weighted_deposit)
+            / u128::from(weighted_total_deposit)) as u64
+    };
+
+    // User 1 receives everything once the immediate release starts
+    warp_time(
+        &mut lite_svm,
+        presale_state
+            .immediate_release_timestamp
+            .max(presale_state.presale_end_time),
+    );
+
+    claim_and_assert(
+        &mut lite_svm,
+        Rc::clone(&user_1),
+        presale_pubkey,
+        0,
+        Cmp::GreaterThan,
+        Some(expected_claim_amount(
+            escrow_state_1.total_deposit + price_discount_bonus,
+        )),
+    );
+
+    warp_time(&mut lite_svm, presale_state.vesting_end_time + 1);
+
+    claim_and_assert(
+        &mut lite_svm,
+        Rc::clone(&user_1),
+        presale_pubkey,
+        0,
+        Cmp::Equal,
+        None,
+    );
+
+    claim_and_assert(
+        &mut lite_svm,
+        Rc::clone(&user),
+        presale_pubkey,
+        0,
+        Cmp::GreaterThan,
+        None,
+    );
+
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    let expected_claim_amount = expected_claim_amount(escrow_state.total_deposit);
+    assert!(
+        escrow_state
+            .total_claimed_token
+            .abs_diff(expected_claim_amount)
+            <= 1
+    );
+}
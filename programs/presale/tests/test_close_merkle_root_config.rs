@@ -374,7 +374,7 @@ fn test_close_merkle_root_config_presale_success() {
     let merkle_root_config =
         merkle_tree.get_merkle_root_config_pubkey(presale_pubkey, &presale::ID);
 
-    let tree_node = merkle_tree.get_node(&user_pubkey);
+    let tree_node = merkle_tree.get_node(&user_pubkey).unwrap();
     let proof = tree_node.proof.unwrap();
 
     let presale_state = lite_svm
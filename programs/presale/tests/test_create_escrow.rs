@@ -1,11 +1,14 @@
 pub mod helpers;
 
 use anchor_client::solana_sdk::{
-    native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer,
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
 };
-use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::{error::ERROR_CODE_OFFSET, prelude::Clock};
 use helpers::*;
-use presale::{Escrow, Presale, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX};
+use presale::{
+    BoolType, CreateOperatorParams, Escrow, Operator, Presale, UpdateOperatorParams, WhitelistMode,
+    DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+};
 use std::rc::Rc;
 
 #[test]
@@ -77,6 +80,7 @@ fn test_initialize_escrow_with_invalid_whitelist_mode() {
         HandleCreateOperatorArgs {
             owner: Rc::clone(&user),
             operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
         },
     );
 
@@ -220,6 +224,7 @@ fn test_initialize_permissioned_with_authority_escrow_with_invalid_operator() {
         HandleCreateOperatorArgs {
             owner: Rc::clone(&user),
             operator: operator_0.pubkey(),
+            params: CreateOperatorParams::default(),
         },
     );
 
@@ -229,6 +234,7 @@ fn test_initialize_permissioned_with_authority_escrow_with_invalid_operator() {
         HandleCreateOperatorArgs {
             owner: Rc::clone(&user_1),
             operator: operator_1.pubkey(),
+            params: CreateOperatorParams::default(),
         },
     );
 
@@ -303,7 +309,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_invalid_proof() {
         },
     );
 
-    let tree_node = merkle_tree.get_node(&user_1.pubkey());
+    let tree_node = merkle_tree.get_node(&user_1.pubkey()).unwrap();
 
     let err = handle_create_permissioned_escrow_with_merkle_proof_err(
         &mut lite_svm,
@@ -368,7 +374,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_invalid_registry_i
         },
     );
 
-    let tree_node = merkle_tree.get_node(&user.pubkey());
+    let tree_node = merkle_tree.get_node(&user.pubkey()).unwrap();
 
     let err = handle_create_permissioned_escrow_with_merkle_proof_err(
         &mut lite_svm,
@@ -414,6 +420,7 @@ fn test_initialize_permissioned_with_authority_escrow_with_invalid_registry_inde
         HandleCreateOperatorArgs {
             owner: Rc::clone(&user),
             operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
         },
     );
 
@@ -468,6 +475,7 @@ fn test_initialize_permissioned_with_authority_escrow() {
         HandleCreateOperatorArgs {
             owner: Rc::clone(&user),
             operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
         },
     );
 
@@ -493,7 +501,7 @@ fn test_initialize_permissioned_with_authority_escrow() {
 }
 
 #[test]
-fn test_initialize_permissioned_with_merkle_proof_escrow() {
+fn test_initialize_permissioned_with_authority_escrow_with_scoped_operator() {
     let mut setup_context = SetupContext::initialize();
     let mint = setup_context.setup_mint(
         DEFAULT_BASE_TOKEN_DECIMALS,
@@ -504,56 +512,231 @@ fn test_initialize_permissioned_with_merkle_proof_escrow() {
     let quote = anchor_spl::token::spl_token::native_mint::ID;
 
     let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
-        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
+        handle_create_predefined_permissioned_with_authority_fixed_price_presale(
             &mut lite_svm,
             mint,
             quote,
             Rc::clone(&user),
         );
 
-    let presale: Presale = lite_svm
+    let presale_state: Presale = lite_svm
         .get_deserialized_zc_account(&presale_pubkey)
         .unwrap();
+    let max_deposit_cap = presale_state
+        .presale_registries
+        .get(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX as usize)
+        .unwrap()
+        .buyer_maximum_deposit_cap;
 
-    let whitelist_wallet = vec![WhitelistWallet {
-        address: user.pubkey(),
-        registry_index: 0,
-        max_deposit_cap: presale
-            .presale_registries
-            .get(0)
-            .unwrap()
-            .buyer_maximum_deposit_cap,
-    }];
+    // Operator scoped to another presale
+    let operator_0 = Rc::new(Keypair::new());
+    let other_presale = Keypair::new().pubkey();
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator_0.pubkey(),
+            params: CreateOperatorParams {
+                presale: other_presale,
+                ..Default::default()
+            },
+        },
+    );
 
-    let merkle_tree = build_merkle_tree(whitelist_wallet, 0);
+    let mut instruction = create_permissioned_escrow_with_operator_ix(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator_0),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap,
+        },
+    )
+    .unwrap();
 
-    handle_create_merkle_root_config(
+    let unscoped_operator = derive_operator(
+        &user.pubkey(),
+        &operator_0.pubkey(),
+        &Pubkey::default(),
+        &presale::ID,
+    );
+    let other_presale_operator = derive_operator(
+        &user.pubkey(),
+        &operator_0.pubkey(),
+        &other_presale,
+        &presale::ID,
+    );
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == unscoped_operator {
+            account.pubkey = other_presale_operator;
+        }
+    }
+
+    let err = process_transaction(
         &mut lite_svm,
-        HandleCreateMerkleRootConfigArgs {
+        &[instruction],
+        Some(&user.pubkey()),
+        &[&user, &operator_0],
+    )
+    .unwrap_err();
+
+    let expected_err = presale::errors::PresaleError::InvalidOperator;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Operator scoped to the presale, with a quota of one escrow
+    let operator_1 = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator_1.pubkey(),
+            params: CreateOperatorParams {
+                presale: presale_pubkey,
+                max_escrow_count: 1,
+                ..Default::default()
+            },
+        },
+    );
+
+    handle_create_permissioned_escrow_with_operator(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
             presale: presale_pubkey,
             owner: Rc::clone(&user),
-            merkle_tree: &merkle_tree,
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator_1),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap,
+        },
+    );
+
+    let operator_state: Operator = lite_svm
+        .get_deserialized_zc_account(&derive_operator(
+            &user.pubkey(),
+            &operator_1.pubkey(),
+            &presale_pubkey,
+            &presale::ID,
+        ))
+        .unwrap();
+    assert_eq!(operator_state.escrow_count, 1);
+    assert_eq!(operator_state.total_deposit_cap, max_deposit_cap);
+
+    let user_1 = Rc::new(Keypair::new());
+    transfer_sol(
+        &mut lite_svm,
+        Rc::clone(&user),
+        user_1.pubkey(),
+        LAMPORTS_PER_SOL,
+    );
+
+    let err = handle_create_permissioned_escrow_with_operator_err(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user_1),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator_1),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap,
         },
     );
 
-    let tree_node = merkle_tree.get_node(&user.pubkey());
+    let expected_err = presale::errors::PresaleError::OperatorQuotaExceeded;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
 
-    handle_create_permissioned_escrow_with_merkle_proof(
+    // Quota raised without revoking the operator
+    let err = handle_update_operator_err(
         &mut lite_svm,
-        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+        HandleUpdateOperatorArgs {
+            owner: Rc::clone(&user_1),
+            operator_pda: derive_operator(
+                &user.pubkey(),
+                &operator_1.pubkey(),
+                &presale_pubkey,
+                &presale::ID,
+            ),
+            params: UpdateOperatorParams {
+                max_escrow_count: 2,
+                ..Default::default()
+            },
+        },
+    );
+
+    let error_code = anchor_lang::error::ErrorCode::ConstraintHasOne;
+    let err_str = format!("Error Number: {}.", error_code as u32);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_update_operator(
+        &mut lite_svm,
+        HandleUpdateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator_pda: derive_operator(
+                &user.pubkey(),
+                &operator_1.pubkey(),
+                &presale_pubkey,
+                &presale::ID,
+            ),
+            params: UpdateOperatorParams {
+                max_escrow_count: 2,
+                ..Default::default()
+            },
+        },
+    );
+
+    handle_create_permissioned_escrow_with_operator(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
             presale: presale_pubkey,
+            owner: Rc::clone(&user_1),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator_1),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap,
+        },
+    );
+
+    let operator_state: Operator = lite_svm
+        .get_deserialized_zc_account(&derive_operator(
+            &user.pubkey(),
+            &operator_1.pubkey(),
+            &presale_pubkey,
+            &presale::ID,
+        ))
+        .unwrap();
+    assert_eq!(operator_state.escrow_count, 2);
+
+    // Quota can't be lowered below the escrows already created
+    let err = handle_update_operator_err(
+        &mut lite_svm,
+        HandleUpdateOperatorArgs {
             owner: Rc::clone(&user),
-            proof: tree_node.proof.unwrap(),
-            merkle_root_config: merkle_tree
-                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
-            registry_index: tree_node.registry_index,
-            max_deposit_cap: tree_node.deposit_cap,
+            operator_pda: derive_operator(
+                &user.pubkey(),
+                &operator_1.pubkey(),
+                &presale_pubkey,
+                &presale::ID,
+            ),
+            params: UpdateOperatorParams {
+                max_escrow_count: 1,
+                ..Default::default()
+            },
         },
     );
+
+    let expected_err = presale::errors::PresaleError::InvalidOperatorParams;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
 }
 
 #[test]
-fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry_index_same_tree() {
+fn test_initialize_permissioned_with_authority_escrow_with_expired_operator() {
     let mut setup_context = SetupContext::initialize();
     let mint = setup_context.setup_mint(
         DEFAULT_BASE_TOKEN_DECIMALS,
@@ -561,124 +744,79 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry
     );
     let SetupContext { mut lite_svm, user } = setup_context;
 
-    let user_1 = Rc::new(Keypair::new());
-    let user_1_pubkey = user_1.pubkey();
-    transfer_sol(
-        &mut lite_svm,
-        Rc::clone(&user),
-        user_1_pubkey,
-        2 * LAMPORTS_PER_SOL,
-    );
-
     let quote = anchor_spl::token::spl_token::native_mint::ID;
 
     let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
-        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale_with_multiple_presale_registries(
+        handle_create_predefined_permissioned_with_authority_fixed_price_presale(
             &mut lite_svm,
             mint,
             quote,
             Rc::clone(&user),
         );
 
-    let presale_state: Presale = lite_svm
-        .get_deserialized_zc_account(&presale_pubkey)
-        .unwrap();
-
-    let whitelist_wallet = vec![
-        WhitelistWallet {
-            address: user.pubkey(),
-            registry_index: 0,
-            max_deposit_cap: presale_state
-                .presale_registries
-                .get(0)
-                .unwrap()
-                .buyer_maximum_deposit_cap,
-        },
-        WhitelistWallet {
-            address: user_1_pubkey,
-            registry_index: 1,
-            max_deposit_cap: presale_state
-                .presale_registries
-                .get(1)
-                .unwrap()
-                .buyer_maximum_deposit_cap,
-        },
-    ];
-
-    let merkle_tree = build_merkle_tree(whitelist_wallet, 0);
+    let clock: Clock = lite_svm.get_sysvar();
+    let current_timestamp = clock.unix_timestamp as u64;
 
-    handle_create_merkle_root_config(
+    let operator = Rc::new(Keypair::new());
+    let err = handle_create_operator_err(
         &mut lite_svm,
-        HandleCreateMerkleRootConfigArgs {
-            presale: presale_pubkey,
+        HandleCreateOperatorArgs {
             owner: Rc::clone(&user),
-            merkle_tree: &merkle_tree,
+            operator: operator.pubkey(),
+            params: CreateOperatorParams {
+                expiry_timestamp: current_timestamp,
+                ..Default::default()
+            },
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user.pubkey());
+    let expected_err = presale::errors::PresaleError::InvalidOperatorParams;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
 
-    handle_create_permissioned_escrow_with_merkle_proof(
+    handle_create_operator(
         &mut lite_svm,
-        HandleCreatePermissionedEscrowWithMerkleProofArgs {
-            presale: presale_pubkey,
+        HandleCreateOperatorArgs {
             owner: Rc::clone(&user),
-            proof: tree_node_0.proof.unwrap(),
-            merkle_root_config: merkle_tree
-                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
-            registry_index: tree_node_0.registry_index,
-            max_deposit_cap: tree_node_0.deposit_cap,
+            operator: operator.pubkey(),
+            params: CreateOperatorParams {
+                expiry_timestamp: current_timestamp + 1,
+                ..Default::default()
+            },
         },
     );
 
-    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey);
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    warp_time(&mut lite_svm, current_timestamp + 2);
 
-    handle_create_permissioned_escrow_with_merkle_proof(
+    let err = handle_create_permissioned_escrow_with_operator_err(
         &mut lite_svm,
-        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+        HandleCreatePermissionedEscrowWithOperatorArgs {
             presale: presale_pubkey,
-            owner: Rc::clone(&user_1),
-            proof: tree_node_1.proof.unwrap(),
-            merkle_root_config: merkle_tree
-                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
-            registry_index: tree_node_1.registry_index,
-            max_deposit_cap: tree_node_1.deposit_cap,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap: presale_state
+                .presale_registries
+                .get(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX as usize)
+                .unwrap()
+                .buyer_maximum_deposit_cap,
         },
     );
 
-    let presale = derive_presale(&mint, &quote, &user.pubkey(), &presale::ID);
-    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
-    assert_eq!(presale_state.total_escrow, 2);
-
-    let presale_registry = presale_state.presale_registries.get(0).unwrap();
-    assert_eq!(presale_registry.total_escrow, 1);
-
-    let presale_registry_1 = presale_state.presale_registries.get(1).unwrap();
-    assert_eq!(presale_registry_1.total_escrow, 1);
-
-    let escrow_0 = derive_escrow(
-        &presale,
-        &user.pubkey(),
-        tree_node_0.registry_index,
-        &presale::ID,
-    );
-
-    let escrow_1 = derive_escrow(
-        &presale,
-        &user_1_pubkey,
-        tree_node_1.registry_index,
-        &presale::ID,
-    );
-
-    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow_0).unwrap();
-    assert_eq!(escrow_state.registry_index, tree_node_0.registry_index);
-
-    let escrow_state_1: Escrow = lite_svm.get_deserialized_zc_account(&escrow_1).unwrap();
-    assert_eq!(escrow_state_1.registry_index, tree_node_1.registry_index);
+    let expected_err = presale::errors::PresaleError::OperatorExpired;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
 }
 
 #[test]
-fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry_index_and_tree() {
+fn test_initialize_permissioned_with_merkle_proof_escrow() {
     let mut setup_context = SetupContext::initialize();
     let mint = setup_context.setup_mint(
         DEFAULT_BASE_TOKEN_DECIMALS,
@@ -686,40 +824,335 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry
     );
     let SetupContext { mut lite_svm, user } = setup_context;
 
-    let user_1 = Rc::new(Keypair::new());
-    let user_1_pubkey = user_1.pubkey();
-    transfer_sol(
-        &mut lite_svm,
-        Rc::clone(&user),
-        user_1_pubkey,
-        2 * LAMPORTS_PER_SOL,
-    );
-
     let quote = anchor_spl::token::spl_token::native_mint::ID;
 
     let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
-        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale_with_multiple_presale_registries(
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
             &mut lite_svm,
             mint,
             quote,
             Rc::clone(&user),
         );
 
-    let presale_state: Presale = lite_svm
+    let presale: Presale = lite_svm
         .get_deserialized_zc_account(&presale_pubkey)
         .unwrap();
 
-    let whitelist_wallet_0 = vec![WhitelistWallet {
+    let whitelist_wallet = vec![WhitelistWallet {
         address: user.pubkey(),
         registry_index: 0,
-        max_deposit_cap: presale_state
+        max_deposit_cap: presale
             .presale_registries
             .get(0)
             .unwrap()
             .buyer_maximum_deposit_cap,
     }];
 
-    let merkle_tree_0 = build_merkle_tree(whitelist_wallet_0, 0);
+    let merkle_tree = build_merkle_tree(whitelist_wallet, 0);
+
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree,
+        },
+    );
+
+    let tree_node = merkle_tree.get_node(&user.pubkey()).unwrap();
+
+    handle_create_permissioned_escrow_with_merkle_proof(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            proof: tree_node.proof.unwrap(),
+            merkle_root_config: merkle_tree
+                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
+            registry_index: tree_node.registry_index,
+            max_deposit_cap: tree_node.deposit_cap,
+        },
+    );
+}
+
+#[test]
+fn test_initialize_permissioned_with_merkle_proof_escrow_with_revoked_config() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let presale: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let presale_registry = presale.presale_registries.get(0).unwrap();
+
+    // Tree published with wrong cap
+    let merkle_tree_v0 = build_merkle_tree(
+        vec![WhitelistWallet {
+            address: user.pubkey(),
+            registry_index: 0,
+            max_deposit_cap: presale_registry.buyer_minimum_deposit_cap,
+        }],
+        0,
+    );
+
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree_v0,
+        },
+    );
+
+    let merkle_root_config_v0 =
+        merkle_tree_v0.get_merkle_root_config_pubkey(presale_pubkey, &presale::ID);
+
+    handle_revoke_merkle_root_config(
+        &mut lite_svm,
+        HandleRevokeMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            merkle_root_config: merkle_root_config_v0,
+            creator: Rc::clone(&user),
+        },
+    );
+
+    let tree_node = merkle_tree_v0.get_node(&user.pubkey()).unwrap();
+
+    let err = handle_create_permissioned_escrow_with_merkle_proof_err(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            proof: tree_node.proof.unwrap(),
+            merkle_root_config: merkle_root_config_v0,
+            registry_index: tree_node.registry_index,
+            max_deposit_cap: tree_node.deposit_cap,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::MerkleRootConfigRevoked;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Superseding version with the corrected cap
+    let merkle_tree_v1 = build_merkle_tree(
+        vec![WhitelistWallet {
+            address: user.pubkey(),
+            registry_index: 0,
+            max_deposit_cap: presale_registry.buyer_maximum_deposit_cap,
+        }],
+        1,
+    );
+
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree_v1,
+        },
+    );
+
+    let tree_node = merkle_tree_v1.get_node(&user.pubkey()).unwrap();
+
+    handle_create_permissioned_escrow_with_merkle_proof(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            proof: tree_node.proof.unwrap(),
+            merkle_root_config: merkle_tree_v1
+                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
+            registry_index: tree_node.registry_index,
+            max_deposit_cap: tree_node.deposit_cap,
+        },
+    );
+}
+
+#[test]
+fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry_index_same_tree() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_1 = Rc::new(Keypair::new());
+    let user_1_pubkey = user_1.pubkey();
+    transfer_sol(
+        &mut lite_svm,
+        Rc::clone(&user),
+        user_1_pubkey,
+        2 * LAMPORTS_PER_SOL,
+    );
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale_with_multiple_presale_registries(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let whitelist_wallet = vec![
+        WhitelistWallet {
+            address: user.pubkey(),
+            registry_index: 0,
+            max_deposit_cap: presale_state
+                .presale_registries
+                .get(0)
+                .unwrap()
+                .buyer_maximum_deposit_cap,
+        },
+        WhitelistWallet {
+            address: user_1_pubkey,
+            registry_index: 1,
+            max_deposit_cap: presale_state
+                .presale_registries
+                .get(1)
+                .unwrap()
+                .buyer_maximum_deposit_cap,
+        },
+    ];
+
+    let merkle_tree = build_merkle_tree(whitelist_wallet, 0);
+
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree,
+        },
+    );
+
+    let tree_node_0 = merkle_tree.get_node(&user.pubkey()).unwrap();
+
+    handle_create_permissioned_escrow_with_merkle_proof(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            proof: tree_node_0.proof.unwrap(),
+            merkle_root_config: merkle_tree
+                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
+            registry_index: tree_node_0.registry_index,
+            max_deposit_cap: tree_node_0.deposit_cap,
+        },
+    );
+
+    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey).unwrap();
+
+    handle_create_permissioned_escrow_with_merkle_proof(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user_1),
+            proof: tree_node_1.proof.unwrap(),
+            merkle_root_config: merkle_tree
+                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
+            registry_index: tree_node_1.registry_index,
+            max_deposit_cap: tree_node_1.deposit_cap,
+        },
+    );
+
+    let presale = derive_presale(&mint, &quote, &user.pubkey(), &presale::ID);
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+    assert_eq!(presale_state.total_escrow, 2);
+
+    let presale_registry = presale_state.presale_registries.get(0).unwrap();
+    assert_eq!(presale_registry.total_escrow, 1);
+
+    let presale_registry_1 = presale_state.presale_registries.get(1).unwrap();
+    assert_eq!(presale_registry_1.total_escrow, 1);
+
+    let escrow_0 = derive_escrow(
+        &presale,
+        &user.pubkey(),
+        tree_node_0.registry_index,
+        &presale::ID,
+    );
+
+    let escrow_1 = derive_escrow(
+        &presale,
+        &user_1_pubkey,
+        tree_node_1.registry_index,
+        &presale::ID,
+    );
+
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow_0).unwrap();
+    assert_eq!(escrow_state.registry_index, tree_node_0.registry_index);
+
+    let escrow_state_1: Escrow = lite_svm.get_deserialized_zc_account(&escrow_1).unwrap();
+    assert_eq!(escrow_state_1.registry_index, tree_node_1.registry_index);
+}
+
+#[test]
+fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry_index_and_tree() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_1 = Rc::new(Keypair::new());
+    let user_1_pubkey = user_1.pubkey();
+    transfer_sol(
+        &mut lite_svm,
+        Rc::clone(&user),
+        user_1_pubkey,
+        2 * LAMPORTS_PER_SOL,
+    );
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale_with_multiple_presale_registries(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let whitelist_wallet_0 = vec![WhitelistWallet {
+        address: user.pubkey(),
+        registry_index: 0,
+        max_deposit_cap: presale_state
+            .presale_registries
+            .get(0)
+            .unwrap()
+            .buyer_maximum_deposit_cap,
+    }];
+
+    let merkle_tree_0 = build_merkle_tree(whitelist_wallet_0, 0);
 
     handle_create_merkle_root_config(
         &mut lite_svm,
@@ -730,7 +1163,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry
         },
     );
 
-    let tree_node_0 = merkle_tree_0.get_node(&user.pubkey());
+    let tree_node_0 = merkle_tree_0.get_node(&user.pubkey()).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -766,7 +1199,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_different_registry
         },
     );
 
-    let tree_node_1 = merkle_tree_1.get_node(&user_1_pubkey);
+    let tree_node_1 = merkle_tree_1.get_node(&user_1_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -887,7 +1320,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_invalid_deposit_ca
 
     let merkle_root_config =
         merkle_tree.get_merkle_root_config_pubkey(presale_pubkey, &presale::ID);
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     let err_0 = handle_create_permissioned_escrow_with_merkle_proof_err(
         &mut lite_svm,
@@ -901,7 +1334,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_invalid_deposit_ca
         },
     );
 
-    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey);
+    let tree_node_1 = merkle_tree.get_node(&user_1_pubkey).unwrap();
 
     let err_1 = handle_create_permissioned_escrow_with_merkle_proof_err(
         &mut lite_svm,
@@ -915,7 +1348,7 @@ fn test_initialize_permissioned_with_merkle_proof_escrow_with_invalid_deposit_ca
         },
     );
 
-    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey);
+    let tree_node_2 = merkle_tree.get_node(&user_2_pubkey).unwrap();
 
     let err_2 = handle_create_permissioned_escrow_with_merkle_proof_err(
         &mut lite_svm,
@@ -995,3 +1428,360 @@ fn test_initialize_permissionless_escrow() {
         presale_registry.buyer_maximum_deposit_cap
     );
 }
+
+#[test]
+fn test_initialize_escrow_with_mixed_registry_whitelist_mode() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_fcfs_presale_args_wrapper(
+        mint,
+        quote,
+        &lite_svm,
+        WhitelistMode::PermissionWithAuthority,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    // Registry 0 is public, registry 1 is whitelisted by the creator
+    let presale_registries = &mut wrapper.args.params.presale_registries;
+    presale_registries[0].presale_supply /= 2;
+    let mut permissioned_registry = presale_registries[0];
+    presale_registries[0].override_whitelist_mode = BoolType::True.into();
+    presale_registries[0].whitelist_mode = WhitelistMode::Permissionless.into();
+    presale_registries[0].buyer_minimum_deposit_cap = 1_000;
+    permissioned_registry.buyer_minimum_deposit_cap = 1;
+    presale_registries.push(permissioned_registry);
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote, &user_pubkey, &presale::ID);
+
+    handle_create_permissionless_escrow(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let operator = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let whitelisted_user = Rc::new(Keypair::new());
+    lite_svm
+        .airdrop(&whitelisted_user.pubkey(), LAMPORTS_PER_SOL)
+        .unwrap();
+
+    // Permissionless registry doesn't accept escrow created by operator
+    let err = handle_create_permissioned_escrow_with_operator_err(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&whitelisted_user),
+            vault_owner: user_pubkey,
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap: presale_state.presale_maximum_cap,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidPresaleWhitelistMode;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_create_permissioned_escrow_with_operator(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&whitelisted_user),
+            vault_owner: user_pubkey,
+            operator: Rc::clone(&operator),
+            registry_index: 1,
+            max_deposit_cap: presale_state.presale_maximum_cap,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert_eq!(presale_state.total_escrow, 2);
+    assert_eq!(presale_state.presale_registries[0].total_escrow, 1);
+    assert_eq!(presale_state.presale_registries[1].total_escrow, 1);
+}
+
+#[test]
+fn test_initialize_permissioned_with_signature_escrow() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_signature_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let operator = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let max_deposit_cap = presale_state
+        .presale_registries
+        .get(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX as usize)
+        .unwrap()
+        .buyer_maximum_deposit_cap;
+
+    let clock: Clock = lite_svm.get_sysvar();
+    let current_timestamp = clock.unix_timestamp as u64;
+
+    // Operator only sign the voucher, the escrow owner submits the transaction
+    let args = HandleCreatePermissionedEscrowWithSignatureArgs {
+        presale: presale_pubkey,
+        owner: Rc::clone(&user),
+        vault_owner: user.pubkey(),
+        operator: Rc::clone(&operator),
+        registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        max_deposit_cap,
+        expiry: current_timestamp,
+        nonce: 0,
+    };
+    handle_create_permissioned_escrow_with_signature(&mut lite_svm, args.clone());
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.deposit_max_cap, max_deposit_cap);
+
+    let signature_voucher_receipt =
+        derive_signature_voucher_receipt(&presale_pubkey, &user.pubkey(), 0, &presale::ID);
+    assert!(lite_svm.get_account(&signature_voucher_receipt).is_some());
+
+    // Consumed voucher can't be replayed once the escrow is closed
+    handle_close_escrow(
+        &mut lite_svm,
+        HandleCloseEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+    assert!(lite_svm.get_account(&escrow).is_none());
+
+    let err = handle_create_permissioned_escrow_with_signature_err(&mut lite_svm, args.clone());
+    assert!(err.meta.logs.iter().any(|log| log.contains("already in use")));
+    assert!(lite_svm.get_account(&escrow).is_none());
+
+    // Voucher with a new nonce
+    handle_create_permissioned_escrow_with_signature(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithSignatureArgs { nonce: 1, ..args },
+    );
+    assert!(lite_svm.get_account(&escrow).is_some());
+}
+
+#[test]
+fn test_initialize_permissioned_with_signature_escrow_with_invalid_voucher() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_1 = Rc::new(Keypair::new());
+    transfer_sol(
+        &mut lite_svm,
+        Rc::clone(&user),
+        user_1.pubkey(),
+        LAMPORTS_PER_SOL,
+    );
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_signature_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let operator = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
+        },
+    );
+
+    let other_creator_operator = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user_1),
+            operator: other_creator_operator.pubkey(),
+            params: CreateOperatorParams::default(),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let max_deposit_cap = presale_state
+        .presale_registries
+        .get(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX as usize)
+        .unwrap()
+        .buyer_maximum_deposit_cap;
+
+    let clock: Clock = lite_svm.get_sysvar();
+    let current_timestamp = clock.unix_timestamp as u64;
+
+    let args = HandleCreatePermissionedEscrowWithSignatureArgs {
+        presale: presale_pubkey,
+        owner: Rc::clone(&user),
+        vault_owner: user.pubkey(),
+        operator: Rc::clone(&operator),
+        registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        max_deposit_cap,
+        expiry: current_timestamp - 1,
+        nonce: 0,
+    };
+
+    // Expired voucher
+    let err = handle_create_permissioned_escrow_with_signature_err(&mut lite_svm, args.clone());
+
+    let expected_err = presale::errors::PresaleError::SignatureVoucherExpired;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Voucher signed by operator of another creator
+    let err = handle_create_permissioned_escrow_with_signature_err(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithSignatureArgs {
+            vault_owner: user_1.pubkey(),
+            operator: Rc::clone(&other_creator_operator),
+            expiry: current_timestamp,
+            ..args
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidOperator;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_initialize_permissionless_escrow_in_non_default_registry() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_fcfs_presale_args_wrapper(
+        mint,
+        quote,
+        &lite_svm,
+        WhitelistMode::PermissionWithAuthority,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    // Registry 0 is whitelisted by the creator, registry 1 is public
+    let presale_registries = &mut wrapper.args.params.presale_registries;
+    presale_registries[0].presale_supply /= 2;
+    let mut permissionless_registry = presale_registries[0];
+    permissionless_registry.override_whitelist_mode = BoolType::True.into();
+    permissionless_registry.whitelist_mode = WhitelistMode::Permissionless.into();
+    presale_registries.push(permissionless_registry);
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote, &user_pubkey, &presale::ID);
+
+    let err = handle_create_permissionless_escrow_err(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: 0,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidPresaleWhitelistMode;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_create_permissionless_escrow(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: 1,
+        },
+    );
+
+    let escrow = derive_escrow(&presale_pubkey, &user_pubkey, 1, &presale::ID);
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.registry_index, 1);
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert_eq!(presale_state.presale_registries[0].total_escrow, 0);
+    assert_eq!(presale_state.presale_registries[1].total_escrow, 1);
+}
@@ -0,0 +1,211 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::signer::Signer;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use helpers::*;
+use presale::{Escrow, Presale, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX};
+use std::rc::Rc;
+
+#[test]
+fn test_create_permissionless_escrow_with_denylisted_wallet() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let denied_user = setup_context.create_user();
+    let other_user = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    // Escrow isn't created yet, but its PDA is required
+    let denied_user_escrow = derive_escrow(
+        &presale_pubkey,
+        &denied_user.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+
+    handle_add_to_denylist(
+        &mut lite_svm,
+        HandleDenylistArgs {
+            presale: presale_pubkey,
+            wallet: denied_user.pubkey(),
+            owner: Rc::clone(&user),
+            escrows: vec![denied_user_escrow],
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert!(presale_state.is_denylist_enabled());
+
+    let err = handle_create_permissionless_escrow_err(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&denied_user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::WalletDenylisted;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_create_permissionless_escrow(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&other_user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    handle_remove_from_denylist(
+        &mut lite_svm,
+        HandleDenylistArgs {
+            presale: presale_pubkey,
+            wallet: denied_user.pubkey(),
+            owner: Rc::clone(&user),
+            escrows: vec![denied_user_escrow],
+        },
+    );
+
+    handle_create_permissionless_escrow(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&denied_user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+}
+
+#[test]
+fn test_deposit_with_escrow_created_before_wallet_denylisted() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let deposit_args = HandleEscrowDepositArgs {
+        presale: presale_pubkey,
+        owner: Rc::clone(&user),
+        max_amount: 1_000_000,
+        registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+    };
+
+    handle_escrow_deposit(&mut lite_svm, deposit_args.clone());
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+
+    let denylist_args = HandleDenylistArgs {
+        presale: presale_pubkey,
+        wallet: user.pubkey(),
+        owner: Rc::clone(&user),
+        escrows: vec![escrow],
+    };
+
+    handle_add_to_denylist(&mut lite_svm, denylist_args.clone());
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert!(escrow_state.is_denied());
+
+    let err = handle_escrow_deposit_err(&mut lite_svm, deposit_args.clone());
+
+    let expected_err = presale::errors::PresaleError::WalletDenylisted;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_remove_from_denylist(&mut lite_svm, denylist_args);
+
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert!(!escrow_state.is_denied());
+
+    handle_escrow_deposit(&mut lite_svm, deposit_args);
+}
+
+#[test]
+fn test_add_to_denylist_with_escrow_of_other_wallet() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let denied_user = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    handle_create_permissionless_escrow(
+        &mut lite_svm,
+        HandleCreatePermissionlessEscrowArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let err = handle_add_to_denylist_err(
+        &mut lite_svm,
+        HandleDenylistArgs {
+            presale: presale_pubkey,
+            wallet: denied_user.pubkey(),
+            owner: Rc::clone(&user),
+            escrows: vec![derive_escrow(
+                &presale_pubkey,
+                &user.pubkey(),
+                DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+                &presale::ID,
+            )],
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidDenylistEscrow;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Escrow of every permissionless registry is required, so none can be missed
+    let err = handle_add_to_denylist_err(
+        &mut lite_svm,
+        HandleDenylistArgs {
+            presale: presale_pubkey,
+            wallet: denied_user.pubkey(),
+            owner: Rc::clone(&user),
+            escrows: vec![],
+        },
+    );
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
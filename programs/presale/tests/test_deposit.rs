@@ -9,6 +9,7 @@ use anchor_spl::{
     token_interface::TokenAccount,
 };
 use helpers::*;
+use merkle_tree::{config_merkle_tree::ConfigMerkleTree, tree_node::TreeNode};
 use presale::{
     calculate_deposit_fee_included_amount, DepositFeeIncludedCalculation, Escrow,
     FcfsPresaleHandler, FixedPricePresaleHandler, Presale, PresaleProgress, PresaleRegistryArgs,
@@ -481,7 +482,7 @@ fn test_deposit_over_escrow_max_deposit_cap() {
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -635,7 +636,7 @@ fn test_deposit_2022_with_fee() {
         (deposit_amount_0, Rc::clone(&user)),
         (deposit_amount_1, Rc::clone(&user_1)),
     ] {
-        let tree_node = merkle_tree.get_node(&user.pubkey());
+        let tree_node = merkle_tree.get_node(&user.pubkey()).unwrap();
         handle_create_permissioned_escrow_with_merkle_proof(
             &mut lite_svm,
             HandleCreatePermissionedEscrowWithMerkleProofArgs {
@@ -936,3 +937,480 @@ fn test_deposit_fcfs_presale_with_end_earlier_disabled() {
         after_presale_state.vesting_end_time
     );
 }
+
+#[test]
+fn test_deposit_outside_registry_deposit_window() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_pubkey = user.pubkey();
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let mut wrapper = create_default_fcfs_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    let presale_start_time = wrapper.args.params.presale_params.presale_start_time;
+    let registry = &mut wrapper.args.params.presale_registries[0];
+    registry.deposit_start_time = presale_start_time + 30;
+    registry.deposit_end_time = presale_start_time + 90;
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+    let deposit_amount = LAMPORTS_PER_SOL;
+
+    let expected_err = presale::errors::PresaleError::PresaleRegistryNotOpen;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+
+    let err = handle_escrow_deposit_err(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    warp_time(&mut lite_svm, presale_start_time + 30);
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    warp_time(&mut lite_svm, presale_start_time + 90);
+
+    let err = handle_escrow_deposit_err(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_deposit_capped_by_registry_maximum_cap() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_pubkey = user.pubkey();
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let mut wrapper = create_default_fcfs_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    let registry_maximum_cap = LAMPORTS_PER_SOL / 2;
+    wrapper.args.params.presale_registries[0].registry_maximum_cap = registry_maximum_cap;
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: LAMPORTS_PER_SOL,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let presale_registry = presale_state
+        .presale_registries
+        .get(usize::from(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX))
+        .unwrap();
+    assert_eq!(presale_registry.total_deposit, registry_maximum_cap);
+    assert_eq!(presale_state.total_deposit, registry_maximum_cap);
+
+    let err = handle_escrow_deposit_err(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: LAMPORTS_PER_SOL,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::ZeroTokenAmount;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_deposit_within_anti_snipe_window_extends_presale_end_time() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_pubkey = user.pubkey();
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let mut wrapper = create_default_prorata_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    let anti_snipe_window = 30;
+    let anti_snipe_extension = 20;
+    let anti_snipe_max_total_extension = 30;
+
+    let presale_args = &mut wrapper.args.params.presale_params;
+    presale_args.anti_snipe_window = anti_snipe_window;
+    presale_args.anti_snipe_extension = anti_snipe_extension;
+    presale_args.anti_snipe_max_total_extension = anti_snipe_max_total_extension;
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    let initial_presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let deposit_amount = initial_presale_state
+        .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+        .unwrap()
+        .buyer_minimum_deposit_cap;
+
+    let deposit_at = |lite_svm: &mut litesvm::LiteSVM, timestamp: u64| -> Presale {
+        warp_time(lite_svm, timestamp);
+        handle_escrow_deposit(
+            lite_svm,
+            HandleEscrowDepositArgs {
+                presale: presale_pubkey,
+                owner: Rc::clone(&user),
+                max_amount: deposit_amount,
+                registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            },
+        );
+        lite_svm
+            .get_deserialized_zc_account(&presale_pubkey)
+            .unwrap()
+    };
+
+    // Deposit before the anti-snipe window doesn't extend the presale
+    let presale_state = deposit_at(
+        &mut lite_svm,
+        initial_presale_state.presale_end_time - u64::from(anti_snipe_window) - 1,
+    );
+    assert_eq!(
+        presale_state.presale_end_time,
+        initial_presale_state.presale_end_time
+    );
+
+    // Deposit within the anti-snipe window extends the presale, and shift the lock and vest timings
+    let presale_state = deposit_at(&mut lite_svm, initial_presale_state.presale_end_time - 1);
+    let extension = u64::from(anti_snipe_extension);
+    assert_eq!(
+        presale_state.presale_end_time,
+        initial_presale_state.presale_end_time + extension
+    );
+    assert_eq!(
+        presale_state.vesting_start_time,
+        initial_presale_state.vesting_start_time + extension
+    );
+    assert_eq!(
+        presale_state.vesting_end_time,
+        initial_presale_state.vesting_end_time + extension
+    );
+    assert_eq!(
+        presale_state.immediate_release_timestamp,
+        initial_presale_state.immediate_release_timestamp + extension
+    );
+
+    // Extension is bounded by the maximum total extension
+    let presale_state = deposit_at(&mut lite_svm, presale_state.presale_end_time - 1);
+    let max_total_extension = u64::from(anti_snipe_max_total_extension);
+    assert_eq!(
+        presale_state.presale_end_time,
+        initial_presale_state.presale_end_time + max_total_extension
+    );
+    assert_eq!(
+        presale_state.anti_snipe_total_extension,
+        anti_snipe_max_total_extension
+    );
+
+    let presale_state = deposit_at(&mut lite_svm, presale_state.presale_end_time - 1);
+    assert_eq!(
+        presale_state.presale_end_time,
+        initial_presale_state.presale_end_time + max_total_extension
+    );
+}
+
+#[test]
+fn test_deposit_within_anti_snipe_extension_into_registry_closing_with_presale() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let user_pubkey = user.pubkey();
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let mut wrapper = create_default_prorata_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    let anti_snipe_window = 30;
+    let anti_snipe_extension = 20;
+
+    let presale_args = &mut wrapper.args.params.presale_params;
+    presale_args.anti_snipe_window = anti_snipe_window;
+    presale_args.anti_snipe_extension = anti_snipe_extension;
+    presale_args.anti_snipe_max_total_extension = anti_snipe_extension;
+
+    // Registry deposit window closes along with the presale
+    let presale_end_time = presale_args.presale_end_time;
+    wrapper.args.params.presale_registries[0].deposit_end_time = presale_end_time;
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let deposit_amount = presale_state
+        .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+        .unwrap()
+        .buyer_minimum_deposit_cap;
+
+    let deposit_args = HandleEscrowDepositArgs {
+        presale: presale_pubkey,
+        owner: Rc::clone(&user),
+        max_amount: deposit_amount,
+        registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+    };
+
+    warp_time(&mut lite_svm, presale_end_time - 1);
+    handle_escrow_deposit(&mut lite_svm, deposit_args.clone());
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let extended_presale_end_time = presale_end_time + u64::from(anti_snipe_extension);
+    assert_eq!(presale_state.presale_end_time, extended_presale_end_time);
+    assert_eq!(
+        presale_state
+            .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+            .unwrap()
+            .deposit_end_time,
+        extended_presale_end_time
+    );
+
+    // Deposit within the extension is accepted by the registry
+    warp_time(&mut lite_svm, presale_end_time);
+    handle_escrow_deposit(&mut lite_svm, deposit_args);
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.total_deposit, deposit_amount * 2);
+}
+
+#[test]
+fn test_deposit_and_withdraw_fixed_price_presale_with_price_discount() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let user_1 = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+    let user_1_pubkey = user_1.pubkey();
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let deposit_cap = presale_state.presale_registries[0].buyer_maximum_deposit_cap;
+
+    // User 1 buys at 20% discount
+    let price_discount_bps = 2_000;
+    let tree_nodes = vec![
+        TreeNode::new(user_pubkey, 0, deposit_cap),
+        TreeNode {
+            price_discount_bps: Some(price_discount_bps),
+            ..TreeNode::new(user_1_pubkey, 0, deposit_cap)
+        },
+    ];
+    let merkle_tree = ConfigMerkleTree::new(tree_nodes, 0).unwrap();
+    let merkle_root_config =
+        merkle_tree.get_merkle_root_config_pubkey(presale_pubkey, &presale::ID);
+
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree,
+        },
+    );
+
+    for keypair in [Rc::clone(&user), Rc::clone(&user_1)] {
+        let tree_node = merkle_tree.get_node(&keypair.pubkey()).unwrap();
+        handle_create_permissioned_escrow_with_merkle_proof_and_terms_override(
+            &mut lite_svm,
+            HandleCreatePermissionedEscrowWithMerkleProofArgs {
+                presale: presale_pubkey,
+                owner: Rc::clone(&keypair),
+                merkle_root_config,
+                registry_index: tree_node.registry_index,
+                max_deposit_cap: tree_node.deposit_cap,
+                proof: tree_node.proof.clone().unwrap(),
+            },
+            tree_node.get_terms_override(),
+        );
+    }
+
+    let fixed_price_handler = decode_presale_mode_raw_data::<FixedPricePresaleHandler>(
+        &presale_state.presale_mode_raw_data,
+    );
+    let escrow_q_price =
+        fixed_price_handler.q_price * u128::from(10_000 - price_discount_bps) / 10_000;
+
+    // Amount rounded down to whole base lamports at the escrow token price
+    let quote_token_without_surplus = |amount: u64| -> u64 {
+        let base_token_amount = (u128::from(amount) * SCALE_MULTIPLIER) / escrow_q_price;
+        (base_token_amount * escrow_q_price)
+            .div_ceil(SCALE_MULTIPLIER)
+            .try_into()
+            .unwrap()
+    };
+
+    // 1. Discounted deposit is suggested at the discounted price
+    let deposit_amount = presale_state.presale_maximum_cap / 2 + 7;
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user_1),
+            max_amount: deposit_amount,
+            registry_index: 0,
+        },
+    );
+
+    let escrow_1 = derive_escrow(&presale_pubkey, &user_1_pubkey, 0, &presale::ID);
+    let escrow_state_1: Escrow = lite_svm.get_deserialized_zc_account(&escrow_1).unwrap();
+    assert_eq!(
+        escrow_state_1.total_deposit,
+        quote_token_without_surplus(deposit_amount)
+    );
+
+    // 2. Discounted withdraw is suggested at the discounted price
+    let withdraw_amount = escrow_state_1.total_deposit / 3;
+    handle_escrow_withdraw(
+        &mut lite_svm,
+        HandleEscrowWithdrawArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user_1),
+            amount: withdraw_amount,
+            registry_index: 0,
+        },
+    );
+
+    let before_escrow_state_1 = escrow_state_1;
+    let escrow_state_1: Escrow = lite_svm.get_deserialized_zc_account(&escrow_1).unwrap();
+    assert_eq!(
+        before_escrow_state_1.total_deposit - escrow_state_1.total_deposit,
+        quote_token_without_surplus(withdraw_amount)
+    );
+
+    // 3. Presale maximum cap bounds the quote token raised. Price discount bonus doesn't consume the cap.
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_maximum_cap,
+            registry_index: 0,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let presale_registry = &presale_state.presale_registries[0];
+    assert!(presale_registry.total_price_discount_bonus > 0);
+    assert!(presale_state.total_deposit <= presale_state.presale_maximum_cap);
+    assert!(
+        presale_state.presale_maximum_cap - presale_state.total_deposit
+            < presale_registry.total_price_discount_bonus
+    );
+    assert!(
+        presale_state.total_deposit + presale_registry.total_price_discount_bonus
+            > presale_state.presale_maximum_cap
+    );
+}
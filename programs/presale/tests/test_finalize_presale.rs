@@ -0,0 +1,244 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use helpers::*;
+use litesvm::LiteSVM;
+use mock_finalization_callback::Record;
+use presale::{Presale, PresaleProgress, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX};
+use std::rc::Rc;
+
+fn setup_presale_with_finalization_callback(
+    lite_svm: &mut LiteSVM,
+    mint: Pubkey,
+    user: Rc<Keypair>,
+) -> Pubkey {
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale_with_finalization_callback(
+            lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+            mock_finalization_callback::ID,
+        );
+
+    handle_initialize_mock_finalization_callback_record(lite_svm, presale_pubkey, user);
+
+    presale_pubkey
+}
+
+#[test]
+fn test_finalize_presale_invoke_callback() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let presale_pubkey =
+        setup_presale_with_finalization_callback(&mut lite_svm, mint, Rc::clone(&user));
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    assert_eq!(
+        presale_state.finalization_callback_program,
+        mock_finalization_callback::ID
+    );
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    handle_finalize_presale(
+        &mut lite_svm,
+        HandleFinalizePresaleArgs {
+            presale: presale_pubkey,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert!(presale_state.is_finalized());
+
+    let record_pubkey = derive_mock_finalization_callback_record(&presale_pubkey);
+    let record: Record = lite_svm.get_deserialized_account(&record_pubkey).unwrap();
+
+    assert_eq!(record.finalize_count, 1);
+    assert_eq!(record.total_deposit, presale_state.total_deposit);
+    assert_eq!(
+        record.presale_progress,
+        u8::from(PresaleProgress::Completed)
+    );
+    assert_eq!(record.base_token_vault, presale_state.base_token_vault);
+    assert_eq!(record.quote_token_vault, presale_state.quote_token_vault);
+
+    // Finalize only once
+    let err = handle_finalize_presale_err(
+        &mut lite_svm,
+        HandleFinalizePresaleArgs {
+            presale: presale_pubkey,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleAlreadyFinalized;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Creator withdraw doesn't invoke the callback
+    handle_creator_withdraw_token(
+        &mut lite_svm,
+        HandleCreatorWithdrawTokenArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let record: Record = lite_svm.get_deserialized_account(&record_pubkey).unwrap();
+    assert_eq!(record.finalize_count, 1);
+}
+
+#[test]
+fn test_creator_withdraw_without_finalize_presale() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let presale_pubkey =
+        setup_presale_with_finalization_callback(&mut lite_svm, mint, Rc::clone(&user));
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    // Creator withdraw waits for the callback
+    let err = handle_creator_withdraw_token_err(
+        &mut lite_svm,
+        HandleCreatorWithdrawTokenArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleNotFinalized;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_finalize_presale(
+        &mut lite_svm,
+        HandleFinalizePresaleArgs {
+            presale: presale_pubkey,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    handle_creator_withdraw_token(
+        &mut lite_svm,
+        HandleCreatorWithdrawTokenArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert!(presale_state.has_creator_withdrawn());
+
+    let record_pubkey = derive_mock_finalization_callback_record(&presale_pubkey);
+    let record: Record = lite_svm.get_deserialized_account(&record_pubkey).unwrap();
+    assert_eq!(record.finalize_count, 1);
+}
+
+#[test]
+fn test_finalize_presale_when_presale_ongoing() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let presale_pubkey =
+        setup_presale_with_finalization_callback(&mut lite_svm, mint, Rc::clone(&user));
+
+    let err = handle_finalize_presale_err(
+        &mut lite_svm,
+        HandleFinalizePresaleArgs {
+            presale: presale_pubkey,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleNotEnded;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_finalize_presale_without_callback() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    let err = handle_finalize_presale_err(
+        &mut lite_svm,
+        HandleFinalizePresaleArgs {
+            presale: presale_pubkey,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidFinalizationCallbackProgram;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
@@ -13,7 +13,7 @@ use anchor_spl::token_interface::TokenAccount;
 use helpers::*;
 use litesvm::LiteSVM;
 use presale::{
-    FixedPricePresaleHandler, LockedVestingArgs, Presale, PresaleArgs, PresaleMode,
+    BoolType, FixedPricePresaleHandler, LockedVestingArgs, Presale, PresaleArgs, PresaleMode,
     PresaleRegistryArgs, WhitelistMode, MAXIMUM_DURATION_UNTIL_PRESALE,
     MAXIMUM_LOCK_AND_VEST_DURATION, MAXIMUM_PRESALE_DURATION, MAX_PRESALE_REGISTRY_COUNT,
     MINIMUM_PRESALE_DURATION, SCALE_MULTIPLIER,
@@ -663,7 +663,7 @@ fn test_initialize_permissionless_presale_vault_with_multiple_registries() {
         .params
         .locked_vesting_params;
 
-    let err = handle_initialize_presale_err(
+    handle_initialize_presale(
         &mut lite_svm,
         HandleInitializePresaleArgs {
             base_mint: mint,
@@ -677,11 +677,13 @@ fn test_initialize_permissionless_presale_vault_with_multiple_registries() {
         },
     );
 
-    let expected_err = presale::errors::PresaleError::MultiplePresaleRegistriesNotAllowed;
-    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
-    let err_str = format!("Error Number: {}.", err_code);
-
-    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+    // Every registry can be permissionless
+    let presale = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+    for idx in 0..MAX_PRESALE_REGISTRY_COUNT {
+        let whitelist_mode = presale_state.get_registry_whitelist_mode(idx).unwrap();
+        assert_eq!(whitelist_mode, WhitelistMode::Permissionless);
+    }
 }
 
 #[test]
@@ -1355,3 +1357,95 @@ fn test_initialize_presale_vault_with_fixed_token_price_with_multiple_registries
         initialized_registry_count
     );
 }
+
+#[test]
+fn test_initialize_presale_vault_with_multiple_permissionless_registries_override() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_fcfs_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::PermissionWithMerkleProof,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    // Permissionless registry alongside the merkle proof registry
+    let presale_registries = &mut wrapper.args.params.presale_registries;
+    presale_registries[0].presale_supply /= 2;
+    let mut permissionless_registry = presale_registries[0];
+    permissionless_registry.override_whitelist_mode = BoolType::True.into();
+    permissionless_registry.whitelist_mode = WhitelistMode::Permissionless.into();
+    presale_registries.push(permissionless_registry);
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    let presale = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+    let presale_state: Presale = lite_svm.get_deserialized_zc_account(&presale).unwrap();
+    assert_eq!(
+        presale_state.get_registry_whitelist_mode(0).unwrap(),
+        WhitelistMode::PermissionWithMerkleProof
+    );
+    assert_eq!(
+        presale_state.get_registry_whitelist_mode(1).unwrap(),
+        WhitelistMode::Permissionless
+    );
+}
+
+#[test]
+fn test_initialize_fixed_price_presale_vault_with_missing_registry_price() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_fixed_price_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::PermissionWithAuthority,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    // Registry 1 has its own price, but registry 2 doesn't
+    let fixed_price_params = &mut wrapper.fixed_point_params_wrapper.args.params;
+    fixed_price_params.registry_q_prices[0] = fixed_price_params.q_price;
+
+    let presale_registries = &mut wrapper
+        .presale_params_wrapper
+        .args
+        .params
+        .presale_registries;
+    presale_registries[0].presale_supply /= 3;
+    let registry = presale_registries[0];
+    presale_registries.push(registry);
+    presale_registries.push(registry);
+
+    let instructions = wrapper.to_instructions();
+    let err = process_transaction(&mut lite_svm, &instructions, Some(&user_pubkey), &[&user])
+        .unwrap_err();
+
+    let expected_err = presale::errors::PresaleError::InvalidTokenPrice;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
@@ -0,0 +1,250 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::signer::Signer;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_spl::token_interface::TokenAccount;
+use helpers::*;
+use presale::{Escrow, Presale, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX};
+use std::rc::Rc;
+
+#[test]
+fn test_deposit_and_withdraw_native() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let user_pubkey = user.pubkey();
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let deposit_amount = 1_000_000;
+
+    handle_escrow_deposit_native(
+        &mut lite_svm,
+        HandleEscrowDepositNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.total_deposit, deposit_amount);
+
+    let quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+    assert_eq!(
+        quote_vault.amount,
+        presale_state.total_deposit + presale_state.total_deposit_fee
+    );
+
+    let withdraw_amount = deposit_amount / 2;
+
+    handle_escrow_withdraw_native(
+        &mut lite_svm,
+        HandleEscrowWithdrawNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            amount: withdraw_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.total_deposit, deposit_amount - withdraw_amount);
+
+    let after_quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+    assert_eq!(
+        quote_vault.amount - after_quote_vault.amount,
+        withdraw_amount
+    );
+
+    // Temporary unwrap token account is closed
+    let native_unwrap_token = derive_native_unwrap_token(&user_pubkey, &presale::ID);
+    assert!(lite_svm.get_account(&native_unwrap_token).is_none());
+}
+
+#[test]
+fn test_withdraw_remaining_quote_native_failed_presale() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let user_pubkey = user.pubkey();
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_escrow_deposit_native(
+        &mut lite_svm,
+        HandleEscrowDepositNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap - 1,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    handle_withdraw_remaining_quote_native(
+        &mut lite_svm,
+        HandleWithdrawRemainingQuoteNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert!(escrow_state.is_remaining_quote_withdrawn());
+
+    let quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+    assert_eq!(quote_vault.amount, 0);
+}
+
+#[test]
+fn test_creator_withdraw_native() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_escrow_deposit_native(
+        &mut lite_svm,
+        HandleEscrowDepositNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    // Creator can't withdraw before presale completed
+    let err = handle_creator_withdraw_native_err(
+        &mut lite_svm,
+        HandleCreatorWithdrawNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleNotCompleted;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    handle_creator_withdraw_native(
+        &mut lite_svm,
+        HandleCreatorWithdrawNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert!(presale_state.has_creator_withdrawn());
+
+    // Only the deposit fee is left for creator_collect_fee
+    let quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+    assert_eq!(quote_vault.amount, presale_state.total_deposit_fee);
+}
+
+#[test]
+fn test_deposit_native_with_non_native_quote_mint() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let quote_mint = setup_context.setup_mint(6, 1_000_000_000 * 10u64.pow(6));
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    let err = handle_escrow_deposit_native_err(
+        &mut lite_svm,
+        HandleEscrowDepositNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: 1_000_000,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::QuoteMintNotNative;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
@@ -0,0 +1,381 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::{pubkey::Pubkey, signer::Signer};
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_spl::{associated_token::get_associated_token_address, token_interface::TokenAccount};
+use helpers::*;
+use litesvm::LiteSVM;
+use presale::{Escrow, Presale, QuoteMintConfig, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX};
+use std::rc::Rc;
+
+// 1 alternative quote mint token = 2 accounting unit
+const CONVERSION_RATIO_NUMERATOR: u64 = 2;
+const CONVERSION_RATIO_DENOMINATOR: u64 = 1;
+
+struct QuoteMintPresaleSetup {
+    lite_svm: LiteSVM,
+    user: Rc<anchor_client::solana_sdk::signature::Keypair>,
+    presale_pubkey: Pubkey,
+    alternative_quote_mint: Pubkey,
+}
+
+fn setup_presale_with_alternative_quote_mint() -> QuoteMintPresaleSetup {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let alternative_quote_mint = setup_context.setup_mint(6, 1_000_000_000 * 10u64.pow(6));
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    // Quote mint config can only be created before the presale starts
+    warp_time(&mut lite_svm, presale_state.presale_start_time - 1);
+
+    handle_create_quote_mint_config(
+        &mut lite_svm,
+        HandleCreateQuoteMintConfigArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            conversion_ratio_numerator: CONVERSION_RATIO_NUMERATOR,
+            conversion_ratio_denominator: CONVERSION_RATIO_DENOMINATOR,
+        },
+    );
+
+    warp_time(&mut lite_svm, presale_state.presale_start_time);
+
+    QuoteMintPresaleSetup {
+        lite_svm,
+        user,
+        presale_pubkey,
+        alternative_quote_mint,
+    }
+}
+
+fn get_quote_mint_config_vault_amount(
+    lite_svm: &LiteSVM,
+    presale: &Pubkey,
+    quote_mint: &Pubkey,
+) -> u64 {
+    let quote_mint_config = derive_quote_mint_config(presale, quote_mint, &presale::ID);
+    let quote_mint_config_state: QuoteMintConfig = lite_svm
+        .get_deserialized_zc_account(&quote_mint_config)
+        .unwrap();
+    let quote_token_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&quote_mint_config_state.quote_token_vault)
+        .unwrap();
+    quote_token_vault.amount
+}
+
+#[test]
+fn test_create_quote_mint_config_after_presale_started() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let alternative_quote_mint = setup_context.setup_mint(6, 1_000_000_000 * 10u64.pow(6));
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    let err = handle_create_quote_mint_config_err(
+        &mut lite_svm,
+        HandleCreateQuoteMintConfigArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            conversion_ratio_numerator: CONVERSION_RATIO_NUMERATOR,
+            conversion_ratio_denominator: CONVERSION_RATIO_DENOMINATOR,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleAlreadyStarted;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_deposit_and_withdraw_with_quote_mint() {
+    let QuoteMintPresaleSetup {
+        mut lite_svm,
+        user,
+        presale_pubkey,
+        alternative_quote_mint,
+    } = setup_presale_with_alternative_quote_mint();
+    let user_pubkey = user.pubkey();
+
+    let deposit_amount = 1_000_000;
+
+    handle_deposit_with_quote_mint(
+        &mut lite_svm,
+        HandleDepositWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    // Caps and escrow deposit are in accounting unit
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    assert_eq!(presale_state.total_deposit, deposit_amount);
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.total_deposit, deposit_amount);
+    assert_eq!(
+        escrow_state.quote_mint_config,
+        derive_quote_mint_config(&presale_pubkey, &alternative_quote_mint, &presale::ID)
+    );
+
+    let vault_amount =
+        get_quote_mint_config_vault_amount(&lite_svm, &presale_pubkey, &alternative_quote_mint);
+    assert_eq!(
+        vault_amount,
+        deposit_amount * CONVERSION_RATIO_DENOMINATOR / CONVERSION_RATIO_NUMERATOR
+    );
+
+    let withdraw_amount = deposit_amount / 2;
+
+    handle_withdraw_with_quote_mint(
+        &mut lite_svm,
+        HandleWithdrawWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            amount: withdraw_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.total_deposit, deposit_amount - withdraw_amount);
+
+    let after_vault_amount =
+        get_quote_mint_config_vault_amount(&lite_svm, &presale_pubkey, &alternative_quote_mint);
+    assert_eq!(
+        vault_amount - after_vault_amount,
+        withdraw_amount * CONVERSION_RATIO_DENOMINATOR / CONVERSION_RATIO_NUMERATOR
+    );
+}
+
+#[test]
+fn test_deposit_with_quote_mint_escrow_mismatch() {
+    let QuoteMintPresaleSetup {
+        mut lite_svm,
+        user,
+        presale_pubkey,
+        alternative_quote_mint,
+    } = setup_presale_with_alternative_quote_mint();
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: 1_000_000,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    // Escrow is bound to the presale quote mint
+    let err = handle_deposit_with_quote_mint_err(
+        &mut lite_svm,
+        HandleDepositWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            max_amount: 1_000_000,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::EscrowQuoteMintMismatch;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_withdraw_remaining_quote_with_quote_mint_failed_presale() {
+    let QuoteMintPresaleSetup {
+        mut lite_svm,
+        user,
+        presale_pubkey,
+        alternative_quote_mint,
+    } = setup_presale_with_alternative_quote_mint();
+    let user_pubkey = user.pubkey();
+
+    let user_quote_token = get_associated_token_address(&user_pubkey, &alternative_quote_mint);
+    let before_user_quote_token: TokenAccount = lite_svm
+        .get_deserialized_account(&user_quote_token)
+        .unwrap();
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_deposit_with_quote_mint(
+        &mut lite_svm,
+        HandleDepositWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap - CONVERSION_RATIO_NUMERATOR,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    handle_withdraw_remaining_quote_with_quote_mint(
+        &mut lite_svm,
+        HandleWithdrawRemainingQuoteWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    // Refund is paid in the original mint
+    let vault_amount =
+        get_quote_mint_config_vault_amount(&lite_svm, &presale_pubkey, &alternative_quote_mint);
+    assert_eq!(vault_amount, 0);
+
+    let after_user_quote_token: TokenAccount = lite_svm
+        .get_deserialized_account(&user_quote_token)
+        .unwrap();
+    assert_eq!(
+        before_user_quote_token.amount,
+        after_user_quote_token.amount
+    );
+}
+
+#[test]
+fn test_creator_withdraw_quote_mint() {
+    let QuoteMintPresaleSetup {
+        mut lite_svm,
+        user,
+        presale_pubkey,
+        alternative_quote_mint,
+    } = setup_presale_with_alternative_quote_mint();
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_deposit_with_quote_mint(
+        &mut lite_svm,
+        HandleDepositWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let err = handle_creator_withdraw_quote_mint_err(
+        &mut lite_svm,
+        HandleCreatorWithdrawQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleNotCompleted;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    handle_creator_withdraw_quote_mint(
+        &mut lite_svm,
+        HandleCreatorWithdrawQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let vault_amount =
+        get_quote_mint_config_vault_amount(&lite_svm, &presale_pubkey, &alternative_quote_mint);
+    assert_eq!(vault_amount, 0);
+
+    let quote_mint_config =
+        derive_quote_mint_config(&presale_pubkey, &alternative_quote_mint, &presale::ID);
+    let quote_mint_config_state: QuoteMintConfig = lite_svm
+        .get_deserialized_zc_account(&quote_mint_config)
+        .unwrap();
+    assert!(quote_mint_config_state.has_creator_withdrawn());
+}
+
+#[test]
+fn test_deposit_with_quote_mint_with_referral_rejected() {
+    let QuoteMintPresaleSetup {
+        mut lite_svm,
+        user,
+        presale_pubkey,
+        alternative_quote_mint,
+    } = setup_presale_with_alternative_quote_mint();
+
+    let referrer = Pubkey::new_unique();
+
+    handle_create_referral(
+        &mut lite_svm,
+        HandleCreateReferralArgs {
+            presale: presale_pubkey,
+            referrer,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    // Deposit fee held by the alternative quote token vault isn't referred
+    let err = handle_deposit_with_quote_mint_with_referral_err(
+        &mut lite_svm,
+        HandleDepositWithQuoteMintArgs {
+            presale: presale_pubkey,
+            quote_mint: alternative_quote_mint,
+            owner: Rc::clone(&user),
+            max_amount: 1_000_000,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+        referrer,
+    );
+
+    let expected_err = presale::errors::PresaleError::ReferralNotSupportedWithQuoteMint;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
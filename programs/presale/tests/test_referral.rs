@@ -0,0 +1,248 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::signer::Signer;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_spl::{associated_token::get_associated_token_address, token_interface::TokenAccount};
+use helpers::*;
+use presale::{Presale, Referral, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX};
+use std::rc::Rc;
+
+#[test]
+fn test_deposit_with_referral_and_claim_referral_fee() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let referrer = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let referrer_pubkey = referrer.pubkey();
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale_with_deposit_fee(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    handle_create_referral(
+        &mut lite_svm,
+        HandleCreateReferralArgs {
+            presale: presale_pubkey,
+            referrer: referrer_pubkey,
+            payer: Rc::clone(&referrer),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_escrow_deposit_with_referral(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+        referrer_pubkey,
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let presale_registry = presale_state
+        .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+        .unwrap();
+
+    let referral = derive_referral(&presale_pubkey, &referrer_pubkey, &presale::ID);
+    let referral_state: Referral = lite_svm.get_deserialized_zc_account(&referral).unwrap();
+
+    assert_eq!(
+        referral_state.total_referred_deposit,
+        presale_state.total_deposit
+    );
+    assert_eq!(
+        referral_state.total_referred_deposit_fee,
+        presale_state.total_deposit_fee
+    );
+    assert_eq!(
+        presale_registry.total_referred_deposit_fee,
+        presale_state.total_deposit_fee
+    );
+
+    // Referral fee is only claimable once the presale completed
+    let err = handle_claim_referral_fee_err(
+        &mut lite_svm,
+        HandleClaimReferralFeeArgs {
+            presale: presale_pubkey,
+            referrer: Rc::clone(&referrer),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::PresaleNotCompleted;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    let expected_referral_fee =
+        presale_state.total_deposit_fee * u64::from(DEFAULT_REFERRAL_FEE_BPS) / 10_000;
+
+    let quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+
+    handle_creator_collect_fee(
+        &mut lite_svm,
+        HandleCreatorCollectFeeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let after_creator_collect_quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+
+    // Creator collect fee excludes the referral fee
+    assert_eq!(
+        quote_vault.amount - after_creator_collect_quote_vault.amount,
+        presale_state.total_deposit_fee - expected_referral_fee
+    );
+
+    handle_claim_referral_fee(
+        &mut lite_svm,
+        HandleClaimReferralFeeArgs {
+            presale: presale_pubkey,
+            referrer: Rc::clone(&referrer),
+        },
+    );
+
+    let referrer_quote_token: TokenAccount = lite_svm
+        .get_deserialized_account(&get_associated_token_address(&referrer_pubkey, &quote_mint))
+        .unwrap();
+    assert_eq!(referrer_quote_token.amount, expected_referral_fee);
+
+    let err = handle_claim_referral_fee_err(
+        &mut lite_svm,
+        HandleClaimReferralFeeArgs {
+            presale: presale_pubkey,
+            referrer: Rc::clone(&referrer),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::ReferralFeeAlreadyClaimed;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_deposit_with_self_referral() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let user_pubkey = user.pubkey();
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale_with_deposit_fee(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    handle_create_referral(
+        &mut lite_svm,
+        HandleCreateReferralArgs {
+            presale: presale_pubkey,
+            referrer: user_pubkey,
+            payer: Rc::clone(&user),
+        },
+    );
+
+    let err = handle_escrow_deposit_with_referral_err(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: 1_000_000,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+        user_pubkey,
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidReferral;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_deposit_native_with_referral() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let referrer = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let referrer_pubkey = referrer.pubkey();
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissionless_fixed_price_presale_with_deposit_fee(
+            &mut lite_svm,
+            mint,
+            anchor_spl::token::spl_token::native_mint::ID,
+            Rc::clone(&user),
+        );
+
+    handle_create_referral(
+        &mut lite_svm,
+        HandleCreateReferralArgs {
+            presale: presale_pubkey,
+            referrer: referrer_pubkey,
+            payer: Rc::clone(&referrer),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_deposit_native_with_referral(
+        &mut lite_svm,
+        HandleEscrowDepositNativeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+        referrer_pubkey,
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let referral = derive_referral(&presale_pubkey, &referrer_pubkey, &presale::ID);
+    let referral_state: Referral = lite_svm.get_deserialized_zc_account(&referral).unwrap();
+
+    assert_eq!(
+        referral_state.total_referred_deposit,
+        presale_state.total_deposit
+    );
+    assert_eq!(
+        referral_state.total_referred_deposit_fee,
+        presale_state.total_deposit_fee
+    );
+}
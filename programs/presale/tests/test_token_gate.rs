@@ -0,0 +1,323 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::signer::Signer;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id, token_interface::TokenAccount,
+};
+use helpers::*;
+use presale::{Escrow, Presale, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX, SCALE_MULTIPLIER};
+use std::rc::Rc;
+
+#[test]
+fn test_initialize_permissioned_with_token_gate_escrow() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let gating_mint = setup_context.setup_mint(0, 1_000);
+    let holder = setup_context.create_user();
+    let non_holder = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_token_gate_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    let minimum_holding_amount = 10;
+
+    handle_create_token_gate_config(
+        &mut lite_svm,
+        HandleCreateTokenGateConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            minimum_holding_amount,
+            q_deposit_cap_per_holding: 0,
+        },
+    );
+
+    transfer_token(
+        &mut lite_svm,
+        Rc::clone(&user),
+        holder.pubkey(),
+        gating_mint,
+        minimum_holding_amount,
+    );
+    transfer_token(
+        &mut lite_svm,
+        Rc::clone(&user),
+        non_holder.pubkey(),
+        gating_mint,
+        minimum_holding_amount - 1,
+    );
+
+    handle_create_permissioned_escrow_with_token_gate(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithTokenGateArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&holder),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            holding_amount: minimum_holding_amount,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let presale_registry = presale_state
+        .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+        .unwrap();
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &holder.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(
+        escrow_state.deposit_max_cap,
+        presale_registry.buyer_maximum_deposit_cap
+    );
+
+    let err = handle_create_permissioned_escrow_with_token_gate_err(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithTokenGateArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&non_holder),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            holding_amount: minimum_holding_amount - 1,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InsufficientGatingTokenHolding;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Escrow without locked holding is rejected, so the gating token vault can't be orphaned
+    let err = handle_create_permissioned_escrow_with_token_gate_err(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithTokenGateArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&non_holder),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            holding_amount: 0,
+        },
+    );
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
+
+#[test]
+fn test_initialize_permissioned_with_token_gate_escrow_with_holding_based_deposit_cap() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let gating_mint = setup_context.setup_mint(0, u64::MAX);
+    let holder = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_token_gate_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    // 1 quote token lamport of deposit cap per held gating token
+    handle_create_token_gate_config(
+        &mut lite_svm,
+        HandleCreateTokenGateConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            minimum_holding_amount: 1,
+            q_deposit_cap_per_holding: SCALE_MULTIPLIER,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let presale_registry = presale_state
+        .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+        .unwrap();
+
+    let holding_amount = presale_registry.buyer_minimum_deposit_cap;
+    transfer_token(
+        &mut lite_svm,
+        Rc::clone(&user),
+        holder.pubkey(),
+        gating_mint,
+        holding_amount,
+    );
+
+    handle_create_permissioned_escrow_with_token_gate(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithTokenGateArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&holder),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            holding_amount,
+        },
+    );
+
+    // Deposit cap is derived from the holding, bounded by the registry buyer maximum deposit cap
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &holder.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(
+        escrow_state.deposit_max_cap,
+        holding_amount.min(presale_registry.buyer_maximum_deposit_cap)
+    );
+}
+
+#[test]
+fn test_token_gate_holding_locked_until_presale_ends() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let gating_mint = setup_context.setup_mint(0, 1_000);
+    let holder = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_token_gate_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote_mint,
+            Rc::clone(&user),
+        );
+
+    let minimum_holding_amount = 10;
+
+    handle_create_token_gate_config(
+        &mut lite_svm,
+        HandleCreateTokenGateConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            minimum_holding_amount,
+            q_deposit_cap_per_holding: 0,
+        },
+    );
+
+    transfer_token(
+        &mut lite_svm,
+        Rc::clone(&user),
+        holder.pubkey(),
+        gating_mint,
+        minimum_holding_amount,
+    );
+
+    handle_create_permissioned_escrow_with_token_gate(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithTokenGateArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&holder),
+            gating_mint,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            holding_amount: minimum_holding_amount,
+        },
+    );
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &holder.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let gating_token_vault = derive_gating_token_vault(&escrow, &presale::ID);
+    let holder_gating_token = get_associated_token_address_with_program_id(
+        &holder.pubkey(),
+        &gating_mint,
+        &anchor_spl::token::ID,
+    );
+
+    // Holding is locked, so it can't back the escrow of another wallet
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.locked_gating_amount, minimum_holding_amount);
+
+    let vault_state: TokenAccount = lite_svm
+        .get_deserialized_account(&gating_token_vault)
+        .unwrap();
+    assert_eq!(vault_state.amount, minimum_holding_amount);
+
+    let holder_token_state: TokenAccount = lite_svm
+        .get_deserialized_account(&holder_gating_token)
+        .unwrap();
+    assert_eq!(holder_token_state.amount, 0);
+
+    let withdraw_args = HandleWithdrawGatingTokenArgs {
+        presale: presale_pubkey,
+        owner: Rc::clone(&holder),
+        gating_mint,
+        registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+    };
+
+    let err = handle_withdraw_gating_token_err(&mut lite_svm, withdraw_args.clone());
+
+    let expected_err = presale::errors::PresaleError::GatingTokenLocked;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    let close_escrow_args = HandleCloseEscrowArgs {
+        presale: presale_pubkey,
+        owner: Rc::clone(&holder),
+        registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+    };
+
+    let err = handle_close_escrow_err(&mut lite_svm, close_escrow_args.clone());
+
+    let expected_err = presale::errors::PresaleError::EscrowNotEmpty;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    handle_withdraw_gating_token(&mut lite_svm, withdraw_args);
+
+    let holder_token_state: TokenAccount = lite_svm
+        .get_deserialized_account(&holder_gating_token)
+        .unwrap();
+    assert_eq!(holder_token_state.amount, minimum_holding_amount);
+    assert!(lite_svm.get_account(&gating_token_vault).is_none());
+
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.locked_gating_amount, 0);
+
+    handle_close_escrow(&mut lite_svm, close_escrow_args);
+}
@@ -65,7 +65,7 @@ fn test_unsold_token_action_prorata_presale_with_zero_deposit_registry_burn_unso
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -179,7 +179,7 @@ fn test_unsold_token_action_prorata_presale_with_zero_deposit_registry_refund_un
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -306,7 +306,7 @@ fn test_unsold_token_action_fcfs_presale_with_zero_deposit_registry() {
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
@@ -433,7 +433,7 @@ fn test_unsold_token_action_fixed_price_presale_with_zero_deposit_registry() {
         },
     );
 
-    let tree_node_0 = merkle_tree.get_node(&user_pubkey);
+    let tree_node_0 = merkle_tree.get_node(&user_pubkey).unwrap();
 
     handle_create_permissioned_escrow_with_merkle_proof(
         &mut lite_svm,
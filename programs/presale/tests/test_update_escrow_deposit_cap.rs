@@ -0,0 +1,292 @@
+pub mod helpers;
+
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use helpers::*;
+use presale::{
+    CreateOperatorParams, Escrow, Presale, PresaleRegistry, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+};
+use std::rc::Rc;
+
+#[test]
+fn test_update_escrow_deposit_cap_with_operator() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_authority_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let operator = Rc::new(Keypair::new());
+    handle_create_operator(
+        &mut lite_svm,
+        HandleCreateOperatorArgs {
+            owner: Rc::clone(&user),
+            operator: operator.pubkey(),
+            params: CreateOperatorParams::default(),
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let PresaleRegistry {
+        buyer_maximum_deposit_cap,
+        ..
+    } = presale_state.presale_registries[DEFAULT_PERMISSIONLESS_REGISTRY_INDEX as usize];
+
+    handle_create_permissioned_escrow_with_operator(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap: buyer_maximum_deposit_cap / 2,
+        },
+    );
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: buyer_maximum_deposit_cap / 4,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+
+    // Raise the deposit cap
+    handle_update_escrow_deposit_cap_with_operator(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            deposit_cap: buyer_maximum_deposit_cap,
+        },
+    );
+
+    let escrow_state_after: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(
+        escrow_state_after.deposit_max_cap,
+        buyer_maximum_deposit_cap
+    );
+
+    // Can't exceed the registry maximum deposit cap
+    let err = handle_update_escrow_deposit_cap_with_operator_err(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            deposit_cap: buyer_maximum_deposit_cap + 1,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidDepositCap;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Can't lower below the deposited amount
+    let err = handle_update_escrow_deposit_cap_with_operator_err(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            deposit_cap: escrow_state.total_deposit - 1,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::DepositCapBelowTotalDeposit;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    // Lower the deposit cap to the deposited amount
+    handle_update_escrow_deposit_cap_with_operator(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithOperatorArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            vault_owner: user.pubkey(),
+            operator: Rc::clone(&operator),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            deposit_cap: escrow_state.total_deposit,
+        },
+    );
+
+    let escrow_state_after: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(
+        escrow_state_after.deposit_max_cap,
+        escrow_state.total_deposit
+    );
+}
+
+#[test]
+fn test_update_escrow_deposit_cap_with_merkle_proof() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote = anchor_spl::token::spl_token::native_mint::ID;
+
+    let HandleCreatePredefinedPresaleResponse { presale_pubkey, .. } =
+        handle_create_predefined_permissioned_with_merkle_proof_fixed_price_presale(
+            &mut lite_svm,
+            mint,
+            quote,
+            Rc::clone(&user),
+        );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let PresaleRegistry {
+        buyer_maximum_deposit_cap,
+        ..
+    } = presale_state.presale_registries[DEFAULT_PERMISSIONLESS_REGISTRY_INDEX as usize];
+
+    let merkle_tree_0 = build_merkle_tree(
+        vec![WhitelistWallet {
+            address: user.pubkey(),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap: buyer_maximum_deposit_cap / 2,
+        }],
+        0,
+    );
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree_0,
+        },
+    );
+
+    let tree_node = merkle_tree_0.get_node(&user.pubkey()).unwrap();
+    handle_create_permissioned_escrow_with_merkle_proof(
+        &mut lite_svm,
+        HandleCreatePermissionedEscrowWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_root_config: merkle_tree_0
+                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
+            registry_index: tree_node.registry_index,
+            max_deposit_cap: tree_node.deposit_cap,
+            proof: tree_node.proof.unwrap(),
+        },
+    );
+
+    // Next version of the tree upgrades the deposit cap
+    let merkle_tree_1 = build_merkle_tree(
+        vec![WhitelistWallet {
+            address: user.pubkey(),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            max_deposit_cap: buyer_maximum_deposit_cap,
+        }],
+        1,
+    );
+    handle_create_merkle_root_config(
+        &mut lite_svm,
+        HandleCreateMerkleRootConfigArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_tree: &merkle_tree_1,
+        },
+    );
+
+    let tree_node = merkle_tree_1.get_node(&user.pubkey()).unwrap();
+    let merkle_root_config =
+        merkle_tree_1.get_merkle_root_config_pubkey(presale_pubkey, &presale::ID);
+
+    // Deposit cap must match the leaf
+    let err = handle_update_escrow_deposit_cap_with_merkle_proof_err(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_root_config,
+            registry_index: tree_node.registry_index,
+            deposit_cap: tree_node.deposit_cap - 1,
+            proof: tree_node.proof.clone().unwrap(),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::InvalidMerkleProof;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+
+    handle_update_escrow_deposit_cap_with_merkle_proof(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_root_config,
+            registry_index: tree_node.registry_index,
+            deposit_cap: tree_node.deposit_cap,
+            proof: tree_node.proof.unwrap(),
+        },
+    );
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user.pubkey(),
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+    assert_eq!(escrow_state.deposit_max_cap, buyer_maximum_deposit_cap);
+    assert_eq!(escrow_state.merkle_root_config_version, 1);
+
+    // Previous version of the tree can't roll the deposit cap back
+    let tree_node = merkle_tree_0.get_node(&user.pubkey()).unwrap();
+    let err = handle_update_escrow_deposit_cap_with_merkle_proof_err(
+        &mut lite_svm,
+        HandleUpdateEscrowDepositCapWithMerkleProofArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            merkle_root_config: merkle_tree_0
+                .get_merkle_root_config_pubkey(presale_pubkey, &presale::ID),
+            registry_index: tree_node.registry_index,
+            deposit_cap: tree_node.deposit_cap,
+            proof: tree_node.proof.unwrap(),
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::MerkleRootConfigOutdated;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
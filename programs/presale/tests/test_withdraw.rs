@@ -1,11 +1,14 @@
 pub mod helpers;
 
-use anchor_client::solana_sdk::signer::Signer;
+use anchor_client::solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
 use anchor_lang::error::ERROR_CODE_OFFSET;
 use anchor_spl::{
     associated_token::get_associated_token_address_with_program_id, token_interface::TokenAccount,
 };
 use helpers::*;
+use litesvm::LiteSVM;
 use presale::{
     Escrow, Presale, PresaleRegistryArgs, WhitelistMode, DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
 };
@@ -592,3 +595,454 @@ fn test_withdraw_fixed_price_presale_with_withdraw_disabled() {
     let err_str = format!("Error Number: {}.", err_code);
     assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
 }
+
+#[test]
+fn test_withdraw_prorata_presale_with_withdraw_penalty() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+    let withdraw_penalty_bps = 1_000;
+
+    let mut wrapper = create_default_prorata_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    wrapper.args.params.presale_params.withdraw_penalty_bps = withdraw_penalty_bps;
+
+    let instructions = wrapper.to_instructions();
+
+    process_transaction(&mut lite_svm, &instructions, Some(&user.pubkey()), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    let deposit_amount = 1_000_000;
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let before_presale_state = lite_svm
+        .get_deserialized_zc_account::<Presale>(&presale_pubkey)
+        .unwrap();
+
+    // Penalty is half of the maximum at the middle of the presale
+    let presale_duration =
+        before_presale_state.presale_end_time - before_presale_state.presale_start_time;
+    warp_time(
+        &mut lite_svm,
+        before_presale_state.presale_start_time + presale_duration / 2,
+    );
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let before_escrow_state = lite_svm
+        .get_deserialized_zc_account::<Escrow>(&escrow)
+        .unwrap();
+    let before_quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&before_presale_state.quote_token_vault)
+        .unwrap();
+
+    let withdraw_amount = before_escrow_state.total_deposit / 2;
+
+    handle_escrow_withdraw(
+        &mut lite_svm,
+        HandleEscrowWithdrawArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            amount: withdraw_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let expected_penalty = withdraw_amount * u64::from(withdraw_penalty_bps) / 10_000 / 2;
+
+    let after_escrow_state = lite_svm
+        .get_deserialized_zc_account::<Escrow>(&escrow)
+        .unwrap();
+    let after_presale_state = lite_svm
+        .get_deserialized_zc_account::<Presale>(&presale_pubkey)
+        .unwrap();
+    let after_quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&before_presale_state.quote_token_vault)
+        .unwrap();
+
+    assert_eq!(
+        before_escrow_state.total_deposit - after_escrow_state.total_deposit,
+        withdraw_amount
+    );
+
+    // Penalty is retained apart from the escrow deposit fee
+    assert_eq!(
+        after_escrow_state.total_deposit_fee,
+        before_escrow_state.total_deposit_fee
+    );
+    assert_eq!(
+        after_presale_state.total_deposit_fee,
+        before_presale_state.total_deposit_fee
+    );
+    assert_eq!(
+        after_presale_state.total_withdraw_penalty - before_presale_state.total_withdraw_penalty,
+        expected_penalty
+    );
+    assert_eq!(
+        after_presale_state.presale_registries[usize::from(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX)]
+            .total_withdraw_penalty,
+        expected_penalty
+    );
+    assert_eq!(
+        before_quote_vault.amount - after_quote_vault.amount,
+        withdraw_amount - expected_penalty
+    );
+}
+
+fn create_prorata_presale_with_withdraw_penalty(
+    lite_svm: &mut LiteSVM,
+    mint: Pubkey,
+    user: Rc<Keypair>,
+    withdraw_penalty_bps: u16,
+) -> Pubkey {
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+
+    let mut wrapper = create_default_prorata_presale_args_wrapper(
+        mint,
+        quote_mint,
+        lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+    wrapper.args.params.presale_params.withdraw_penalty_bps = withdraw_penalty_bps;
+
+    let instructions = wrapper.to_instructions();
+    process_transaction(lite_svm, &instructions, Some(&user_pubkey), &[&user]).unwrap();
+
+    derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID)
+}
+
+fn warp_to_presale_middle(lite_svm: &mut LiteSVM, presale_pubkey: &Pubkey) {
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(presale_pubkey)
+        .unwrap();
+    let presale_duration = presale_state.presale_end_time - presale_state.presale_start_time;
+    warp_time(
+        lite_svm,
+        presale_state.presale_start_time + presale_duration / 2,
+    );
+}
+
+fn get_quote_token_balance(lite_svm: &LiteSVM, owner: &Pubkey, quote_mint: &Pubkey) -> u64 {
+    let quote_token =
+        get_associated_token_address_with_program_id(owner, quote_mint, &anchor_spl::token::ID);
+    let quote_token_state: TokenAccount = lite_svm.get_deserialized_account(&quote_token).unwrap();
+    quote_token_state.amount
+}
+
+#[test]
+fn test_withdraw_penalty_not_refunded_on_failed_prorata_presale() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let user_pubkey = user.pubkey();
+    let withdraw_penalty_bps = 1_000;
+
+    let presale_pubkey = create_prorata_presale_with_withdraw_penalty(
+        &mut lite_svm,
+        mint,
+        Rc::clone(&user),
+        withdraw_penalty_bps,
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: presale_state.presale_minimum_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    warp_to_presale_middle(&mut lite_svm, &presale_pubkey);
+
+    // Withdraw below the minimum cap to fail the presale
+    let withdraw_amount = presale_state.presale_minimum_cap / 2;
+    handle_escrow_withdraw(
+        &mut lite_svm,
+        HandleEscrowWithdrawArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            amount: withdraw_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let expected_penalty = withdraw_amount * u64::from(withdraw_penalty_bps) / 10_000 / 2;
+    assert!(expected_penalty > 0);
+    assert_eq!(presale_state.total_withdraw_penalty, expected_penalty);
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    let before_balance =
+        get_quote_token_balance(&lite_svm, &user_pubkey, &presale_state.quote_mint);
+
+    handle_escrow_withdraw_remaining_quote(
+        &mut lite_svm,
+        HandleEscrowWithdrawRemainingQuoteArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let after_balance = get_quote_token_balance(&lite_svm, &user_pubkey, &presale_state.quote_mint);
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+
+    // Only the remaining deposit is refunded, the penalty stays in the vault
+    assert_eq!(escrow_state.total_deposit_fee, 0);
+    assert_eq!(after_balance - before_balance, escrow_state.total_deposit);
+
+    handle_creator_collect_fee(
+        &mut lite_svm,
+        HandleCreatorCollectFeeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let collected_balance =
+        get_quote_token_balance(&lite_svm, &user_pubkey, &presale_state.quote_mint);
+    assert_eq!(collected_balance - after_balance, expected_penalty);
+
+    let quote_vault: TokenAccount = lite_svm
+        .get_deserialized_account(&presale_state.quote_token_vault)
+        .unwrap();
+    assert_eq!(quote_vault.amount, 0);
+}
+
+#[test]
+fn test_withdraw_penalty_not_refunded_on_oversubscribed_prorata_presale() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let user_1 = setup_context.create_user();
+    let SetupContext { mut lite_svm, user } = setup_context;
+    let user_pubkey = user.pubkey();
+    let withdraw_penalty_bps = 1_000;
+
+    transfer_sol(
+        &mut lite_svm,
+        Rc::clone(&user),
+        user_1.pubkey(),
+        2 * LAMPORTS_PER_SOL,
+    );
+
+    let presale_pubkey = create_prorata_presale_with_withdraw_penalty(
+        &mut lite_svm,
+        mint,
+        Rc::clone(&user),
+        withdraw_penalty_bps,
+    );
+
+    for owner in [&user, &user_1] {
+        handle_escrow_deposit(
+            &mut lite_svm,
+            HandleEscrowDepositArgs {
+                presale: presale_pubkey,
+                owner: Rc::clone(owner),
+                max_amount: LAMPORTS_PER_SOL,
+                registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+            },
+        );
+    }
+
+    warp_to_presale_middle(&mut lite_svm, &presale_pubkey);
+
+    let withdraw_amount = LAMPORTS_PER_SOL / 2;
+    handle_escrow_withdraw(
+        &mut lite_svm,
+        HandleEscrowWithdrawArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user_1),
+            amount: withdraw_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+    let expected_penalty = withdraw_amount * u64::from(withdraw_penalty_bps) / 10_000 / 2;
+    assert!(presale_state.total_deposit > presale_state.presale_maximum_cap);
+    assert_eq!(presale_state.total_withdraw_penalty, expected_penalty);
+
+    warp_to_presale_end(&mut lite_svm, &presale_state);
+
+    let before_balance =
+        get_quote_token_balance(&lite_svm, &user_pubkey, &presale_state.quote_mint);
+
+    handle_escrow_withdraw_remaining_quote(
+        &mut lite_svm,
+        HandleEscrowWithdrawRemainingQuoteArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let after_balance = get_quote_token_balance(&lite_svm, &user_pubkey, &presale_state.quote_mint);
+
+    let escrow = derive_escrow(
+        &presale_pubkey,
+        &user_pubkey,
+        DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        &presale::ID,
+    );
+    let escrow_state: Escrow = lite_svm.get_deserialized_zc_account(&escrow).unwrap();
+
+    // Refund of the oversubscription carries no share of the penalty
+    let refund_amount = (presale_state.total_deposit - presale_state.presale_maximum_cap)
+        * escrow_state.total_deposit
+        / presale_state.total_deposit;
+    assert_eq!(after_balance - before_balance, refund_amount);
+
+    handle_creator_collect_fee(
+        &mut lite_svm,
+        HandleCreatorCollectFeeArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+        },
+    );
+
+    let collected_balance =
+        get_quote_token_balance(&lite_svm, &user_pubkey, &presale_state.quote_mint);
+    assert_eq!(collected_balance - after_balance, expected_penalty);
+}
+
+#[test]
+fn test_withdraw_fixed_price_presale_within_withdraw_cutoff() {
+    let mut setup_context = SetupContext::initialize();
+    let mint = setup_context.setup_mint(
+        DEFAULT_BASE_TOKEN_DECIMALS,
+        1_000_000_000 * 10u64.pow(DEFAULT_BASE_TOKEN_DECIMALS.into()),
+    );
+    let SetupContext { mut lite_svm, user } = setup_context;
+
+    let quote_mint = anchor_spl::token::spl_token::native_mint::ID;
+    let user_pubkey = user.pubkey();
+    let withdraw_cutoff_duration = 30;
+
+    let mut wrapper = create_default_fixed_price_presale_args_wrapper(
+        mint,
+        quote_mint,
+        &lite_svm,
+        WhitelistMode::Permissionless,
+        Rc::clone(&user),
+        user_pubkey,
+    );
+
+    wrapper
+        .presale_params_wrapper
+        .args
+        .params
+        .presale_params
+        .withdraw_cutoff_duration = withdraw_cutoff_duration;
+
+    let instructions = wrapper.to_instructions();
+
+    process_transaction(&mut lite_svm, &instructions, Some(&user.pubkey()), &[&user]).unwrap();
+
+    let presale_pubkey = derive_presale(&mint, &quote_mint, &user_pubkey, &presale::ID);
+
+    let presale_state: Presale = lite_svm
+        .get_deserialized_zc_account(&presale_pubkey)
+        .unwrap();
+
+    let presale_registry = presale_state
+        .get_presale_registry(DEFAULT_PERMISSIONLESS_REGISTRY_INDEX.into())
+        .unwrap();
+
+    let deposit_amount = presale_registry.buyer_minimum_deposit_cap * 2;
+
+    handle_escrow_deposit(
+        &mut lite_svm,
+        HandleEscrowDepositArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            max_amount: deposit_amount,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let withdraw_cutoff_time = presale_state.presale_end_time - withdraw_cutoff_duration;
+
+    // Withdraw is still allowed right before the cutoff window
+    warp_time(&mut lite_svm, withdraw_cutoff_time - 1);
+
+    handle_escrow_withdraw(
+        &mut lite_svm,
+        HandleEscrowWithdrawArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            amount: presale_registry.buyer_minimum_deposit_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    warp_time(&mut lite_svm, withdraw_cutoff_time);
+
+    let err = handle_escrow_withdraw_err(
+        &mut lite_svm,
+        HandleEscrowWithdrawArgs {
+            presale: presale_pubkey,
+            owner: Rc::clone(&user),
+            amount: presale_registry.buyer_minimum_deposit_cap,
+            registry_index: DEFAULT_PERMISSIONLESS_REGISTRY_INDEX,
+        },
+    );
+
+    let expected_err = presale::errors::PresaleError::WithdrawCutoffReached;
+    let err_code = ERROR_CODE_OFFSET + expected_err as u32;
+    let err_str = format!("Error Number: {}.", err_code);
+    assert!(err.meta.logs.iter().any(|log| log.contains(&err_str)));
+}
@@ -0,0 +1,37 @@
+use hyper::StatusCode;
+use merkle_tree::error::MerkleTreeError;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProofServerError {
+    #[error("Route not found")]
+    RouteNotFound,
+    #[error("Invalid wallet {0}")]
+    InvalidWallet(String),
+    #[error("Wallet {0} is not whitelisted")]
+    WalletNotWhitelisted(Pubkey),
+    #[error("Operator signing is disabled")]
+    SigningDisabled,
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("Merkle Tree Error: {0}")]
+    MerkleTreeError(#[from] MerkleTreeError),
+}
+
+impl ProofServerError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ProofServerError::RouteNotFound | ProofServerError::WalletNotWhitelisted(_) => {
+                StatusCode::NOT_FOUND
+            }
+            ProofServerError::InvalidWallet(_)
+            | ProofServerError::InvalidRequest(_)
+            | ProofServerError::InvalidTransaction(_) => StatusCode::BAD_REQUEST,
+            ProofServerError::SigningDisabled => StatusCode::FORBIDDEN,
+            ProofServerError::MerkleTreeError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
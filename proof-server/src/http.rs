@@ -0,0 +1,141 @@
+use std::{convert::Infallible, net::TcpListener, result, str::FromStr, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyper::{
+    body::HttpBody,
+    header::{
+        HeaderValue, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE,
+    },
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
+
+use crate::{
+    error::ProofServerError::{self, InvalidRequest},
+    proof_server::{ProofServer, Result},
+};
+
+const PROOF_ROUTE_PREFIX: &str = "/proof/";
+const SIGN_ROUTE: &str = "/sign";
+
+// Legacy transaction is at most 1232 bytes, this leaves room for the base64 encoding and the json
+const MAX_REQUEST_BODY_SIZE: usize = 4096;
+
+/// Body of `POST /sign` request and response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignTransactionBody {
+    /// Base64 encoded bincode serialized legacy transaction
+    pub transaction: String,
+}
+
+impl SignTransactionBody {
+    pub fn new(transaction: &Transaction) -> Self {
+        // Serializing a transaction doesn't fail
+        Self {
+            transaction: STANDARD.encode(bincode::serialize(transaction).unwrap()),
+        }
+    }
+
+    pub fn to_transaction(&self) -> Result<Transaction> {
+        let bytes = STANDARD
+            .decode(&self.transaction)
+            .map_err(|err| InvalidRequest(err.to_string()))?;
+
+        bincode::deserialize(&bytes).map_err(|err| InvalidRequest(err.to_string()))
+    }
+}
+
+/// Serve on the listener until the server fails.
+/// `GET /proof/<wallet>` returns the user proof, and `POST /sign` returns the transaction partially signed by the operator.
+pub async fn serve(proof_server: Arc<ProofServer>, listener: TcpListener) -> hyper::Result<()> {
+    let make_service = make_service_fn(move |_| {
+        let proof_server = proof_server.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                handle_request(proof_server.clone(), request)
+            }))
+        }
+    });
+
+    Server::from_tcp(listener)?.serve(make_service).await
+}
+
+async fn handle_request(
+    proof_server: Arc<ProofServer>,
+    request: Request<Body>,
+) -> result::Result<Response<Body>, Infallible> {
+    let path = request.uri().path().to_string();
+
+    let result = match (request.method(), path.as_str()) {
+        // CORS preflight of browser clients
+        (&Method::OPTIONS, _) => {
+            return Ok(build_response(StatusCode::NO_CONTENT, Body::empty()));
+        }
+        (&Method::GET, path) if path.starts_with(PROOF_ROUTE_PREFIX) => {
+            get_user_proof(&proof_server, &path[PROOF_ROUTE_PREFIX.len()..])
+        }
+        (&Method::POST, SIGN_ROUTE) => sign_transaction(&proof_server, request).await,
+        _ => Err(ProofServerError::RouteNotFound),
+    };
+
+    Ok(match result {
+        Ok(body) => build_response(StatusCode::OK, Body::from(body.to_string())),
+        Err(err) => build_response(
+            err.status_code(),
+            Body::from(json!({ "error": err.to_string() }).to_string()),
+        ),
+    })
+}
+
+fn get_user_proof(proof_server: &ProofServer, wallet: &str) -> Result<serde_json::Value> {
+    let wallet = Pubkey::from_str(wallet)
+        .map_err(|_| ProofServerError::InvalidWallet(wallet.to_string()))?;
+    let user_proof = proof_server.get_user_proof(&wallet)?;
+
+    serde_json::to_value(user_proof).map_err(|err| InvalidRequest(err.to_string()))
+}
+
+async fn sign_transaction(
+    proof_server: &ProofServer,
+    request: Request<Body>,
+) -> Result<serde_json::Value> {
+    let body = read_body(request.into_body()).await?;
+    let sign_transaction_body: SignTransactionBody =
+        serde_json::from_slice(&body).map_err(|err| InvalidRequest(err.to_string()))?;
+
+    let transaction = proof_server.sign_transaction(sign_transaction_body.to_transaction()?)?;
+
+    serde_json::to_value(SignTransactionBody::new(&transaction))
+        .map_err(|err| InvalidRequest(err.to_string()))
+}
+
+async fn read_body(mut body: Body) -> Result<Vec<u8>> {
+    let mut bytes = vec![];
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|err| InvalidRequest(err.to_string()))?;
+        if bytes.len() + chunk.len() > MAX_REQUEST_BODY_SIZE {
+            return Err(InvalidRequest("Request body is too large".to_string()));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+fn build_response(status: StatusCode, body: Body) -> Response<Body> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+    headers.insert(
+        ACCESS_CONTROL_ALLOW_HEADERS,
+        HeaderValue::from_static("content-type"),
+    );
+
+    response
+}
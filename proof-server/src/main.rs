@@ -0,0 +1,78 @@
+use std::{net::TcpListener, path::PathBuf, process::exit, sync::Arc};
+
+use clap::Parser;
+use merkle_tree::config_merkle_tree::ConfigMerkleTree;
+use proof_server::{
+    http::serve,
+    proof_server::{ProofServer, ProofServerConfig},
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+
+#[derive(Parser)]
+#[command(
+    about = "Serve merkle proofs and operator signatures of a permissioned presale, to be set as server_url of PermissionedServerMetadata"
+)]
+struct Cli {
+    /// Tree file written by the merkle-tree binary. Files with the bin extension are read as compact tree
+    #[arg(long)]
+    tree: PathBuf,
+    /// Presale of the merkle root config
+    #[arg(long)]
+    presale: Pubkey,
+    #[arg(long, default_value_t = presale::ID)]
+    program_id: Pubkey,
+    /// Keypair of the operator owner. POST /sign is disabled when not set
+    #[arg(long)]
+    operator_keypair: Option<PathBuf>,
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    bind: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let tree = ConfigMerkleTree::new_from_tree_file(&cli.tree).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+
+    let operator: Option<Keypair> = cli.operator_keypair.map(|path| {
+        read_keypair_file(&path).unwrap_or_else(|err| {
+            eprintln!(
+                "Failed to read operator keypair {}: {}",
+                path.display(),
+                err
+            );
+            exit(1);
+        })
+    });
+    if let Some(operator) = operator.as_ref() {
+        println!("operator: {}", operator.pubkey());
+    }
+
+    let proof_server = ProofServer::new(
+        &tree,
+        ProofServerConfig {
+            presale: cli.presale,
+            program_id: cli.program_id,
+            operator,
+        },
+    );
+    println!("merkle root config: {}", proof_server.merkle_root_config());
+
+    let listener = TcpListener::bind(&cli.bind).unwrap_or_else(|err| {
+        eprintln!("Failed to bind {}: {}", cli.bind, err);
+        exit(1);
+    });
+    println!("listening on {}", cli.bind);
+
+    if let Err(err) = serve(Arc::new(proof_server), listener).await {
+        eprintln!("{}", err);
+        exit(1);
+    }
+}
@@ -0,0 +1,183 @@
+use std::{collections::HashMap, result};
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use merkle_tree::{
+    config_merkle_tree::{ConfigMerkleTree, UserProof},
+    tree_node::TreeNode,
+};
+use presale::instruction::CreatePermissionedEscrowWithCreator;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
+
+use crate::error::ProofServerError::{self, InvalidTransaction};
+
+pub type Result<T> = result::Result<T, ProofServerError>;
+
+// Account positions of CreatePermissionedEscrowWithCreatorCtx
+const PRESALE_ACCOUNT_INDEX: usize = 0;
+const OWNER_ACCOUNT_INDEX: usize = 2;
+const OPERATOR_OWNER_ACCOUNT_INDEX: usize = 4;
+const PAYER_ACCOUNT_INDEX: usize = 5;
+
+pub struct ProofServerConfig {
+    pub presale: Pubkey,
+    pub program_id: Pubkey,
+    /// Operator owner key partially signing `create_permissioned_escrow_with_creator`. Signing is disabled when None
+    pub operator: Option<Keypair>,
+}
+
+/// Serves the whitelist of a presale loaded from a merkle tree file.
+/// The tree nodes are the proofs of `create_permissioned_escrow_with_merkle_proof`, and the allowed escrow params of `create_permissioned_escrow_with_creator`.
+pub struct ProofServer {
+    tree_nodes: HashMap<Pubkey, TreeNode>,
+    merkle_root_config: Pubkey,
+    presale: Pubkey,
+    program_id: Pubkey,
+    operator: Option<Keypair>,
+}
+
+impl ProofServer {
+    pub fn new(tree: &ConfigMerkleTree, config: ProofServerConfig) -> Self {
+        let ProofServerConfig {
+            presale,
+            program_id,
+            operator,
+        } = config;
+
+        Self {
+            tree_nodes: tree.convert_to_hashmap(),
+            merkle_root_config: tree.get_merkle_root_config_pubkey(presale, &program_id),
+            presale,
+            program_id,
+            operator,
+        }
+    }
+
+    pub fn merkle_root_config(&self) -> Pubkey {
+        self.merkle_root_config
+    }
+
+    fn get_node(&self, wallet: &Pubkey) -> Result<&TreeNode> {
+        self.tree_nodes
+            .get(wallet)
+            .ok_or(ProofServerError::WalletNotWhitelisted(*wallet))
+    }
+
+    pub fn get_user_proof(&self, wallet: &Pubkey) -> Result<UserProof> {
+        Ok(self
+            .get_node(wallet)?
+            .to_user_proof(&self.merkle_root_config)?)
+    }
+
+    /// Partially sign the transaction with the operator key. The operator only signs `create_permissioned_escrow_with_creator` of the presale
+    /// matching the whitelisted registry index and deposit cap, and never pays for the transaction.
+    pub fn sign_transaction(&self, mut transaction: Transaction) -> Result<Transaction> {
+        let operator = self
+            .operator
+            .as_ref()
+            .ok_or(ProofServerError::SigningDisabled)?;
+        let operator_owner = operator.pubkey();
+        let message = &transaction.message;
+
+        if message.account_keys.first() == Some(&operator_owner) {
+            return Err(InvalidTransaction(
+                "Operator can't be the fee payer".to_string(),
+            ));
+        }
+
+        let mut create_escrow_count = 0;
+        for instruction in message.instructions.iter() {
+            let program_id = message
+                .account_keys
+                .get(usize::from(instruction.program_id_index))
+                .ok_or_else(|| InvalidTransaction("Invalid program id index".to_string()))?;
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|index| {
+                    message
+                        .account_keys
+                        .get(usize::from(*index))
+                        .copied()
+                        .ok_or_else(|| InvalidTransaction("Invalid account index".to_string()))
+                })
+                .collect::<Result<Vec<Pubkey>>>()?;
+
+            if *program_id != operator_owner && !accounts.contains(&operator_owner) {
+                continue;
+            }
+
+            if *program_id != self.program_id
+                || !instruction
+                    .data
+                    .starts_with(CreatePermissionedEscrowWithCreator::DISCRIMINATOR)
+            {
+                return Err(InvalidTransaction(
+                    "Operator only signs create_permissioned_escrow_with_creator".to_string(),
+                ));
+            }
+
+            let CreatePermissionedEscrowWithCreator { params } =
+                CreatePermissionedEscrowWithCreator::try_from_slice(
+                    &instruction.data[CreatePermissionedEscrowWithCreator::DISCRIMINATOR.len()..],
+                )
+                .map_err(|err| InvalidTransaction(err.to_string()))?;
+
+            self.validate_create_escrow_accounts(&accounts, &operator_owner)?;
+
+            let tree_node = self.get_node(&accounts[OWNER_ACCOUNT_INDEX])?;
+            if tree_node.registry_index != params.registry_index
+                || tree_node.deposit_cap != params.deposit_cap
+                || tree_node.get_terms_override().is_some()
+            {
+                return Err(InvalidTransaction(format!(
+                    "Escrow params of {} don't match the whitelist",
+                    tree_node.escrow_owner
+                )));
+            }
+
+            create_escrow_count += 1;
+        }
+
+        if create_escrow_count == 0 {
+            return Err(InvalidTransaction(
+                "Missing create_permissioned_escrow_with_creator".to_string(),
+            ));
+        }
+
+        let recent_blockhash = transaction.message.recent_blockhash;
+        transaction
+            .try_partial_sign(&[operator], recent_blockhash)
+            .map_err(|err| InvalidTransaction(err.to_string()))?;
+
+        Ok(transaction)
+    }
+
+    fn validate_create_escrow_accounts(
+        &self,
+        accounts: &[Pubkey],
+        operator_owner: &Pubkey,
+    ) -> Result<()> {
+        if accounts.len() <= PAYER_ACCOUNT_INDEX {
+            return Err(InvalidTransaction("Missing accounts".to_string()));
+        }
+
+        if accounts[PRESALE_ACCOUNT_INDEX] != self.presale {
+            return Err(InvalidTransaction(format!(
+                "Presale {} is not served",
+                accounts[PRESALE_ACCOUNT_INDEX]
+            )));
+        }
+
+        // The operator key must not be the payer or any other account of the instruction
+        let is_operator_misplaced = accounts.iter().enumerate().any(|(index, account)| {
+            (account == operator_owner) != (index == OPERATOR_OWNER_ACCOUNT_INDEX)
+        });
+        if is_operator_misplaced {
+            return Err(InvalidTransaction(
+                "Operator must only be the operator owner account".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::{net::TcpListener, sync::Arc};
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use hyper::{body::to_bytes, Body, Client, Method, Request, StatusCode};
+use merkle_tree::{
+    config_merkle_tree::{ConfigMerkleTree, UserProof},
+    tree_node::TreeNode,
+};
+use presale::CreatePermissionedEscrowWithCreatorParams;
+use proof_server::{
+    http::{serve, SignTransactionBody},
+    proof_server::{ProofServer, ProofServerConfig},
+};
+use solana_sdk::{
+    hash::Hash, instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_program, transaction::Transaction,
+};
+
+struct TestServer {
+    url: String,
+    presale: Pubkey,
+    operator: Pubkey,
+    tree: ConfigMerkleTree,
+}
+
+fn start_server(escrow_owners: &[Pubkey]) -> TestServer {
+    let tree = ConfigMerkleTree::new(
+        escrow_owners
+            .iter()
+            .map(|escrow_owner| TreeNode::new(*escrow_owner, 0, 1000))
+            .collect(),
+        0,
+    )
+    .unwrap();
+
+    let presale = Pubkey::new_unique();
+    let operator = Keypair::new();
+    let operator_pubkey = operator.pubkey();
+
+    let proof_server = ProofServer::new(
+        &tree,
+        ProofServerConfig {
+            presale,
+            program_id: presale::ID,
+            operator: Some(operator),
+        },
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(serve(Arc::new(proof_server), listener));
+
+    TestServer {
+        url,
+        presale,
+        operator: operator_pubkey,
+        tree,
+    }
+}
+
+fn create_permissioned_escrow_with_creator_ix(
+    presale: Pubkey,
+    owner: Pubkey,
+    operator: Pubkey,
+    deposit_cap: u64,
+) -> Instruction {
+    let creator = Pubkey::new_unique();
+    let data = presale::instruction::CreatePermissionedEscrowWithCreator {
+        params: CreatePermissionedEscrowWithCreatorParams {
+            registry_index: 0,
+            deposit_cap,
+            ..Default::default()
+        },
+    }
+    .data();
+
+    let accounts = presale::accounts::CreatePermissionedEscrowWithCreatorCtx {
+        presale,
+        escrow: Pubkey::find_program_address(
+            &[
+                presale::seeds::ESCROW_PREFIX,
+                presale.as_ref(),
+                owner.as_ref(),
+                0u8.to_le_bytes().as_ref(),
+            ],
+            &presale::ID,
+        )
+        .0,
+        owner,
+        operator: Pubkey::find_program_address(
+            &[
+                presale::seeds::OPERATOR_PREFIX,
+                creator.as_ref(),
+                operator.as_ref(),
+                Pubkey::default().as_ref(),
+            ],
+            &presale::ID,
+        )
+        .0,
+        operator_owner: operator,
+        payer: owner,
+        system_program: system_program::ID,
+        event_authority: Pubkey::find_program_address(&[b"__event_authority"], &presale::ID).0,
+        program: presale::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: presale::ID,
+        accounts,
+        data,
+    }
+}
+
+async fn send_request(request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let response = Client::new().request(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn request_sign_transaction(
+    url: &str,
+    transaction: &Transaction,
+) -> (StatusCode, serde_json::Value) {
+    let body = serde_json::to_string(&SignTransactionBody::new(transaction)).unwrap();
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("{}/sign", url))
+        .body(Body::from(body))
+        .unwrap();
+
+    send_request(request).await
+}
+
+#[tokio::test]
+async fn test_get_user_proof() {
+    let wallet = Pubkey::new_unique();
+    let server = start_server(&[wallet, Pubkey::new_unique()]);
+
+    let request = Request::get(format!("{}/proof/{}", server.url, wallet))
+        .body(Body::empty())
+        .unwrap();
+    let (status, body) = send_request(request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let user_proof: UserProof = serde_json::from_value(body).unwrap();
+    let tree_node = server.tree.get_node(&wallet).unwrap();
+    assert_eq!(
+        user_proof.merkle_root_config,
+        server
+            .tree
+            .get_merkle_root_config_pubkey(server.presale, &presale::ID)
+            .to_string()
+    );
+    assert_eq!(user_proof.max_cap, tree_node.deposit_cap);
+    assert_eq!(Some(user_proof.proof), tree_node.proof);
+
+    let request = Request::get(format!("{}/proof/{}", server.url, Pubkey::new_unique()))
+        .body(Body::empty())
+        .unwrap();
+    let (status, _) = send_request(request).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let request = Request::get(format!("{}/proof/abc", server.url))
+        .body(Body::empty())
+        .unwrap();
+    let (status, _) = send_request(request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_sign_create_permissioned_escrow_with_creator() {
+    let owner = Keypair::new();
+    let server = start_server(&[owner.pubkey()]);
+
+    let instruction = create_permissioned_escrow_with_creator_ix(
+        server.presale,
+        owner.pubkey(),
+        server.operator,
+        1000,
+    );
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&owner.pubkey()));
+    transaction.partial_sign(&[&owner], Hash::new_unique());
+
+    let (status, body) = request_sign_transaction(&server.url, &transaction).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let sign_transaction_body: SignTransactionBody = serde_json::from_value(body).unwrap();
+    let signed_transaction = sign_transaction_body.to_transaction().unwrap();
+    assert!(signed_transaction.is_signed());
+    signed_transaction.verify().unwrap();
+}
+
+#[tokio::test]
+async fn test_sign_create_permissioned_escrow_with_creator_rejected() {
+    let owner = Keypair::new();
+    let server = start_server(&[owner.pubkey()]);
+
+    // Deposit cap doesn't match the whitelist
+    let instruction = create_permissioned_escrow_with_creator_ix(
+        server.presale,
+        owner.pubkey(),
+        server.operator,
+        1001,
+    );
+    let transaction = Transaction::new_with_payer(&[instruction], Some(&owner.pubkey()));
+    let (status, _) = request_sign_transaction(&server.url, &transaction).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Wallet isn't whitelisted
+    let wallet = Pubkey::new_unique();
+    let instruction =
+        create_permissioned_escrow_with_creator_ix(server.presale, wallet, server.operator, 1000);
+    let transaction = Transaction::new_with_payer(&[instruction], Some(&wallet));
+    let (status, _) = request_sign_transaction(&server.url, &transaction).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Operator pays for the transaction
+    let instruction = create_permissioned_escrow_with_creator_ix(
+        server.presale,
+        owner.pubkey(),
+        server.operator,
+        1000,
+    );
+    let transaction = Transaction::new_with_payer(&[instruction], Some(&server.operator));
+    let (status, _) = request_sign_transaction(&server.url, &transaction).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}